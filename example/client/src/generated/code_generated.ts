@@ -41,15 +41,6 @@ export interface Chatroom {
 	status: number,
 }
 
-export function serializeChatroom(obj: Chatroom): any[] | null {
-	return [
-		obj.id,
-		obj.messages.map(function (nested) { return serializeMessage(nested); }),
-		obj.name,
-		obj.status
-	];
-}
-
 export function deserializeChatroom(obj: any): Chatroom | null {
 	if (obj == null) { return null; }
 	return {
@@ -72,25 +63,12 @@ export function serializeGetRoomsRequest(obj: GetRoomsRequest): any[] | null {
 	];
 }
 
-export function deserializeGetRoomsRequest(obj: any): GetRoomsRequest | null {
-	if (obj == null) { return null; }
-	return {
-		withStatus: obj[0]
-	};
-}
-
 /** Returns the chat rooms */
 export interface GetRoomsResponse {
 	/** A list of rooms */
 	rooms: Chatroom[],
 }
 
-export function serializeGetRoomsResponse(obj: GetRoomsResponse): any[] | null {
-	return [
-		obj.rooms.map(function (nested) { return serializeChatroom(nested); })
-	];
-}
-
 export function deserializeGetRoomsResponse(obj: any): GetRoomsResponse | null {
 	if (obj == null) { return null; }
 	return {
@@ -144,21 +122,13 @@ export function serializeSendMessageRequest(obj: SendMessageRequest): any[] | nu
 	];
 }
 
-export function deserializeSendMessageRequest(obj: any): SendMessageRequest | null {
-	if (obj == null) { return null; }
-	return {
-		msg: deserializeMessage(obj[0]),
-		roomId: obj[1]
-	};
-}
-
 /* ============ End Structs ============ */
 
 /* ============ Enums ============ */
 
 export const RoomStatus = {
-	ACTIVE: 1,
-	DISABLED: 2
+	ACTIVE: 0,
+	DISABLED: 1
 } as const;
 
 /* ============ End Enums ============ */
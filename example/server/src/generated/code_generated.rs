@@ -1,10 +1,7 @@
 // This is an autogenerated file. DO NOT EDIT
 
-#![allow(dead_code)]
-use warp::Filter;
 use serde::ser::SerializeTuple;
 
-
 /// AUTOGENERATED. The struct that serializes error states
 #[derive(Debug, Clone)]
 pub struct ZetroServerError {
@@ -14,8 +11,6 @@ pub struct ZetroServerError {
 	pub code: i16,
 }
 
-impl warp::reject::Reject for ZetroServerError {}
-
 /// Contains basic information about a single message author
 #[derive(Debug, Clone)]
 pub struct AuthorRef {
@@ -27,7 +22,7 @@ impl serde::ser::Serialize for AuthorRef {
 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where S: serde::ser::Serializer,
 	{
-		let mut state = serializer.serialize_tuple(2)?;
+		let mut state = serializer.serialize_tuple(1)?;
 		state.serialize_element(&self.username)?;
 		state.end()    
 	}
@@ -79,7 +74,7 @@ impl serde::ser::Serialize for Chatroom {
 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where S: serde::ser::Serializer,
 	{
-		let mut state = serializer.serialize_tuple(2)?;
+		let mut state = serializer.serialize_tuple(4)?;
 		state.serialize_element(&self.id)?;
 		state.serialize_element(&self.messages)?;
 		state.serialize_element(&self.name)?;
@@ -88,57 +83,6 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	}
 }
 
-impl<'de> serde::de::Deserialize<'de> for Chatroom {
-fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where D: serde::Deserializer<'de>,
-	{
-		struct Visitor;
-		impl<'de> serde::de::Visitor<'de> for Visitor {
-			type Value = Chatroom;
-
-			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-				write!(formatter, "")
-			}
-
-			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-			where A: serde::de::SeqAccess<'de>,
-			{
-				let id = seq.next_element::<u64>()?;
-				if id.is_none() {
-					return Err(serde::de::Error::custom("invalid field"));
-				}
-				let id = id.unwrap();
-
-				let messages = seq.next_element::<Vec<Message>>()?;
-				if messages.is_none() {
-					return Err(serde::de::Error::custom("invalid field"));
-				}
-				let messages = messages.unwrap();
-
-				let name = seq.next_element::<String>()?;
-				if name.is_none() {
-					return Err(serde::de::Error::custom("invalid field"));
-				}
-				let name = name.unwrap();
-
-				let status = seq.next_element::<RoomStatus>()?;
-				if status.is_none() {
-					return Err(serde::de::Error::custom("invalid field"));
-				}
-				let status = status.unwrap();
-
-				Ok(Chatroom {
-					id: id,
-					messages: messages,
-					name: name,
-					status: status,
-				})
-			}
-		}
-		deserializer.deserialize_tuple(4, Visitor)
-	}
-}
-
 /// Parameters for a GetRooms request
 #[derive(Debug, Clone)]
 pub struct GetRoomsRequest {
@@ -146,16 +90,6 @@ pub struct GetRoomsRequest {
 	pub with_status: Option<RoomStatus>,
 }
 
-impl serde::ser::Serialize for GetRoomsRequest {
-fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where S: serde::ser::Serializer,
-	{
-		let mut state = serializer.serialize_tuple(2)?;
-		state.serialize_element(&self.with_status)?;
-		state.end()    
-	}
-}
-
 impl<'de> serde::de::Deserialize<'de> for GetRoomsRequest {
 fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where D: serde::Deserializer<'de>,
@@ -197,42 +131,12 @@ impl serde::ser::Serialize for GetRoomsResponse {
 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where S: serde::ser::Serializer,
 	{
-		let mut state = serializer.serialize_tuple(2)?;
+		let mut state = serializer.serialize_tuple(1)?;
 		state.serialize_element(&self.rooms)?;
 		state.end()    
 	}
 }
 
-impl<'de> serde::de::Deserialize<'de> for GetRoomsResponse {
-fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where D: serde::Deserializer<'de>,
-	{
-		struct Visitor;
-		impl<'de> serde::de::Visitor<'de> for Visitor {
-			type Value = GetRoomsResponse;
-
-			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-				write!(formatter, "")
-			}
-
-			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-			where A: serde::de::SeqAccess<'de>,
-			{
-				let rooms = seq.next_element::<Vec<Chatroom>>()?;
-				if rooms.is_none() {
-					return Err(serde::de::Error::custom("invalid field"));
-				}
-				let rooms = rooms.unwrap();
-
-				Ok(GetRoomsResponse {
-					rooms: rooms,
-				})
-			}
-		}
-		deserializer.deserialize_tuple(1, Visitor)
-	}
-}
-
 /// Resembles a single chat message in a room
 #[derive(Debug, Clone)]
 pub struct Message {
@@ -250,7 +154,7 @@ impl serde::ser::Serialize for Message {
 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where S: serde::ser::Serializer,
 	{
-		let mut state = serializer.serialize_tuple(2)?;
+		let mut state = serializer.serialize_tuple(4)?;
 		state.serialize_element(&self.author)?;
 		state.serialize_element(&self.date)?;
 		state.serialize_element(&self.id)?;
@@ -319,17 +223,6 @@ pub struct SendMessageRequest {
 	pub room_id: u64,
 }
 
-impl serde::ser::Serialize for SendMessageRequest {
-fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where S: serde::ser::Serializer,
-	{
-		let mut state = serializer.serialize_tuple(2)?;
-		state.serialize_element(&self.msg)?;
-		state.serialize_element(&self.room_id)?;
-		state.end()    
-	}
-}
-
 impl<'de> serde::de::Deserialize<'de> for SendMessageRequest {
 fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where D: serde::Deserializer<'de>,
@@ -374,6 +267,10 @@ pub enum RoomStatus {
 	Disabled = 1,
 }
 
+use warp::Filter;
+
+impl warp::reject::Reject for ZetroServerError {}
+
 pub struct ZetroContext {
 	data: fnv::FnvHashMap<std::any::TypeId, Box<dyn std::any::Any + Sync + Send>>,
 }
@@ -0,0 +1,19 @@
+//! Generates the Rust code whose deserializers the `deserialize` target runs:
+//! the example schema and the corpus schema with every kind of field, each
+//! tagged and untagged.
+
+fn main() {
+    let schemas = [
+        ("chat", "../../example/schema.json"),
+        ("kinds", "../tests/corpus/kinds.json"),
+    ];
+    for (name, schema) in schemas {
+        for untagged in [true, false] {
+            let mut options = zetro_build::Options::new(zetro_build::Language::Rust);
+            options.untagged = untagged;
+            let encoding = if untagged { "untagged" } else { "tagged" };
+            zetro_build::generate(schema, &options, &format!("{}_{}.rs", name, encoding))
+                .unwrap_or_else(|e| panic!("{}: {}", schema, e));
+        }
+    }
+}
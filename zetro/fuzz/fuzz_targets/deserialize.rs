@@ -0,0 +1,39 @@
+//! Feeds arbitrary bytes to the generated deserializers of every request type,
+//! which is what a server does with the body of a route. Bodies may be
+//! rejected, but must never crash the server.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+macro_rules! generated {
+    ($module:ident, $file:literal) => {
+        #[allow(dead_code, clippy::all)]
+        mod $module {
+            include!(concat!(env!("OUT_DIR"), "/", $file));
+        }
+    };
+}
+
+generated!(chat_untagged, "chat_untagged.rs");
+generated!(chat_tagged, "chat_tagged.rs");
+generated!(kinds_untagged, "kinds_untagged.rs");
+generated!(kinds_tagged, "kinds_tagged.rs");
+
+fn deserialize<T: serde::de::DeserializeOwned>(data: &[u8]) {
+    let _ = serde_json::from_slice::<T>(data);
+}
+
+fuzz_target!(|data: &[u8]| {
+    deserialize::<chat_untagged::GetRoomsRequest>(data);
+    deserialize::<chat_untagged::SendMessageRequest>(data);
+    deserialize::<chat_tagged::GetRoomsRequest>(data);
+    deserialize::<chat_tagged::SendMessageRequest>(data);
+
+    deserialize::<kinds_untagged::Primitives>(data);
+    deserialize::<Vec<kinds_untagged::Tree>>(data);
+    deserialize::<Option<Vec<kinds_untagged::Modifiers>>>(data);
+    deserialize::<kinds_tagged::Primitives>(data);
+    deserialize::<Vec<kinds_tagged::Tree>>(data);
+    deserialize::<Option<Vec<kinds_tagged::Modifiers>>>(data);
+});
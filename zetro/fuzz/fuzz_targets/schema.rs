@@ -0,0 +1,31 @@
+//! Parses arbitrary JSON as a schema. Invalid schemas have to be errors, not
+//! panics, and a valid schema has to be generated and written back without
+//! panicking either.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zetro::{Language, Options, ZetroSchema};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(data) else {
+        return;
+    };
+    let Ok(schema) = ZetroSchema::from_value(&value) else {
+        return;
+    };
+
+    // `zetro fmt` relies on the canonical form being a valid schema too
+    if let Err(e) = ZetroSchema::from_value(&schema.to_value()) {
+        panic!("the canonical form of a valid schema is invalid: {}", e);
+    }
+
+    for language in [Language::Rust, Language::TypeScript] {
+        for untagged in [true, false] {
+            let mut options = Options::new(language);
+            options.untagged = untagged;
+            options.keep_unused_types = true;
+            let _ = zetro::generate(&schema, &options);
+        }
+    }
+});
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::{read_schema_source, usage, Failure, SCHEMA_HELP};
+use crate::{
+    common::{
+        schema::{routes::RouteKind, ZetroSchema},
+        wire,
+    },
+    utilities,
+};
+
+/// How long to wait for the server, including reading the response
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(super) fn help() -> String {
+    format!(
+        "Call the routes of a running server. Request bodies are readable JSON, as for
+`zetro encode`, and the response is printed the same way, as for `zetro inspect`.
+
+Usage: zetro call --url <url> [options] <route> <body> [<route> <body> ...]
+
+Several routes are sent as one request, so they must all be queries or all be
+mutations. The exit code is 1 if the server returns an error or a response that
+doesn't match the schema, 2 if a body doesn't match the schema, and 5 if the
+request fails.
+
+Example:
+  zetro call --url=http://localhost:8090/api GetRooms '{{\"withStatus\": \"ACTIVE\"}}'
+
+Options:
+{}  --url <url>          URL the client posts requests to
+  --header <header>    Header to send, as 'Name: value'. Can be repeated
+  --untagged <bool>    Whether the server uses untagged structs (default: true)
+",
+        SCHEMA_HELP
+    )
+}
+
+/// `zetro call`: Sends a request to a server and prints the response
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_call_args(args))?;
+    let (_, schema) = read_schema_source(args.source)?;
+    let body = encode(&schema, &args.calls, args.untagged)?;
+
+    let response = post(&args.url, &args.headers, &body)?;
+    let (decoded, mismatches) = wire::decode_response(&schema, &response);
+    println!("{}", serde_json::to_string_pretty(&decoded).unwrap());
+    for mismatch in &mismatches {
+        eprintln!("mismatch: {}", mismatch);
+    }
+
+    if !mismatches.is_empty() || decoded.get("error").is_some() {
+        return Err(Failure::Check);
+    }
+    Ok(())
+}
+
+/// The wire body of `calls`, which are route names with their JSON body
+fn encode(
+    schema: &ZetroSchema,
+    calls: &[(String, String)],
+    untagged: bool,
+) -> Result<String, Failure> {
+    let mut kind: Option<(&RouteKind, &str)> = None;
+    let mut readable: Vec<Value> = Vec::new();
+
+    for (name, body) in calls {
+        let route = schema
+            .queries
+            .iter()
+            .chain(schema.mutations.iter())
+            .find(|route| &route.name == name)
+            .ok_or_else(|| Failure::Usage(format!("No route is named '{}'", name)))?;
+        match kind {
+            Some((kind, first)) if kind.to_method_code() != route.kind.to_method_code() => {
+                return Err(Failure::Usage(format!(
+                    "'{}' and '{}' can't be called together, as one is a query and the other a mutation",
+                    first, name
+                )));
+            }
+            Some(_) => {}
+            None => kind = Some((&route.kind, name)),
+        }
+
+        let body = serde_json::from_str::<Value>(body).map_err(|e| {
+            Failure::Usage(format!("The body of '{}' is not valid JSON: {}", name, e))
+        })?;
+        readable.push(json!({ name: body }));
+    }
+
+    let key = match kind {
+        Some((RouteKind::Mutation, _)) => "mutation",
+        _ => "query",
+    };
+    match wire::encode_request(schema, &json!({ key: readable }), untagged) {
+        Ok(body) => Ok(body.to_string()),
+        // The server is never called with a body that doesn't match the schema
+        Err(mismatches) => Err(Failure::Usage(
+            mismatches
+                .iter()
+                .map(|mismatch| format!("error: {}", mismatch))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+    }
+}
+
+/// Posts `body` and reads the JSON response. Only a 200 response carries a
+/// payload; any other status is a failed request.
+fn post(url: &str, headers: &[(String, String)], body: &str) -> Result<Value, Failure> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let mut request = agent.post(url).set("Content-Type", "application/json");
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    let response = match request.send_string(body) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            let text = response.into_string().unwrap_or_default();
+            return Err(Failure::Request(format!(
+                "{} responded with status {}: {}",
+                url, status, text
+            )));
+        }
+        Err(e) => return Err(Failure::Request(format!("The request failed: {}", e))),
+    };
+    let text = response
+        .into_string()
+        .map_err(|e| Failure::Request(format!("Error reading the response: {}", e)))?;
+    serde_json::from_str(&text)
+        .map_err(|e| Failure::Request(format!("The response is not valid JSON: {}\n{}", e, text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    use serde_json::json;
+
+    use super::{encode, post};
+    use crate::common::schema::{routes::ZetroRoute, ZetroSchema};
+
+    /// Routes are encoded into one request, which is posted with the given
+    /// headers. Queries and mutations can't be mixed.
+    #[test]
+    fn posts_requests() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {},
+            "enums": {},
+            "routes": {
+                "GetRoom": { "kind": "query", "description": "", "request": "u64", "response": "string" },
+                "Ping": { "kind": "query", "description": "", "request": "u8", "response": "u8" },
+                "DeleteRoom": { "kind": "mutation", "description": "", "request": "u64", "response": "bool" }
+            }
+        }))
+        .unwrap();
+        let calls = |calls: &[(&str, &str)]| {
+            calls
+                .iter()
+                .map(|(name, body)| (name.to_string(), body.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let body = encode(&schema, &calls(&[("GetRoom", "7"), ("Ping", "1")]), true).unwrap();
+        assert_eq!(
+            body,
+            json!([
+                1,
+                [
+                    [ZetroRoute::encrypt_name("GetRoom"), 7],
+                    [ZetroRoute::encrypt_name("Ping"), 1]
+                ]
+            ])
+            .to_string()
+        );
+        assert!(encode(
+            &schema,
+            &calls(&[("GetRoom", "7"), ("DeleteRoom", "7")]),
+            true
+        )
+        .is_err());
+        assert!(encode(&schema, &calls(&[("GetRooms", "7")]), true).is_err());
+        // A body that doesn't match the schema is invalid usage, not an error
+        // of the server
+        let invalid = encode(&schema, &calls(&[("GetRoom", "\"seven\"")]), true);
+        assert_eq!(invalid.unwrap_err().exit_code(), 2);
+
+        // A server that answers one request, and returns it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/api", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response = "[[[\"x\",\"General\"]],null]";
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let headers = [(String::from("Authorization"), String::from("Bearer abc"))];
+        let response = post(&url, &headers, &body).unwrap();
+        let (head, sent) = server.join().unwrap();
+        assert_eq!(response, json!([[["x", "General"]], null]));
+        assert_eq!(sent, body);
+        assert!(head.starts_with("POST /api HTTP/1.1\r\n"));
+        assert!(head.contains("Authorization: Bearer abc\r\n"));
+    }
+}
@@ -0,0 +1,71 @@
+use super::{
+    diff::unified_diff, generate_target, read_schema, read_targets, stale_files, target_help,
+    usage, Failure,
+};
+use crate::utilities::{self, SchemaSource};
+
+pub(super) fn help() -> String {
+    format!(
+        "Check that generated files are up to date. Every target is generated in memory
+and compared with its out file. Differences, and modules of split output that
+are no longer generated, are printed and the command exits with code 1.
+Nothing is written.
+
+With only --schema, the schema is validated and nothing is generated.
+
+Usage: zetro check [options]
+
+{}",
+        target_help()
+    )
+}
+
+/// `zetro check`: Fails if an out file differs from what `zetro generate`
+/// would write
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    // A schema without targets can only be validated
+    if let Ok(SchemaSource::Schema(path)) = utilities::parse_schema_args(args) {
+        read_schema(&path)?;
+        println!("{}: ok", path);
+        return Ok(());
+    }
+
+    let (targets, schema) = read_targets(usage(utilities::parse_target_args(args))?)?;
+    let mut outdated = 0;
+    let mut files = 0;
+
+    for target in &targets {
+        let generated = generate_target(&schema, target)?;
+        let outputs = generated.outputs(&target.out_file);
+        for path in stale_files(target, &outputs) {
+            eprintln!("{}: no longer generated", path);
+            files += 1;
+            outdated += 1;
+        }
+        for (path, contents) in outputs {
+            files += 1;
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    outdated += 1;
+                    continue;
+                }
+            };
+            if let Some(diff) = unified_diff(&path, &existing, contents) {
+                print!("{}", diff);
+                outdated += 1;
+            }
+        }
+    }
+
+    if outdated > 0 {
+        eprintln!(
+            "{} of {} generated file(s) are out of date. Run `zetro generate` to update them",
+            outdated, files
+        );
+        return Err(Failure::Check);
+    }
+    println!("{} generated file(s) are up to date", files);
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use super::{read_schema, usage, Failure};
+use crate::{common::source, utilities};
+
+pub(super) fn help() -> String {
+    String::from(
+        "Convert a schema to another format.
+
+Usage: zetro convert --schema <path> --out-file <path>
+
+Options:
+  --schema <path>     Schema to convert
+  --out-file <path>   File to write. Its extension picks the format: .json,
+                      .json5, .jsonc, .yaml, .yml, .toml or .zetro
+",
+    )
+}
+
+/// `zetro convert`: Rewrites a schema in another format
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_convert_args(args))?;
+    let schema = read_schema(&args.schema_file)?;
+
+    let format = source::SchemaFormat::from_path(&args.out_file);
+    std::fs::write(&args.out_file, source::print_schema(&schema, format))
+        .map_err(|e| Failure::Generation(format!("error writing {}: {}", args.out_file, e)))
+}
@@ -0,0 +1,68 @@
+use super::{generate_target, read_targets, stale_files, target_help, usage, Failure};
+use crate::utilities;
+
+pub(super) fn help() -> String {
+    format!(
+        "Show how `zetro generate` would change the output files, as a unified diff.
+Nothing is written.
+
+Usage: zetro diff [options]
+
+{}",
+        target_help()
+    )
+}
+
+/// `zetro diff`: Prints the difference between each target and its out file
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let (targets, schema) = read_targets(usage(utilities::parse_target_args(args))?)?;
+
+    for target in &targets {
+        let generated = generate_target(&schema, target)?;
+        let outputs = generated.outputs(&target.out_file);
+        // A stale module shows up as a diff that removes every line
+        for path in stale_files(target, &outputs) {
+            let existing = std::fs::read_to_string(&path).unwrap_or_default();
+            if let Some(diff) = unified_diff(&path, &existing, "") {
+                print!("{}", diff);
+            }
+        }
+        for (path, contents) in outputs {
+            // A missing file shows up as a diff that adds every line
+            let existing = std::fs::read_to_string(&path).unwrap_or_default();
+            if let Some(diff) = unified_diff(&path, &existing, contents) {
+                print!("{}", diff);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A unified diff from `old` to `new`, or `None` if they are the same
+pub(super) fn unified_diff(path: &str, old: &str, new: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let diff = similar::TextDiff::from_lines(old, new);
+    Some(
+        diff.unified_diff()
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified_diff;
+
+    #[test]
+    fn diffs_changed_lines() {
+        assert!(unified_diff("a_generated.rs", "same\n", "same\n").is_none());
+
+        let diff = unified_diff("a_generated.rs", "one\ntwo\n", "one\n2\n").unwrap();
+        assert_eq!(
+            diff,
+            "--- a/a_generated.rs\n+++ b/a_generated.rs\n@@ -1,2 +1,2 @@\n one\n-two\n+2\n"
+        );
+    }
+}
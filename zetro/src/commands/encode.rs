@@ -0,0 +1,42 @@
+use super::{read_schema_source, read_stdin_json, usage, Failure, SCHEMA_HELP};
+use crate::{common::wire, utilities};
+
+pub(super) fn help() -> String {
+    format!(
+        "Build the body of a request from readable JSON, as a client would send it. The
+request is read from stdin and names routes, fields and enum variants:
+
+  {{\"query\": {{\"GetRooms\": {{\"withStatus\": \"ACTIVE\"}}}}}}
+
+Use a list, eg. {{\"query\": [{{\"GetRooms\": ...}}, {{\"GetRooms\": ...}}]}}, to call a
+route more than once. Values that don't match the schema are reported with their
+path, and the exit code is 1.
+
+Usage: zetro encode [options] < request.json
+
+Options:
+{}  --untagged <bool> Encode structs as arrays (default: true)
+",
+        SCHEMA_HELP
+    )
+}
+
+/// `zetro encode`: Prints the wire body of a readable request
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_encode_args(args))?;
+    let (_, schema) = read_schema_source(args.source)?;
+    let request = read_stdin_json("request")?;
+
+    match wire::encode_request(&schema, &request, args.untagged) {
+        Ok(body) => {
+            println!("{}", body);
+            Ok(())
+        }
+        Err(mismatches) => {
+            for mismatch in &mismatches {
+                eprintln!("error: {}", mismatch);
+            }
+            Err(Failure::Check)
+        }
+    }
+}
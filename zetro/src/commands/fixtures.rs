@@ -0,0 +1,48 @@
+use super::{read_schema_source, usage, Failure, SCHEMA_HELP};
+use crate::{common::fake, utilities};
+
+pub(super) fn help() -> String {
+    format!(
+        "Print a JSON list of instances of a struct, filled with fake data that is valid
+for the schema. The same options always give the same data.
+
+Usage: zetro fixtures --struct <name> [options]
+
+Options:
+{}  --struct <name>              Struct to generate
+  --count <number>             How many instances to generate (default: 1)
+  --seed <number>              Seed of the fake data (default: 0)
+  --untagged <bool>            Print structs as arrays (default: true)
+  --null-probability <number>  How often nullable fields are null, from 0 to 1
+                               (default: 0.25)
+  --list-len <min..max>        How many items lists have (default: 0..3)
+  --max-depth <number>         How deep structs nest before nullable fields are
+                               null and lists are empty (default: 4)
+",
+        SCHEMA_HELP
+    )
+}
+
+/// `zetro fixtures`: Prints fake instances of a struct
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_fixtures_args(args))?;
+    let (_, schema) = read_schema_source(args.source)?;
+
+    let values = fake::fake_structs(
+        &schema,
+        &args.struct_name,
+        args.count,
+        args.untagged,
+        &args.options,
+    )
+    .map_err(|e| match e {
+        fake::FakeError::Options(_) | fake::FakeError::UnknownStruct(_) => {
+            Failure::Usage(e.to_string())
+        }
+        fake::FakeError::EmptyEnum(_) | fake::FakeError::Infinite(_) => {
+            Failure::Schema(e.to_string())
+        }
+    })?;
+    println!("{}", serde_json::to_string_pretty(&values).unwrap());
+    Ok(())
+}
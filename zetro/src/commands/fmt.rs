@@ -0,0 +1,261 @@
+use super::{usage, Failure};
+use crate::{
+    common::{
+        idl::lexer::{self, TokenKind},
+        source::{self, SchemaFormat},
+    },
+    utilities,
+};
+
+pub(super) fn help() -> String {
+    String::from(
+        "Rewrite a schema in place, in canonical form. The file keeps its format.
+
+Usage: zetro fmt --schema <path> [--check] [--strip-comments]
+
+Options:
+  --schema <path>    Schema file to format
+  --check            Only check whether the file is formatted. Exits with code 1
+                     if it is not
+  --strip-comments   Format files whose comments would be lost. Comments that
+                     are not descriptions can't be kept, so without this option
+                     such files are left unchanged and the exit code is 2
+",
+    )
+}
+
+/// `zetro fmt`: Rewrites a schema in canonical form, in place
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_fmt_args(args))?;
+
+    let contents = std::fs::read_to_string(&args.schema_file)
+        .map_err(|e| Failure::Schema(format!("{}: error reading file: {}", args.schema_file, e)))?;
+    let format = source::SchemaFormat::from_path(&args.schema_file);
+    let schema = source::parse_schema(&contents, format, &args.schema_file)
+        .map_err(|e| Failure::Schema(e.to_string()))?;
+
+    let formatted = source::print_schema(&schema, format);
+    if formatted == contents {
+        return Ok(());
+    }
+    let lost = lost_comments(&contents, format, &formatted);
+    if args.check {
+        eprintln!(
+            "{} is not formatted. Run `zetro fmt` to fix it",
+            args.schema_file
+        );
+        if !lost.is_empty() {
+            eprintln!(
+                "Formatting it would remove {} comment(s), which needs --strip-comments",
+                lost.len()
+            );
+        }
+        return Err(Failure::Check);
+    }
+    if let Some(line) = lost.first().filter(|_| !args.strip_comments) {
+        return Err(Failure::Usage(format!(
+            "{}:{}: formatting would remove {} comment(s), starting here. \
+            Pass --strip-comments to format the file anyway",
+            args.schema_file,
+            line,
+            lost.len()
+        )));
+    }
+    std::fs::write(&args.schema_file, formatted)
+        .map_err(|e| Failure::Generation(format!("error writing {}: {}", args.schema_file, e)))
+}
+
+/// One-based lines of the comments in `contents` that are not in the
+/// `formatted` text, ie. that are not descriptions
+fn lost_comments(contents: &str, format: SchemaFormat, formatted: &str) -> Vec<usize> {
+    comments(contents, format)
+        .into_iter()
+        .filter(|(_, text)| !formatted.contains(text.as_str()))
+        .map(|(offset, _)| source::offset_to_position(contents, offset).0)
+        .collect()
+}
+
+/// The offset and trimmed text of every comment in a schema file
+fn comments(contents: &str, format: SchemaFormat) -> Vec<(usize, String)> {
+    let mut comments: Vec<(usize, String)> = Vec::new();
+    let mut push = |offset: usize, text: &str| comments.push((offset, text.trim().to_owned()));
+    let line_end = |from: usize| {
+        contents[from..]
+            .find('\n')
+            .map_or(contents.len(), |i| from + i)
+    };
+
+    match format {
+        SchemaFormat::Json => {}
+        SchemaFormat::Zetro => {
+            for token in lexer::tokenize(contents).0 {
+                if let TokenKind::LineComment(text) = token.kind {
+                    push(token.span.start, &text);
+                }
+            }
+        }
+        SchemaFormat::Json5 | SchemaFormat::Yaml | SchemaFormat::Toml => {
+            let bytes = contents.as_bytes();
+            let next = |i: usize| i + contents[i..].chars().next().map_or(1, char::len_utf8);
+            let mut i = 0;
+            // The quote that closes the string being read, if any
+            let mut quote: Option<&str> = None;
+            while i < bytes.len() {
+                let rest = &contents[i..];
+                if let Some(q) = quote {
+                    // Only JSON5 escapes in single quoted strings
+                    let literal = q.starts_with('\'') && format != SchemaFormat::Json5;
+                    if bytes[i] == b'\\' && !literal {
+                        i = next(i + 1);
+                        continue;
+                    }
+                    // YAML escapes single quotes by doubling them
+                    if format == SchemaFormat::Yaml && q == "'" && rest.starts_with("''") {
+                        i += 2;
+                        continue;
+                    }
+                    if rest.starts_with(q) {
+                        i += q.len();
+                        quote = None;
+                    } else {
+                        i = next(i);
+                    }
+                    continue;
+                }
+
+                let comment = match format {
+                    SchemaFormat::Json5 => {
+                        if rest.starts_with("//") {
+                            Some((i + 2, line_end(i)))
+                        } else if rest.starts_with("/*") {
+                            let end = rest.find("*/").map_or(contents.len(), |e| i + e);
+                            Some((i + 2, end))
+                        } else {
+                            None
+                        }
+                    }
+                    // `#` only starts a YAML comment after whitespace, eg. not
+                    // in `a#b`
+                    SchemaFormat::Yaml => (bytes[i] == b'#'
+                        && (i == 0 || bytes[i - 1].is_ascii_whitespace()))
+                    .then(|| (i + 1, line_end(i))),
+                    _ => (bytes[i] == b'#').then(|| (i + 1, line_end(i))),
+                };
+                if let Some((start, end)) = comment {
+                    push(i, &contents[start..end]);
+                    i = end;
+                    continue;
+                }
+
+                quote = match format {
+                    SchemaFormat::Toml if rest.starts_with("\"\"\"") => Some("\"\"\""),
+                    SchemaFormat::Toml if rest.starts_with("'''") => Some("'''"),
+                    // A quote inside a plain YAML scalar, eg. `it's`, is text
+                    SchemaFormat::Yaml
+                        if contents[..i]
+                            .trim_end_matches([' ', '\t'])
+                            .ends_with(|c: char| c.is_alphanumeric()) =>
+                    {
+                        None
+                    }
+                    _ if bytes[i] == b'"' => Some("\""),
+                    _ if bytes[i] == b'\'' => Some("'"),
+                    _ => None,
+                };
+                i = quote.map_or_else(|| next(i), |q| i + q.len());
+            }
+        }
+    }
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{comments, lost_comments, run};
+    use crate::common::source::SchemaFormat;
+
+    /// Comments are found outside strings in every format
+    #[test]
+    fn finds_comments() {
+        let texts = |contents: &str, format: SchemaFormat| {
+            comments(contents, format)
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            texts(
+                "{ // One\n \"a\": \"// no\", /* Two */ 'b': 'it\\'s // no' }",
+                SchemaFormat::Json5
+            ),
+            vec!["One", "Two"]
+        );
+        assert_eq!(
+            texts(
+                "# One\na: it's é # Two\nb: 'it''s # no'\nc: a#b\nd: \"\\\" # no\"",
+                SchemaFormat::Yaml
+            ),
+            vec!["One", "Two"]
+        );
+        assert_eq!(
+            texts(
+                "a = \"# no\" # One\nb = '''\n# no'''\n# Two",
+                SchemaFormat::Toml
+            ),
+            vec!["One", "Two"]
+        );
+        assert_eq!(
+            texts(
+                "struct A {\n  // One\n  a: u8 // Two\n}",
+                SchemaFormat::Zetro
+            ),
+            vec!["One", "Two"]
+        );
+        assert!(texts("{ \"a\": \"// no\" }", SchemaFormat::Json).is_empty());
+    }
+
+    /// Comments that are descriptions are kept by formatting
+    #[test]
+    fn keeps_descriptions() {
+        let contents = "struct A {\n  // Removed\n  a: u8 // Kept\n}";
+        let formatted = "struct A {\n  a: u8 // Kept\n}\n";
+        assert_eq!(
+            lost_comments(contents, SchemaFormat::Zetro, formatted),
+            vec![2]
+        );
+    }
+
+    /// A commented JSON5 file is left as it is, unless comments may be
+    /// stripped
+    #[test]
+    fn keeps_commented_files() {
+        let dir = std::env::temp_dir().join(format!("zetro-fmt-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("schema.json5");
+        let contents = "{
+  // Not a description
+  routes: {},
+  structs: {},
+  enums: {},
+}
+";
+        std::fs::write(&path, contents).unwrap();
+        let args = |extra: &[&str]| {
+            let mut args = vec![format!("--schema={}", path.display())];
+            args.extend(extra.iter().map(|a| a.to_string()));
+            args
+        };
+
+        assert_eq!(run(&args(&[])).unwrap_err().exit_code(), 2);
+        assert_eq!(run(&args(&["--check"])).unwrap_err().exit_code(), 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+
+        run(&args(&["--strip-comments"])).unwrap();
+        let stripped = std::fs::read_to_string(&path).unwrap();
+        assert!(!stripped.contains("Not a description"));
+        run(&args(&["--check"])).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
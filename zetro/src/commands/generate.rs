@@ -0,0 +1,88 @@
+use super::{
+    generate_target, read_targets, remove_stale_files, target_help, usage, write_if_changed,
+    Failure,
+};
+use crate::utilities;
+
+pub(super) fn help() -> String {
+    format!(
+        "Generate code from a schema. The schema is parsed once and every target is written.
+
+Usage: zetro generate [options]
+
+{}",
+        target_help()
+    )
+}
+
+/// `zetro generate`: Writes every target whose content changed, and deletes
+/// the modules that split output no longer has
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let (targets, schema) = read_targets(usage(utilities::parse_target_args(args))?)?;
+
+    for target in &targets {
+        let generated = generate_target(&schema, target)?;
+        let outputs = generated.outputs(&target.out_file);
+        for (path, contents) in &outputs {
+            write_if_changed(path, contents)?;
+        }
+        remove_stale_files(target, &outputs)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{super::check, run};
+
+    /// Modules of removed routes are deleted, and reported by `zetro check`
+    /// until they are
+    #[test]
+    fn removes_stale_modules() {
+        let dir = std::env::temp_dir().join(format!("zetro-stale-{}", std::process::id()));
+        let out_dir = dir.join("api_generated");
+        fs::create_dir_all(&out_dir).unwrap();
+        let schema = dir.join("schema.json");
+        let write_schema = |routes: &str| {
+            let contents = format!(
+                r#"{{
+                    "structs": {{
+                        "Room": {{ "description": "", "fields": {{ "id": "u64" }} }},
+                        "Message": {{ "description": "", "fields": {{ "text": "string" }} }}
+                    }},
+                    "enums": {{}},
+                    "routes": {{ {} }}
+                }}"#,
+                routes
+            );
+            fs::write(&schema, contents).unwrap();
+        };
+        let args = vec![
+            format!("--schema={}", schema.display()),
+            format!("--out-dir={}", out_dir.display()),
+            String::from("--lang=ts"),
+        ];
+
+        write_schema(
+            r#""GetRoom": { "kind": "query", "description": "", "request": "u64", "response": "struct~Room" },
+            "GetMessage": { "kind": "query", "description": "", "request": "u64", "response": "struct~Message" }"#,
+        );
+        run(&args).unwrap();
+        assert!(out_dir.join("getMessage.ts").exists());
+        fs::write(out_dir.join("handWritten.ts"), "export const a = 1;\n").unwrap();
+
+        write_schema(
+            r#""GetRoom": { "kind": "query", "description": "", "request": "u64", "response": "struct~Room" }"#,
+        );
+        assert_eq!(check::run(&args).unwrap_err().exit_code(), 1);
+        run(&args).unwrap();
+        assert!(!out_dir.join("getMessage.ts").exists());
+        assert!(out_dir.join("getRoom.ts").exists());
+        assert!(out_dir.join("handWritten.ts").exists());
+        check::run(&args).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use super::{read_schema_source, read_stdin_json, usage, Failure, SCHEMA_HELP};
+use crate::{
+    common::{
+        schema::{
+            fields::{FieldKind, ZetroField},
+            routes::{RouteKind, ZetroRoute},
+            ZetroSchema,
+        },
+        wire,
+    },
+    utilities::{self, PayloadDirection},
+};
+
+pub(super) fn help() -> String {
+    format!(
+        "Show the routes of a schema with their method codes and encrypted names, and
+the position of every field in untagged (array) payloads.
+
+With --direction, decode a payload captured from the wire instead. The payload
+is read from stdin and printed as JSON with route, field and variant names.
+Values that don't match the schema are reported, and the exit code is 1.
+
+Usage: zetro inspect [options]
+       zetro inspect --direction <request|response> [options] < payload.json
+
+Options:
+{}  --direction <request|response>
+                    Decode a request or response payload from stdin
+",
+        SCHEMA_HELP
+    )
+}
+
+/// `zetro inspect`: Prints how a schema looks on the wire
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_inspect_args(args))?;
+    let (_, schema) = read_schema_source(args.source)?;
+    let direction = match args.direction {
+        Some(v) => v,
+        None => {
+            print!("{}", describe(&schema));
+            return Ok(());
+        }
+    };
+
+    let payload = read_stdin_json("payload")?;
+    let (decoded, mismatches) = match direction {
+        PayloadDirection::Request => wire::decode_request(&schema, &payload),
+        PayloadDirection::Response => wire::decode_response(&schema, &payload),
+    };
+    println!("{}", serde_json::to_string_pretty(&decoded).unwrap());
+    for mismatch in &mismatches {
+        eprintln!("mismatch: {}", mismatch);
+    }
+
+    if !mismatches.is_empty() {
+        return Err(Failure::Check);
+    }
+    Ok(())
+}
+
+fn describe(schema: &ZetroSchema) -> String {
+    let mut out = String::new();
+
+    for (title, kind, routes) in [
+        ("Queries", RouteKind::Query, &schema.queries),
+        ("Mutations", RouteKind::Mutation, &schema.mutations),
+    ] {
+        out.push_str(&format!(
+            "{} (method code {})\n",
+            title,
+            kind.to_method_code()
+        ));
+        for route in routes {
+            push_route(&mut out, route);
+        }
+        out.push('\n');
+    }
+
+    out.push_str("Structs (untagged structs are arrays in this order)\n");
+    for _struct in &schema.structs {
+        out.push_str(&format!("  {}\n", _struct.name));
+        push_fields(&mut out, &_struct.fields, 2);
+    }
+    out.push('\n');
+
+    out.push_str("Enums (variants are sent as numbers)\n");
+    for _enum in &schema.enums {
+        out.push_str(&format!("  {}\n", _enum.name));
+        for (i, variant) in _enum.variants.iter().enumerate() {
+            out.push_str(&format!("    {}  {}\n", i, variant));
+        }
+    }
+
+    out
+}
+
+fn push_route(out: &mut String, route: &ZetroRoute) {
+    out.push_str(&format!(
+        "  {}  [{}]\n    request:  {}\n    response: {}\n",
+        route.name,
+        route.encrypt_route_name(),
+        type_name(&route.request_body),
+        type_name(&route.response_body)
+    ));
+}
+
+/// Lists fields with their position. Nested objects are listed below the
+/// field that contains them.
+fn push_fields(out: &mut String, fields: &[ZetroField], depth: usize) {
+    let indent = "  ".repeat(depth);
+    for (i, field) in fields.iter().enumerate() {
+        out.push_str(&format!(
+            "{}{}  {}: {}\n",
+            indent,
+            i,
+            field.name,
+            type_name(field)
+        ));
+        if let FieldKind::NestedObject(nested) = &field.kind {
+            push_fields(out, &nested.fields, depth + 2);
+        }
+    }
+}
+
+/// The type of a field as written in a `.zetro` file, eg. `?[]Message`
+fn type_name(field: &ZetroField) -> String {
+    let name = match &field.kind {
+        FieldKind::StructValue(name) | FieldKind::EnumValue(name) => name.as_str(),
+        FieldKind::NestedObject(_) => "{..}",
+        primitive => primitive.primitive_name().unwrap(),
+    };
+    format!(
+        "{}{}{}",
+        if field.is_nullable { "?" } else { "" },
+        if field.is_multiple { "[]" } else { "" },
+        name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::describe;
+    use crate::common::schema::ZetroSchema;
+
+    #[test]
+    fn describes_schema() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": {
+                    "description": "A message",
+                    "fields": {
+                        "text": "string",
+                        "id": "u64",
+                        "meta": { "description": "Metadata", "fields": { "tags": "?[]string" } }
+                    }
+                }
+            },
+            "enums": { "Status": ["ACTIVE", "DISABLED"] },
+            "routes": {
+                "GetMessage": {
+                    "kind": "query",
+                    "description": "Get a message",
+                    "request": "u64",
+                    "response": "?struct~Message"
+                }
+            }
+        }))
+        .unwrap();
+        let described = describe(&schema);
+
+        assert!(described.contains("Queries (method code 1)\n  GetMessage  ["));
+        assert!(described.contains("    request:  u64\n    response: ?Message\n"));
+        assert!(described.contains(
+            "  Message\n    0  id: u64\n    1  meta: {..}\n        0  tags: ?[]string\n    2  text: string\n"
+        ));
+        assert!(described.contains("  Status\n    0  ACTIVE\n    1  DISABLED\n"));
+    }
+}
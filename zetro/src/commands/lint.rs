@@ -0,0 +1,67 @@
+use super::{read_schema, usage, Failure};
+use crate::{
+    common::source::SchemaFormat,
+    lint::{self, config::LintConfig},
+    utilities::{self, LintFormat},
+};
+
+pub(super) fn help() -> String {
+    let mut rules = String::new();
+    for rule in lint::Rule::ALL {
+        rules.push_str(&format!("  {}\n", rule.name()));
+    }
+
+    format!(
+        "Report schemas that are valid but probably not what you meant.
+
+Usage: zetro lint --schema <path> [options]
+
+Options:
+  --schema <path>          Schema file to lint
+  --config <path>          Lint config. Defaults to {} next to the schema
+  --format <human|json>    Output format (default: human)
+
+Exits with code 1 if a rule set to `error` fires.
+
+Rules:
+{}",
+        lint::config::CONFIG_FILE_NAME,
+        rules
+    )
+}
+
+/// `zetro lint`: Reports likely mistakes in a schema. Fails if any rule
+/// configured as an error fired.
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_lint_args(args))?;
+
+    // Without --config, use the config file next to the schema, if any
+    let config_file = args.config_file.clone().or_else(|| {
+        let path =
+            std::path::Path::new(&args.schema_file).with_file_name(lint::config::CONFIG_FILE_NAME);
+        path.exists().then(|| path.to_string_lossy().into_owned())
+    });
+    let config = match config_file {
+        Some(path) => usage(LintConfig::read(&path))?,
+        None => LintConfig::default(),
+    };
+
+    let schema = read_schema(&args.schema_file)?;
+    let contents = std::fs::read_to_string(&args.schema_file).unwrap_or_default();
+
+    let mut diagnostics = lint::lint(&schema, &config);
+    lint::locate(
+        &mut diagnostics,
+        &contents,
+        SchemaFormat::from_path(&args.schema_file),
+    );
+    match args.format {
+        LintFormat::Human => print!("{}", lint::format_human(&diagnostics, &args.schema_file)),
+        LintFormat::Json => println!("{}", lint::format_json(&diagnostics, &args.schema_file)),
+    }
+
+    if diagnostics.iter().any(|d| d.level == lint::Level::Error) {
+        return Err(Failure::Check);
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use super::{usage, Failure};
+use crate::{lsp, utilities};
+
+pub(super) fn help() -> String {
+    String::from(
+        "Run a language server for schema files, for editors that speak the Language
+Server Protocol. It talks to the editor on stdin and stdout.
+
+Usage: zetro lsp
+
+Schemas of every format get diagnostics, completion of dtypes and of struct
+and enum names, go-to-definition, find-references and rename of structs and
+enums, and hovers that show the generated Rust and TypeScript code.
+Definitions, references and renames also cover the other open schemas and the
+schemas named by the zetro.toml files of the workspace.
+
+Options:
+  --stdio   Accepted for editors that always pass it. Stdio is the only
+            transport
+",
+    )
+}
+
+/// `zetro lsp`: Serves the language server protocol until the editor exits
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    usage(utilities::parse_flags(args, &[], &["--stdio"]))?;
+    lsp::serve().map_err(|e| Failure::Usage(format!("Language server error: {}", e)))
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::{read_schema_source, usage, Failure, SCHEMA_HELP};
+use crate::{
+    common::{
+        fake::{FakeOptions, Faker},
+        schema::{routes::ZetroRoute, ZetroSchema},
+        wire,
+    },
+    utilities,
+};
+
+pub(super) fn help() -> String {
+    format!(
+        "Serve the routes of a schema with fake data, eg. to work on a client before the
+server exists. Requests are validated like the generated warp server does, and
+every route responds with random values of its response type. The values are
+the same every time the server starts with the same seed.
+
+Usage: zetro mock [options]
+
+Options:
+{}  --host <host>        Address to listen on (default: 127.0.0.1)
+  --port <port>        Port to listen on (default: 8090)
+  --fixtures <path>    JSON file with the responses of some routes, as
+                       {{\"GetRooms\": {{\"rooms\": [...]}}}}. Other routes get fake data
+  --untagged <bool>    Whether structs are untagged (default: true)
+  --seed <number>      Seed of the fake data (default: 0)
+",
+        SCHEMA_HELP
+    )
+}
+
+/// `zetro mock`: Serves fake responses until stopped
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let args = usage(utilities::parse_mock_args(args))?;
+    let (_, schema) = read_schema_source(args.source)?;
+    let fixtures = match &args.fixtures_file {
+        Some(path) => read_fixtures(&schema, path, args.untagged)?,
+        None => HashMap::new(),
+    };
+    let faker = Faker::new(
+        &schema,
+        FakeOptions {
+            seed: args.seed,
+            ..Default::default()
+        },
+    );
+    for route in routes(&schema).filter(|route| !fixtures.contains_key(&route.name)) {
+        faker
+            .check(&route.response_body)
+            .map_err(|e| Failure::Schema(format!("{}: {}", route.name, e)))?;
+    }
+    let mut mock = Mock {
+        schema: &schema,
+        faker,
+        fixtures,
+        untagged: args.untagged,
+    };
+
+    let address = format!("{}:{}", args.host, args.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|e| Failure::Usage(format!("Error listening on {}: {}", address, e)))?;
+    eprintln!("Serving mock responses on http://{}", address);
+
+    for mut request in server.incoming_requests() {
+        let response = match request.method() {
+            // Preflight requests of browsers, as clients usually run on another origin
+            tiny_http::Method::Options => tiny_http::Response::empty(204)
+                .with_header(header("Access-Control-Allow-Methods", "POST"))
+                .with_header(header("Access-Control-Allow-Headers", "*"))
+                .boxed(),
+            tiny_http::Method::Post => {
+                let mut body = Vec::new();
+                let reply = match std::io::Read::read_to_end(request.as_reader(), &mut body) {
+                    Ok(_) => mock.respond(&body),
+                    Err(_) => error_reply(400, "Bad request"),
+                };
+                tiny_http::Response::from_string(reply.to_string())
+                    .with_header(header("Content-Type", "application/json"))
+                    .boxed()
+            }
+            _ => tiny_http::Response::empty(405).boxed(),
+        };
+        let response = response.with_header(header("Access-Control-Allow-Origin", "*"));
+        if let Err(e) = request.respond(response) {
+            eprintln!("Error sending a response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn header(name: &str, value: &str) -> tiny_http::Header {
+    tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap()
+}
+
+/// Reads the fixtures file: route names with their readable response, which
+/// are encoded once here
+fn read_fixtures(
+    schema: &ZetroSchema,
+    path: &str,
+    untagged: bool,
+) -> Result<HashMap<String, Value>, Failure> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| Failure::Usage(format!("{}: error reading file: {}", path, e)))?;
+    let fixtures = serde_json::from_str::<serde_json::Map<String, Value>>(&contents)
+        .map_err(|e| Failure::Usage(format!("{}: {}", path, e)))?;
+
+    let mut encoded = HashMap::new();
+    for (name, response) in fixtures {
+        let route = routes(schema)
+            .find(|route| route.name == name)
+            .ok_or_else(|| Failure::Usage(format!("{}: no route is named '{}'", path, name)))?;
+        let response = wire::encode_body(schema, &route.response_body, &response, untagged, &name)
+            .map_err(|mismatches| {
+                let errors = mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+                Failure::Usage(format!("{}: {}", path, errors.join("\n")))
+            })?;
+        encoded.insert(name, response);
+    }
+    Ok(encoded)
+}
+
+fn routes(schema: &ZetroSchema) -> impl Iterator<Item = &ZetroRoute> {
+    schema.queries.iter().chain(schema.mutations.iter())
+}
+
+struct Mock<'a> {
+    schema: &'a ZetroSchema,
+    faker: Faker<'a>,
+    /// Encoded responses by route name
+    fixtures: HashMap<String, Value>,
+    untagged: bool,
+}
+
+impl Mock<'_> {
+    /// The response to a request body. Invalid requests get the error replies
+    /// of the warp plugin.
+    fn respond(&mut self, body: &[u8]) -> Value {
+        let (method_code, operations) = match serde_json::from_slice::<(u8, Vec<Value>)>(body) {
+            Ok(v) => v,
+            Err(_) => return error_reply(400, "Bad request"),
+        };
+
+        let mut data: Vec<Value> = Vec::new();
+        for operation in operations {
+            let operation = match operation.as_array() {
+                Some(v) => v,
+                None => return error_reply(400, "Operations must be an array"),
+            };
+            let (route_name, route_body) = match (operation.first(), operation.get(1)) {
+                (Some(name), Some(body)) => (name, body),
+                _ => return error_reply(400, "Route name and route body are mandatory"),
+            };
+            let route_name = match route_name.as_str() {
+                Some(v) => v,
+                None => return error_reply(400, "Route name must be string"),
+            };
+
+            let routes = match method_code {
+                1 => &self.schema.queries,
+                2 => &self.schema.mutations,
+                _ => return error_reply(400, "Bad request"),
+            };
+            let route = match routes
+                .iter()
+                .find(|route| route.encrypt_route_name() == route_name)
+            {
+                Some(v) => v,
+                None => return error_reply(400, "Unrecognized route name"),
+            };
+
+            let (_, mismatches) = wire::decode_body(
+                self.schema,
+                &route.request_body,
+                route_body,
+                self.untagged,
+                &route.name,
+            );
+            if !mismatches.is_empty() {
+                for mismatch in &mismatches {
+                    eprintln!("{}: bad request: {}", route.name, mismatch);
+                }
+                return error_reply(400, "Bad request");
+            }
+
+            let response = match self.fixtures.get(&route.name) {
+                Some(v) => v.clone(),
+                None => {
+                    let value = match self.faker.field(&route.response_body) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            eprintln!("{}: {}", route.name, e);
+                            return error_reply(500, "Internal server error");
+                        }
+                    };
+                    wire::encode_body(
+                        self.schema,
+                        &route.response_body,
+                        &value,
+                        self.untagged,
+                        &route.name,
+                    )
+                    .expect("fake values match the schema")
+                }
+            };
+            eprintln!("{}", route.name);
+            data.push(json!([route_name, response]));
+        }
+        json!([data, null])
+    }
+}
+
+fn error_reply(code: i16, message: &str) -> Value {
+    json!([null, [code, message]])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::{json, Value};
+
+    use super::Mock;
+    use crate::common::{
+        fake::Faker,
+        schema::{routes::ZetroRoute, ZetroSchema},
+    };
+
+    /// Valid requests get fixtures or fake data, invalid ones the errors of
+    /// the warp router
+    #[test]
+    fn responds_like_warp() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Room": { "description": "", "fields": { "id": "u64", "name": "string" } }
+            },
+            "enums": {},
+            "routes": {
+                "GetRoom": { "kind": "query", "description": "", "request": "u64", "response": "struct~Room" },
+                "DeleteRoom": { "kind": "mutation", "description": "", "request": "u64", "response": "bool" }
+            }
+        }))
+        .unwrap();
+        let mut mock = Mock {
+            schema: &schema,
+            faker: Faker::new(&schema, Default::default()),
+            fixtures: HashMap::from([(String::from("DeleteRoom"), json!(true))]),
+            untagged: true,
+        };
+        let mut respond = |body: Value| mock.respond(body.to_string().as_bytes());
+        let get = ZetroRoute::encrypt_name("GetRoom");
+        let delete = ZetroRoute::encrypt_name("DeleteRoom");
+
+        let response = respond(json!([1, [[get, 7]]]));
+        let room = &response[0][0][1];
+        assert!(room[0].is_u64() && room[1].is_string(), "{}", response);
+        assert_eq!(
+            respond(json!([2, [[delete, 7], [delete, 8]]])),
+            json!([[[delete, true], [delete, true]], null])
+        );
+
+        let error = |message: &str| json!([null, [400, message]]);
+        assert_eq!(respond(json!({ "a": 1 })), error("Bad request"));
+        assert_eq!(
+            respond(json!([1, [7]])),
+            error("Operations must be an array")
+        );
+        assert_eq!(
+            respond(json!([1, [[get]]])),
+            error("Route name and route body are mandatory")
+        );
+        assert_eq!(
+            respond(json!([1, [[1, 1]]])),
+            error("Route name must be string")
+        );
+        assert_eq!(
+            respond(json!([1, [[delete, 7]]])),
+            error("Unrecognized route name")
+        );
+        assert_eq!(respond(json!([1, [[get, "7"]]])), error("Bad request"));
+        assert_eq!(respond(json!([3, [[get, 7]]])), error("Bad request"));
+    }
+}
@@ -0,0 +1,391 @@
+//! The `zetro` subcommands.
+//!
+//! Every command parses its own options and returns a `Failure` instead of
+//! exiting, so that `run` can report errors and pick the exit code in one
+//! place.
+
+use crate::{
+    common::{schema::ZetroSchema, source},
+    generators::{self, Generated},
+    project,
+    utilities::{SchemaSource, TargetSource, ZetroArgs},
+};
+
+mod call;
+mod check;
+mod convert;
+mod diff;
+mod encode;
+mod fixtures;
+mod fmt;
+mod generate;
+mod inspect;
+mod lint;
+mod lsp;
+mod mock;
+mod watch;
+
+/// Why a command failed. Each kind exits with its own code.
+#[derive(Debug)]
+pub(crate) enum Failure {
+    /// The command ran, but found a problem it already reported, eg. an
+    /// unformatted file or a lint error
+    Check,
+    /// Invalid options or config file
+    Usage(String),
+    /// The schema could not be read or is invalid
+    Schema(String),
+    /// Generating code or writing an output file failed
+    Generation(String),
+    /// A request to an API server failed, see `zetro call`
+    Request(String),
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Check => 1,
+            Failure::Usage(_) => 2,
+            Failure::Schema(_) => 3,
+            Failure::Generation(_) => 4,
+            Failure::Request(_) => 5,
+        }
+    }
+}
+
+struct Command {
+    name: &'static str,
+    summary: &'static str,
+    help: fn() -> String,
+    run: fn(&[String]) -> Result<(), Failure>,
+}
+
+static COMMANDS: [Command; 13] = [
+    Command {
+        name: "generate",
+        summary: "Generate code for the targets in zetro.toml, or a single target",
+        help: generate::help,
+        run: generate::run,
+    },
+    Command {
+        name: "check",
+        summary: "Check that generated files are up to date",
+        help: check::help,
+        run: check::run,
+    },
+    Command {
+        name: "diff",
+        summary: "Show how `generate` would change the output files",
+        help: diff::help,
+        run: diff::run,
+    },
+    Command {
+        name: "watch",
+        summary: "Regenerate the targets whenever the schema or zetro.toml changes",
+        help: watch::help,
+        run: watch::run,
+    },
+    Command {
+        name: "fmt",
+        summary: "Rewrite a schema in canonical form",
+        help: fmt::help,
+        run: fmt::run,
+    },
+    Command {
+        name: "inspect",
+        summary: "Show the routes and wire layout of a schema",
+        help: inspect::help,
+        run: inspect::run,
+    },
+    Command {
+        name: "encode",
+        summary: "Build the wire body of a request from readable JSON",
+        help: encode::help,
+        run: encode::run,
+    },
+    Command {
+        name: "call",
+        summary: "Call the routes of a running server",
+        help: call::help,
+        run: call::run,
+    },
+    Command {
+        name: "mock",
+        summary: "Serve fake responses for the routes of a schema",
+        help: mock::help,
+        run: mock::run,
+    },
+    Command {
+        name: "fixtures",
+        summary: "Generate instances of a struct with fake data",
+        help: fixtures::help,
+        run: fixtures::run,
+    },
+    Command {
+        name: "lint",
+        summary: "Report likely mistakes in a schema",
+        help: lint::help,
+        run: lint::run,
+    },
+    Command {
+        name: "convert",
+        summary: "Convert a schema to another format",
+        help: convert::help,
+        run: convert::run,
+    },
+    Command {
+        name: "lsp",
+        summary: "Run a language server for schema files",
+        help: lsp::help,
+        run: lsp::run,
+    },
+];
+
+fn help() -> String {
+    let mut out = format!(
+        "zetro {}\n{}\n\nUsage: zetro <command> [options]\n\nCommands:\n",
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_PKG_DESCRIPTION")
+    );
+    for command in &COMMANDS {
+        out.push_str(&format!("  {:<10} {}\n", command.name, command.summary));
+    }
+    out.push_str(
+        "  help       Show help for a command
+
+Options:
+  -h, --help     Show help
+  -V, --version  Show the version
+
+Options take a value either as `--name=value` or `--name value`.
+Run `zetro help <command>` for the options of a command.
+
+Exit codes: 1 when a check fails (eg. `check` or `fmt --check`), 2 for invalid options or
+config files, 3 for schema errors, 4 when generating or writing code fails and 5 when a
+request to a server fails.
+",
+    );
+    out
+}
+
+/// Runs the command in `args` (without the program name) and returns the exit
+/// code of the process
+pub(crate) fn run(args: &[String]) -> u8 {
+    let (name, args) = match args.first().map(String::as_str) {
+        None => {
+            print!("{}", help());
+            return Failure::Usage(String::new()).exit_code();
+        }
+        Some("-h") | Some("--help") => {
+            print!("{}", help());
+            return 0;
+        }
+        Some("-V") | Some("--version") => {
+            println!("zetro {}", env!("CARGO_PKG_VERSION"));
+            return 0;
+        }
+        Some("help") => {
+            match args.get(1) {
+                Some(name) => match find_command(name) {
+                    Ok(command) => print!("{}", (command.help)()),
+                    Err(code) => return code,
+                },
+                None => print!("{}", help()),
+            }
+            return 0;
+        }
+        // Options without a command are a single `generate` target, as in
+        // versions before subcommands existed
+        Some(arg) if arg.starts_with("--") => ("generate", args),
+        Some(name) => (name, &args[1..]),
+    };
+
+    let command = match find_command(name) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", (command.help)());
+        return 0;
+    }
+
+    match (command.run)(args) {
+        Ok(()) => 0,
+        Err(failure) => {
+            match &failure {
+                Failure::Check => {}
+                Failure::Usage(e) => {
+                    eprintln!("{}\nRun `zetro {} --help` for usage", e, command.name)
+                }
+                Failure::Schema(e) => eprintln!("{}", e),
+                Failure::Generation(e) => eprintln!("{}", e),
+                Failure::Request(e) => eprintln!("{}", e),
+            }
+            failure.exit_code()
+        }
+    }
+}
+
+/// Looks up a command. Unknown commands are reported, and their exit code
+/// returned.
+fn find_command(name: &str) -> Result<&'static Command, u8> {
+    match COMMANDS.iter().find(|c| c.name == name) {
+        Some(v) => Ok(v),
+        None => {
+            eprintln!(
+                "Unknown command: '{}'\nRun `zetro --help` for a list of commands",
+                name
+            );
+            Err(Failure::Usage(String::new()).exit_code())
+        }
+    }
+}
+
+fn read_schema(path: &str) -> Result<ZetroSchema, Failure> {
+    source::read_schema(path).map_err(|e| Failure::Schema(e.to_string()))
+}
+
+fn read_config(path: &str) -> Result<project::ProjectConfig, Failure> {
+    project::ProjectConfig::read(path).map_err(Failure::Usage)
+}
+
+/// Reads the schema named by `--schema` or `--config`. Returns the path of the
+/// schema and the schema.
+fn read_schema_source(source: SchemaSource) -> Result<(String, ZetroSchema), Failure> {
+    let schema_file = match source {
+        SchemaSource::Schema(path) => path,
+        SchemaSource::Config(path) => read_config(&path)?.schema_file,
+    };
+    let schema = read_schema(&schema_file)?;
+    Ok((schema_file, schema))
+}
+
+/// Reads the targets of `generate`, `check` and `diff`, and the schema they
+/// share
+fn read_targets(source: TargetSource) -> Result<(Vec<ZetroArgs>, ZetroSchema), Failure> {
+    let (schema_file, targets) = match source {
+        TargetSource::Config(path) => {
+            let config = read_config(&path)?;
+            (config.schema_file, config.targets)
+        }
+        TargetSource::Target(target) => (target.schema_file.clone(), vec![target]),
+    };
+    let schema = read_schema(&schema_file)?;
+    Ok((targets, schema))
+}
+
+/// Generates a single target in memory
+fn generate_target(schema: &ZetroSchema, target: &ZetroArgs) -> Result<Generated, Failure> {
+    generators::generate(schema, target)
+        .map_err(|e| Failure::Generation(format!("error generating {}: {}", target.out_file, e)))
+}
+
+/// Writes `contents` to `path`, unless the file already has exactly that
+/// content. Untouched files don't trigger rebuilds in cargo or bundlers.
+/// Returns whether the file was written.
+fn write_if_changed(path: &str, contents: &str) -> Result<bool, Failure> {
+    if matches!(std::fs::read_to_string(path), Ok(existing) if existing == contents) {
+        return Ok(false);
+    }
+    // Plugins may generate files in new directories
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| Failure::Generation(format!("error writing {}: {}", path, e)))?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|e| Failure::Generation(format!("error writing {}: {}", path, e)))?;
+    Ok(true)
+}
+
+/// Files that split output of `target` wrote before but no longer produces,
+/// eg. the module of a route that was removed. Only files in the out dir with
+/// the extension of the language and the autogenerated comment count, so
+/// hand-written files there are never touched.
+fn stale_files(target: &ZetroArgs, outputs: &[(String, &str)]) -> Vec<String> {
+    let dir = match std::path::Path::new(&target.out_file).parent() {
+        Some(dir) if target.split => dir,
+        _ => return Vec::new(),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let extension = generators::generator(target.language).extension();
+
+    let mut stale = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == extension))
+        .filter(|path| {
+            !outputs
+                .iter()
+                .any(|(output, _)| path == std::path::Path::new(output))
+        })
+        .filter(|path| {
+            std::fs::read_to_string(path).is_ok_and(|contents| {
+                contents.contains("This is an autogenerated file. DO NOT EDIT")
+            })
+        })
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    stale.sort();
+    stale
+}
+
+/// Deletes the stale files of `target`, see `stale_files`
+fn remove_stale_files(
+    target: &ZetroArgs,
+    outputs: &[(String, &str)],
+) -> Result<Vec<String>, Failure> {
+    let stale = stale_files(target, outputs);
+    for path in &stale {
+        std::fs::remove_file(path)
+            .map_err(|e| Failure::Generation(format!("error removing {}: {}", path, e)))?;
+    }
+    Ok(stale)
+}
+
+/// Reads a JSON value from stdin. `what` names it in errors.
+fn read_stdin_json(what: &str) -> Result<serde_json::Value, Failure> {
+    let mut input = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+        .map_err(|e| Failure::Usage(format!("Error reading the {} from stdin: {}", what, e)))?;
+    serde_json::from_str(&input)
+        .map_err(|e| Failure::Usage(format!("The {} is not valid JSON: {}", what, e)))
+}
+
+fn usage<T>(result: Result<T, String>) -> Result<T, Failure> {
+    result.map_err(Failure::Usage)
+}
+
+/// Help for the options shared by `generate`, `check` and `diff`
+fn target_help() -> String {
+    format!(
+        "Targets:
+  Without options, the targets in ./zetro.toml are used.
+  --config <path>            Use the targets in this zetro.toml
+  or a single target:
+  --schema <path>            Schema file
+  --out-file <path>          File to generate. Its name must contain
+                             `_generated` or `-generated`
+  --out-dir <path>           Write one file per route to this directory
+                             instead, plus an index (mod.rs or index.ts)
+  --lang <ts|tsx|rs>         Language. Guessed from --out-file if omitted
+  --untagged <bool>          Encode structs as arrays (default: false)
+  --field-casing <snake|camel>
+                             Casing of field names. Not allowed with --untagged
+  --mangle <bool>            Mangle names in generated TypeScript
+  --add-plugin <call>        Run a plugin, eg. `warp(fnv:true)`. Repeatable
+  --keep-unused-types        Generate types that no route uses
+  --template <path>          Render this template instead of generating the
+                             types. See docs/templates.md
+  --ignore-out-naming        Allow any name for --out-file
+
+{}",
+        generators::plugin_help()
+    )
+}
+
+/// Help for the options of commands that only read a schema
+const SCHEMA_HELP: &str = "  Without options, the schema named in ./zetro.toml is used.
+  --schema <path>   Schema file
+  --config <path>   Use the schema named in this zetro.toml
+";
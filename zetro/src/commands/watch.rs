@@ -0,0 +1,161 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+use super::{
+    generate_target, read_config, read_schema, remove_stale_files, target_help, usage,
+    write_if_changed, Failure,
+};
+use crate::utilities::{self, TargetSource, ZetroArgs};
+
+/// Changes closer together than this are handled as a single change. Editors
+/// often write a file several times when saving.
+const DEBOUNCE_TIME: Duration = Duration::from_millis(200);
+
+pub(super) fn help() -> String {
+    format!(
+        "Generate every target, then regenerate them whenever the schema (or zetro.toml)
+changes. Errors are printed and watching continues. Only files whose content
+changed are rewritten. Stop with Ctrl+C.
+
+Usage: zetro watch [options]
+
+{}",
+        target_help()
+    )
+}
+
+/// `zetro watch`: Regenerates the targets on every change of their inputs
+pub(super) fn run(args: &[String]) -> Result<(), Failure> {
+    let source = usage(utilities::parse_target_args(args))?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIME, tx)
+        .map_err(|e| Failure::Usage(format!("error watching files: {}", e)))?;
+    let mut watched_dirs: HashSet<PathBuf> = HashSet::new();
+
+    loop {
+        // The config file may name another schema after a change, so the
+        // inputs are collected again after every build
+        let inputs = build(&source)
+            .iter()
+            .filter_map(|file| resolve(file))
+            .collect::<HashSet<_>>();
+
+        // Directories are watched instead of files, since editors often
+        // replace a file instead of writing to it
+        for (dir, _) in &inputs {
+            if watched_dirs.insert(dir.clone()) {
+                if let Err(e) = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive) {
+                    eprintln!("error watching {}: {}", dir.display(), e);
+                }
+            }
+        }
+        println!("Watching for changes...");
+
+        // Wait for a change to one of the inputs
+        loop {
+            match rx.recv() {
+                Ok(Ok(events)) => {
+                    if events
+                        .iter()
+                        .any(|event| inputs.iter().any(|(_, file)| file == &event.path))
+                    {
+                        break;
+                    }
+                }
+                Ok(Err(e)) => eprintln!("error watching files: {}", e),
+                Err(_) => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Generates every target and writes the ones that changed. Errors are
+/// printed. Returns the files the targets were built from.
+fn build(source: &TargetSource) -> Vec<String> {
+    let mut inputs: Vec<String> = Vec::new();
+
+    let config;
+    let targets = match source {
+        TargetSource::Config(path) => {
+            inputs.push(path.clone());
+            config = match read_config(path) {
+                Ok(v) => v,
+                Err(e) => {
+                    report(e);
+                    return inputs;
+                }
+            };
+            &config.targets
+        }
+        TargetSource::Target(target) => std::slice::from_ref(target),
+    };
+
+    // Every target of a config shares the same schema
+    let schema_file = &targets[0].schema_file;
+    inputs.push(schema_file.clone());
+    inputs.extend(targets.iter().filter_map(|target| target.template.clone()));
+    let schema = match read_schema(schema_file) {
+        Ok(v) => v,
+        Err(e) => {
+            report(e);
+            return inputs;
+        }
+    };
+
+    for target in targets {
+        match write_target(&schema, target) {
+            Ok(written) => written.iter().for_each(|path| println!("Wrote {}", path)),
+            Err(e) => report(e),
+        }
+    }
+
+    inputs
+}
+
+/// Writes the files of `target` and returns the ones that changed
+fn write_target(
+    schema: &crate::common::schema::ZetroSchema,
+    target: &ZetroArgs,
+) -> Result<Vec<String>, Failure> {
+    let generated = generate_target(schema, target)?;
+    let outputs = generated.outputs(&target.out_file);
+    let mut written = Vec::new();
+    for (path, contents) in &outputs {
+        if write_if_changed(path, contents)? {
+            written.push(path.clone());
+        }
+    }
+    for path in remove_stale_files(target, &outputs)? {
+        println!("Removed {}", path);
+    }
+    Ok(written)
+}
+
+fn report(failure: Failure) {
+    match failure {
+        Failure::Usage(e) | Failure::Schema(e) | Failure::Generation(e) | Failure::Request(e) => {
+            eprintln!("{}", e)
+        }
+        Failure::Check => {}
+    }
+}
+
+/// The absolute path of the directory containing `file`, and of `file`.
+/// `None` if the directory doesn't exist.
+fn resolve(file: &str) -> Option<(PathBuf, PathBuf)> {
+    let path = Path::new(file);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let dir = dir.canonicalize().ok()?;
+    let file = dir.join(path.file_name()?);
+    Some((dir, file))
+}
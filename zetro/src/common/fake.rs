@@ -0,0 +1,380 @@
+//! Fake values for the types of a schema, eg. for `zetro mock` and
+//! `zetro fixtures`. Values are readable JSON, as `wire::decode_request`
+//! prints them, and are the same for the same options.
+
+use std::ops::RangeInclusive;
+
+use serde_json::{json, Map, Value};
+
+use super::{
+    schema::{
+        fields::{FieldKind, ZetroField},
+        structs::ZetroStruct,
+        ZetroSchema,
+    },
+    wire,
+};
+
+/// Largest integer a JavaScript number holds exactly
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+const WORDS: [&str; 16] = [
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliett",
+    "kilo", "lima", "mike", "november", "oscar", "papa",
+];
+
+/// SplitMix64, a small generator that is good enough for test data and
+/// doesn't need a dependency
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..=max`
+    pub fn up_to(&mut self, max: u64) -> u64 {
+        match max.checked_add(1) {
+            Some(n) => self.next_u64() % n,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A number in `[0, 1)`
+    pub fn fraction(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// How fake values are generated
+#[derive(Debug, Clone)]
+pub struct FakeOptions {
+    /// Values are the same for the same seed and options
+    pub seed: u64,
+    /// How often nullable fields are null, from 0 to 1
+    pub null_probability: f64,
+    /// How many items lists have
+    pub list_len: RangeInclusive<usize>,
+    /// How deep structs are nested before nullable fields are always null
+    /// and lists are empty, so that recursive types end
+    pub max_depth: usize,
+}
+
+impl Default for FakeOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            null_probability: 0.25,
+            list_len: 0..=3,
+            max_depth: 4,
+        }
+    }
+}
+
+/// Why fake values could not be generated
+#[derive(Debug, Clone, PartialEq)]
+pub enum FakeError {
+    /// The options are invalid, eg. an empty list length range
+    Options(String),
+    /// No struct has this name
+    UnknownStruct(String),
+    /// A value of this enum is needed, but it has no variants
+    EmptyEnum(String),
+    /// This struct always contains itself, through fields that are neither
+    /// nullable nor lists, so it has no finite value
+    Infinite(String),
+}
+
+impl std::fmt::Display for FakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FakeError::Options(e) => write!(f, "{}", e),
+            FakeError::UnknownStruct(name) => write!(f, "No struct is named '{}'", name),
+            FakeError::EmptyEnum(name) => {
+                write!(f, "Enum '{}' has no variants, so it has no value", name)
+            }
+            FakeError::Infinite(name) => write!(
+                f,
+                "Struct '{}' always contains itself through fields that are not nullable or lists, so it has no value",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FakeError {}
+
+/// Generates fake values for the types of a schema
+pub(crate) struct Faker<'a> {
+    schema: &'a ZetroSchema,
+    options: FakeOptions,
+    rng: Rng,
+    /// Structs being generated below `max_depth`. Fields there are never
+    /// null nor lists, so meeting one of these again means it never ends.
+    required: Vec<String>,
+}
+
+impl<'a> Faker<'a> {
+    pub fn new(schema: &'a ZetroSchema, options: FakeOptions) -> Self {
+        Self {
+            schema,
+            rng: Rng::new(options.seed),
+            options,
+            required: Vec::new(),
+        }
+    }
+
+    /// A value for `field`, eg. the response of a route
+    pub fn field(&mut self, field: &ZetroField) -> Result<Value, FakeError> {
+        self.required.clear();
+        self.field_at(field, 0)
+    }
+
+    /// Fails if some value of `field` can't be generated with these options,
+    /// whatever the seed. Nullable fields are never null and lists have one
+    /// item here, so that every type a value may contain is visited.
+    pub fn check(&self, field: &ZetroField) -> Result<(), FakeError> {
+        let options = FakeOptions {
+            null_probability: 0.0,
+            list_len: 1..=1,
+            ..self.options.clone()
+        };
+        Faker::new(self.schema, options).field(field).map(|_| ())
+    }
+
+    fn field_at(&mut self, field: &ZetroField, depth: usize) -> Result<Value, FakeError> {
+        let deep = depth >= self.options.max_depth;
+        if field.is_nullable && (deep || self.rng.fraction() < self.options.null_probability) {
+            return Ok(Value::Null);
+        }
+        if !field.is_multiple {
+            return self.single(&field.kind, depth);
+        }
+        let (min, max) = (*self.options.list_len.start(), *self.options.list_len.end());
+        let len = if deep {
+            0
+        } else {
+            min + self.rng.up_to(max.saturating_sub(min) as u64) as usize
+        };
+        let values = (0..len)
+            .map(|_| self.single(&field.kind, depth))
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(values))
+    }
+
+    fn single(&mut self, kind: &FieldKind, depth: usize) -> Result<Value, FakeError> {
+        let schema = self.schema;
+        let rng = &mut self.rng;
+        let signed =
+            |rng: &mut Rng, min: i64, max: i64| json!(min + rng.up_to(max.abs_diff(min)) as i64);
+        let value = match kind {
+            FieldKind::Int8 => signed(rng, i8::MIN.into(), i8::MAX.into()),
+            FieldKind::Int16 => signed(rng, i16::MIN.into(), i16::MAX.into()),
+            FieldKind::Int32 => signed(rng, i32::MIN.into(), i32::MAX.into()),
+            FieldKind::Int64 => signed(rng, -(MAX_SAFE_INTEGER as i64), MAX_SAFE_INTEGER as i64),
+            FieldKind::UInt8 => json!(rng.up_to(u8::MAX.into())),
+            FieldKind::UInt16 => json!(rng.up_to(u16::MAX.into())),
+            FieldKind::UInt32 => json!(rng.up_to(u32::MAX.into())),
+            FieldKind::UInt64 => json!(rng.up_to(MAX_SAFE_INTEGER)),
+            FieldKind::Float32 | FieldKind::Float64 => {
+                json!((rng.fraction() * 100_000.0).round() / 100.0)
+            }
+            FieldKind::Boolean => json!(rng.up_to(1) == 1),
+            FieldKind::StringValue => {
+                let words = (0..=rng.up_to(2))
+                    .map(|_| WORDS[rng.up_to(WORDS.len() as u64 - 1) as usize])
+                    .collect::<Vec<_>>();
+                json!(words.join(" "))
+            }
+            FieldKind::EnumValue(name) => {
+                let variants = schema
+                    .enums
+                    .iter()
+                    .find(|e| &e.name == name)
+                    .map(|e| e.variants.as_slice())
+                    .unwrap_or_default();
+                match variants.len() {
+                    0 => return Err(FakeError::EmptyEnum(name.clone())),
+                    n => json!(variants[rng.up_to(n as u64 - 1) as usize]),
+                }
+            }
+            FieldKind::StructValue(name) => match schema.structs.iter().find(|s| &s.name == name) {
+                Some(_struct) => return self.structure(_struct, depth + 1),
+                None => return Err(FakeError::UnknownStruct(name.clone())),
+            },
+            FieldKind::NestedObject(_struct) => return self.structure(_struct, depth + 1),
+        };
+        Ok(value)
+    }
+
+    fn structure(&mut self, _struct: &ZetroStruct, depth: usize) -> Result<Value, FakeError> {
+        let required = depth >= self.options.max_depth;
+        if required {
+            if self.required.contains(&_struct.name) {
+                return Err(FakeError::Infinite(_struct.name.clone()));
+            }
+            self.required.push(_struct.name.clone());
+        }
+        let mut object = Map::new();
+        for field in &_struct.fields {
+            object.insert(field.name.clone(), self.field_at(field, depth)?);
+        }
+        if required {
+            self.required.pop();
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+/// `count` fake instances of the struct `name`, encoded as on the wire
+pub(crate) fn fake_structs(
+    schema: &ZetroSchema,
+    name: &str,
+    count: usize,
+    untagged: bool,
+    options: &FakeOptions,
+) -> Result<Vec<Value>, FakeError> {
+    if !schema.structs.iter().any(|s| s.name == name) {
+        return Err(FakeError::UnknownStruct(name.to_owned()));
+    }
+    if !(0.0..=1.0).contains(&options.null_probability) {
+        return Err(FakeError::Options(format!(
+            "The null probability must be between 0 and 1, got {}",
+            options.null_probability
+        )));
+    }
+    if options.list_len.is_empty() {
+        return Err(FakeError::Options(format!(
+            "The list length range {}..={} is empty",
+            options.list_len.start(),
+            options.list_len.end()
+        )));
+    }
+
+    let field = ZetroField {
+        name: name.to_owned(),
+        description: None,
+        kind: FieldKind::StructValue(name.to_owned()),
+        is_nullable: false,
+        is_multiple: false,
+        is_recursive: false,
+    };
+    let mut faker = Faker::new(schema, options.clone());
+    faker.check(&field)?;
+    (0..count)
+        .map(|_| {
+            let value = faker.field(&field)?;
+            Ok(wire::encode_body(schema, &field, &value, untagged, name)
+                .expect("fake values match the schema"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{fake_structs, FakeError, FakeOptions, Faker};
+    use crate::common::{schema::ZetroSchema, wire};
+
+    /// Fake values are valid for their type, and the same for a seed
+    #[test]
+    fn fakes_valid_values() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Room": {
+                    "description": "",
+                    "fields": {
+                        "children": "?[]struct~Room",
+                        "parent": "?struct~Room",
+                        "score": "f32",
+                        "size": "i8",
+                        "status": "enum~Status",
+                        "tags": "[]string",
+                        "meta": { "description": "", "fields": { "id": "u64" } }
+                    }
+                }
+            },
+            "enums": { "Status": ["ACTIVE", "DISABLED"] },
+            "routes": {
+                "GetRoom": { "kind": "query", "description": "", "request": "u8", "response": "[]struct~Room" }
+            }
+        }))
+        .unwrap();
+        let response = &schema.queries[0].response_body;
+
+        for seed in 0..50 {
+            let options = FakeOptions {
+                seed,
+                ..Default::default()
+            };
+            let value = Faker::new(&schema, options.clone())
+                .field(response)
+                .unwrap();
+            assert_eq!(value, Faker::new(&schema, options).field(response).unwrap());
+            for untagged in [true, false] {
+                let encoded = wire::encode_body(&schema, response, &value, untagged, "$");
+                assert!(encoded.is_ok(), "{}: {:?}", value, encoded);
+            }
+        }
+
+        // Lists have a length in the range. Below the maximum depth, nullable
+        // fields are null and lists are empty.
+        let options = FakeOptions {
+            null_probability: 0.0,
+            list_len: 2..=2,
+            max_depth: 2,
+            ..Default::default()
+        };
+        let rooms = Faker::new(&schema, options).field(response).unwrap();
+        let room = &rooms[0];
+        assert_eq!(rooms.as_array().unwrap().len(), 2);
+        assert_eq!(room["tags"].as_array().unwrap().len(), 2);
+        assert!(room["parent"].is_object());
+        assert!(room["parent"]["parent"].is_null());
+        assert_eq!(room["parent"]["tags"], json!([]));
+    }
+
+    /// Types without a finite value are errors, whatever the seed, instead
+    /// of panics
+    #[test]
+    fn rejects_types_without_values() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "A": { "description": "", "fields": { "b": "struct~B" } },
+                "B": { "description": "", "fields": { "a": "struct~A" } },
+                "Empty": { "description": "", "fields": { "status": "?enum~Nothing" } },
+                // Ends through the nullable field, so it has values
+                "Tree": { "description": "", "fields": { "child": "?struct~Leaf" } },
+                "Leaf": { "description": "", "fields": { "tree": "struct~Tree" } }
+            },
+            "enums": { "Nothing": [] },
+            "routes": {}
+        }))
+        .unwrap();
+
+        for seed in 0..20 {
+            let options = FakeOptions {
+                seed,
+                ..Default::default()
+            };
+            assert!(matches!(
+                fake_structs(&schema, "A", 3, true, &options),
+                Err(FakeError::Infinite(_))
+            ));
+            assert_eq!(
+                fake_structs(&schema, "Empty", 3, true, &options),
+                Err(FakeError::EmptyEnum(String::from("Nothing")))
+            );
+            assert!(fake_structs(&schema, "Tree", 3, true, &options).is_ok());
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use super::Span;
+use crate::common::schema::routes::RouteKind;
+
+/// A parsed `.zetro` file. The tree is purely syntactic: names are not
+/// resolved and nothing is validated until it is lowered into a
+/// `ZetroSchema`.
+#[derive(Debug)]
+pub(crate) struct Document {
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Item {
+    /// Joined `///` lines preceding the item
+    pub docs: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub name: Ident,
+    pub kind: ItemKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum ItemKind {
+    /// `struct Name { field: type ... }`
+    Struct(Vec<FieldDecl>),
+    /// `enum Name { VARIANT, ... }`
+    Enum(Vec<VariantDecl>),
+    /// `query Name { request: type response: type }` (or `mutation`)
+    Route(RouteKind, Vec<FieldDecl>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+/// `@name` or `@name(arg, ...)`
+#[derive(Debug)]
+pub(crate) struct Attribute {
+    pub name: Ident,
+    pub args: Vec<AttributeArg>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub(crate) enum AttributeArg {
+    Ident(Ident),
+    Str(String, Span),
+    Int(i64, Span),
+}
+
+#[derive(Debug)]
+pub(crate) struct FieldDecl {
+    /// `///` lines before the field and/or a `//` comment trailing it
+    pub docs: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub name: Ident,
+    pub ty: TypeExpr,
+}
+
+#[derive(Debug)]
+pub(crate) struct VariantDecl {
+    pub attributes: Vec<Attribute>,
+    pub name: Ident,
+}
+
+/// `?[]Name<Args>` or `?[]{ fields }`
+#[derive(Debug)]
+pub(crate) struct TypeExpr {
+    pub nullable: bool,
+    pub multiple: bool,
+    pub kind: TypeKind,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub(crate) enum TypeKind {
+    /// A primitive, struct or enum name, with optional generic arguments
+    Named {
+        name: Ident,
+        generics: Vec<TypeExpr>,
+    },
+    /// An inline object
+    Object(Vec<FieldDecl>),
+}
@@ -0,0 +1,279 @@
+use super::{IdlError, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    /// An identifier or a keyword. Keywords are contextual, so a field may
+    /// still be called `query` or `struct`.
+    Ident(String),
+    /// A string literal, with escapes already resolved
+    Str(String),
+    Int(i64),
+    /// `/// text`. Attaches to the item, field or variant that follows it.
+    DocComment(String),
+    /// `// text`. Only meaningful when it trails a field on the same line.
+    LineComment(String),
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    LAngle,
+    RAngle,
+    LBracket,
+    RBracket,
+    Question,
+    Colon,
+    Comma,
+    Semicolon,
+    At,
+    Eof,
+}
+
+impl TokenKind {
+    /// Human-readable description used in error messages
+    pub fn describe(&self) -> String {
+        match self {
+            TokenKind::Ident(v) => format!("'{}'", v),
+            TokenKind::Str(v) => format!("\"{}\"", v),
+            TokenKind::Int(v) => format!("'{}'", v),
+            TokenKind::DocComment(_) => String::from("doc comment"),
+            TokenKind::LineComment(_) => String::from("comment"),
+            TokenKind::LBrace => String::from("'{'"),
+            TokenKind::RBrace => String::from("'}'"),
+            TokenKind::LParen => String::from("'('"),
+            TokenKind::RParen => String::from("')'"),
+            TokenKind::LAngle => String::from("'<'"),
+            TokenKind::RAngle => String::from("'>'"),
+            TokenKind::LBracket => String::from("'['"),
+            TokenKind::RBracket => String::from("']'"),
+            TokenKind::Question => String::from("'?'"),
+            TokenKind::Colon => String::from("':'"),
+            TokenKind::Comma => String::from("','"),
+            TokenKind::Semicolon => String::from("';'"),
+            TokenKind::At => String::from("'@'"),
+            TokenKind::Eof => String::from("end of file"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Zero-based line the token starts on. Used to tell trailing comments
+    /// apart from comments on their own line.
+    pub line: usize,
+}
+
+/// Splits `source` into tokens. Unknown characters and unterminated literals
+/// are reported and skipped, so lexing always produces a token stream ending
+/// with `TokenKind::Eof`.
+pub(crate) fn tokenize(source: &str) -> (Vec<Token>, Vec<IdlError>) {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut errors: Vec<IdlError> = Vec::new();
+
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+    let mut line = 0;
+
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+
+        let kind = match c {
+            b'\n' => {
+                line += 1;
+                pos += 1;
+                continue;
+            }
+            b' ' | b'\t' | b'\r' => {
+                pos += 1;
+                continue;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                let end = source[pos..]
+                    .find('\n')
+                    .map_or(bytes.len(), |idx| pos + idx);
+                let text = &source[pos..end];
+                pos = end;
+                if let Some(doc) = text.strip_prefix("///") {
+                    TokenKind::DocComment(doc.trim().to_owned())
+                } else {
+                    TokenKind::LineComment(text[2..].trim().to_owned())
+                }
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                match source[pos + 2..].find("*/") {
+                    Some(idx) => {
+                        let end = pos + 2 + idx + 2;
+                        line += source[pos..end].matches('\n').count();
+                        pos = end;
+                    }
+                    None => {
+                        errors.push(IdlError::new(
+                            Span::new(pos, bytes.len()),
+                            "unterminated block comment",
+                        ));
+                        pos = bytes.len();
+                    }
+                }
+                continue;
+            }
+            b'"' => {
+                pos += 1;
+                let mut value = String::new();
+                let mut terminated = false;
+                while let Some(ch) = source[pos..].chars().next() {
+                    pos += ch.len_utf8();
+                    match ch {
+                        '"' => {
+                            terminated = true;
+                            break;
+                        }
+                        '\n' => {
+                            // Strings can't span lines. Leave the newline for
+                            // the line counter.
+                            pos -= 1;
+                            break;
+                        }
+                        '\\' => {
+                            let escaped = source[pos..].chars().next();
+                            pos += escaped.map_or(0, |e| e.len_utf8());
+                            match escaped {
+                                Some('n') => value.push('\n'),
+                                Some('t') => value.push('\t'),
+                                Some('"') => value.push('"'),
+                                Some('\\') => value.push('\\'),
+                                Some(other) => {
+                                    errors.push(IdlError::new(
+                                        Span::new(pos - 1 - other.len_utf8(), pos),
+                                        format!("unknown escape sequence '\\{}'", other),
+                                    ));
+                                }
+                                None => {}
+                            }
+                        }
+                        _ => value.push(ch),
+                    }
+                }
+                if !terminated {
+                    errors.push(IdlError::new(
+                        Span::new(start, pos),
+                        "unterminated string literal",
+                    ));
+                }
+                TokenKind::Str(value)
+            }
+            b'0'..=b'9' | b'-' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                match source[start..pos].parse::<i64>() {
+                    Ok(v) => TokenKind::Int(v),
+                    Err(_) => {
+                        errors.push(IdlError::new(
+                            Span::new(start, pos),
+                            format!("invalid integer literal '{}'", &source[start..pos]),
+                        ));
+                        continue;
+                    }
+                }
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                {
+                    pos += 1;
+                }
+                TokenKind::Ident(source[start..pos].to_owned())
+            }
+            _ => {
+                pos += 1;
+                match c {
+                    b'{' => TokenKind::LBrace,
+                    b'}' => TokenKind::RBrace,
+                    b'(' => TokenKind::LParen,
+                    b')' => TokenKind::RParen,
+                    b'<' => TokenKind::LAngle,
+                    b'>' => TokenKind::RAngle,
+                    b'[' => TokenKind::LBracket,
+                    b']' => TokenKind::RBracket,
+                    b'?' => TokenKind::Question,
+                    b':' => TokenKind::Colon,
+                    b',' => TokenKind::Comma,
+                    b';' => TokenKind::Semicolon,
+                    b'@' => TokenKind::At,
+                    _ => {
+                        // Skip the whole (possibly multi-byte) character
+                        let ch = source[start..].chars().next().unwrap();
+                        pos = start + ch.len_utf8();
+                        errors.push(IdlError::new(
+                            Span::new(start, pos),
+                            format!("unexpected character '{}'", ch),
+                        ));
+                        continue;
+                    }
+                }
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, pos),
+            line,
+        });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        span: Span::new(bytes.len(), bytes.len()),
+        line,
+    });
+
+    (tokens, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, TokenKind};
+
+    #[test]
+    fn comments_and_literals() {
+        let (tokens, errors) = tokenize(
+            "/// Doc\nstruct A { id: ?[]u64 // trailing\n /* block\n comment */ s: \"a\\\"b\" -3 }",
+        );
+        assert!(errors.is_empty());
+
+        let kinds = tokens.into_iter().map(|t| t.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::DocComment(String::from("Doc")),
+                TokenKind::Ident(String::from("struct")),
+                TokenKind::Ident(String::from("A")),
+                TokenKind::LBrace,
+                TokenKind::Ident(String::from("id")),
+                TokenKind::Colon,
+                TokenKind::Question,
+                TokenKind::LBracket,
+                TokenKind::RBracket,
+                TokenKind::Ident(String::from("u64")),
+                TokenKind::LineComment(String::from("trailing")),
+                TokenKind::Ident(String::from("s")),
+                TokenKind::Colon,
+                TokenKind::Str(String::from("a\"b")),
+                TokenKind::Int(-3),
+                TokenKind::RBrace,
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    /// Bad characters are reported and skipped instead of aborting
+    #[test]
+    fn recovers_from_bad_characters() {
+        let (tokens, errors) = tokenize("struct # A é {}");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(tokens.len(), 5);
+    }
+}
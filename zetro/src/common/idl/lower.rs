@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+
+use super::{
+    ast::{
+        Attribute, AttributeArg, Document, FieldDecl, Ident, Item, ItemKind, TypeExpr, TypeKind,
+    },
+    IdlError,
+};
+use crate::common::schema::{
+    enums::ZetroEnum,
+    fields::{FieldKind, ZetroField},
+    routes::{RouteKind, ZetroRoute},
+    structs::{self, ZetroStruct},
+    ZetroSchema,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Symbol {
+    Struct,
+    Enum,
+}
+
+/// Lowers a parsed document into a `ZetroSchema`.
+///
+/// This enforces the same rules as `ZetroSchema::from_value`, but since names
+/// are resolved here every error can point at the offending span. As with
+/// JSON schemas, structs, enums and routes are ordered by name, and fields are
+/// ordered alphabetically.
+pub(crate) fn lower(document: &Document) -> Result<ZetroSchema, Vec<IdlError>> {
+    let mut lowering = Lowering {
+        symbols: HashMap::new(),
+        errors: Vec::new(),
+    };
+
+    // Declare all types first so fields can reference types declared later
+    let mut routes: HashMap<&str, &Ident> = HashMap::new();
+    for item in &document.items {
+        let symbol = match &item.kind {
+            ItemKind::Struct(_) => Symbol::Struct,
+            ItemKind::Enum(_) => Symbol::Enum,
+            ItemKind::Route(..) => {
+                if routes.insert(&item.name.name, &item.name).is_some() {
+                    lowering.error(&item.name, format!("duplicate route '{}'", item.name.name));
+                }
+                continue;
+            }
+        };
+        if lowering
+            .symbols
+            .insert(item.name.name.clone(), symbol)
+            .is_some()
+        {
+            lowering.error(&item.name, format!("duplicate type '{}'", item.name.name));
+        }
+    }
+
+    let mut schema = ZetroSchema {
+        structs: Vec::new(),
+        enums: Vec::new(),
+        queries: Vec::new(),
+        mutations: Vec::new(),
+    };
+
+    for item in &document.items {
+        let lint_allow = lowering.lint_allow(&item.attributes);
+
+        match &item.kind {
+            ItemKind::Struct(fields) => {
+                let description = lowering.require_docs(item, "struct");
+                let fields = lowering.lower_fields(&item.name.name, fields);
+                schema.structs.push(ZetroStruct {
+                    name: item.name.name.clone(),
+                    description,
+                    is_nullable: false,
+                    is_multiple: false,
+                    fields,
+                    lint_allow,
+                });
+            }
+            ItemKind::Enum(variants) => {
+                if item.docs.is_some() {
+                    // Enums have nowhere to store a description
+                    lowering.error(&item.name, "doc comments are not supported on enums");
+                }
+                let mut names: Vec<String> = Vec::new();
+                for variant in variants {
+                    lowering.reject_attributes(&variant.attributes);
+                    if names.contains(&variant.name.name) {
+                        lowering.error(
+                            &variant.name,
+                            format!("duplicate variant '{}'", variant.name.name),
+                        );
+                    }
+                    names.push(variant.name.name.clone());
+                }
+                schema.enums.push(ZetroEnum {
+                    name: item.name.name.clone(),
+                    variants: names,
+                    lint_allow,
+                });
+            }
+            ItemKind::Route(kind, fields) => {
+                if let Some(route) = lowering.lower_route(item, kind.clone(), fields, lint_allow) {
+                    match kind {
+                        RouteKind::Query => schema.queries.push(route),
+                        RouteKind::Mutation => schema.mutations.push(route),
+                    }
+                }
+            }
+        }
+    }
+
+    if !lowering.errors.is_empty() {
+        lowering.errors.sort_by_key(|e| e.span.start);
+        return Err(lowering.errors);
+    }
+
+    schema.structs.sort_by(|a, b| a.name.cmp(&b.name));
+    schema.enums.sort_by(|a, b| a.name.cmp(&b.name));
+    schema.queries.sort_by(|a, b| a.name.cmp(&b.name));
+    schema.mutations.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(schema)
+}
+
+struct Lowering {
+    symbols: HashMap<String, Symbol>,
+    errors: Vec<IdlError>,
+}
+
+impl Lowering {
+    fn error(&mut self, ident: &Ident, message: impl Into<String>) {
+        self.errors.push(IdlError::new(ident.span, message));
+    }
+
+    /// Collects the lint rules suppressed by `@allow("rule", ...)`, the only
+    /// attribute so far. Structs, enums, routes and inline objects accept it.
+    fn lint_allow(&mut self, attributes: &[Attribute]) -> Vec<String> {
+        let mut rules: Vec<String> = Vec::new();
+
+        for attribute in attributes {
+            if attribute.name.name != "allow" {
+                self.error(
+                    &attribute.name,
+                    format!("unknown attribute '@{}'", attribute.name.name),
+                );
+                continue;
+            }
+            if attribute.args.is_empty() {
+                self.errors.push(IdlError::new(
+                    attribute.span,
+                    "'@allow' expects at least one rule name",
+                ));
+            }
+            for arg in &attribute.args {
+                let (span, found) = match arg {
+                    AttributeArg::Str(rule, span) if rule.is_empty() => (*span, String::new()),
+                    AttributeArg::Str(rule, _) => {
+                        rules.push(rule.clone());
+                        continue;
+                    }
+                    AttributeArg::Ident(ident) => (ident.span, ident.name.clone()),
+                    AttributeArg::Int(v, span) => (*span, v.to_string()),
+                };
+                self.errors.push(IdlError::new(
+                    span,
+                    format!(
+                        "expected a rule name in quotes, eg. @allow(\"unused-type\"). Found '{}'",
+                        found
+                    ),
+                ));
+            }
+        }
+
+        rules
+    }
+
+    /// Reports attributes on elements that can't have any
+    fn reject_attributes(&mut self, attributes: &[Attribute]) {
+        for attribute in attributes {
+            let message = if attribute.name.name == "allow" {
+                String::from(
+                    "'@allow' can only be used on structs, enums, routes and inline objects",
+                )
+            } else {
+                format!("unknown attribute '@{}'", attribute.name.name)
+            };
+            self.errors.push(IdlError::new(attribute.span, message));
+        }
+    }
+
+    fn require_docs(&mut self, item: &Item, what: &str) -> String {
+        match &item.docs {
+            Some(d) => d.clone(),
+            None => {
+                self.error(
+                    &item.name,
+                    format!(
+                        "{} '{}' needs a description. Add a `///` comment above it",
+                        what, item.name.name
+                    ),
+                );
+                String::new()
+            }
+        }
+    }
+
+    /// Lowers the fields of a struct (or nested object) named `parent`
+    fn lower_fields(&mut self, parent: &str, fields: &[FieldDecl]) -> Vec<ZetroField> {
+        let mut lowered: Vec<ZetroField> = Vec::new();
+
+        for field in fields {
+            if lowered.iter().any(|f| f.name == field.name.name) {
+                self.error(
+                    &field.name,
+                    format!("duplicate field '{}'", field.name.name),
+                );
+                continue;
+            }
+            if let Some(f) = self.lower_field(parent, field) {
+                lowered.push(f);
+            }
+        }
+
+        lowered.sort_by(|a, b| a.name.cmp(&b.name));
+        lowered
+    }
+
+    fn lower_field(&mut self, parent: &str, field: &FieldDecl) -> Option<ZetroField> {
+        let ty = &field.ty;
+        let mut is_recursive = false;
+
+        let (kind, description) = match &ty.kind {
+            TypeKind::Object(fields) => {
+                // Nested objects become structs named {Parent}_{field}. Their
+                // description is the field's description.
+                let name = structs::generate_nested_struct_name(parent, &field.name.name);
+                let description = match &field.docs {
+                    Some(d) => d.clone(),
+                    None => {
+                        self.error(
+                            &field.name,
+                            format!(
+                                "inline object '{}' needs a description. Add a `///` comment above it",
+                                field.name.name
+                            ),
+                        );
+                        String::new()
+                    }
+                };
+                let lint_allow = self.lint_allow(&field.attributes);
+                let fields = self.lower_fields(&name, fields);
+                let nested = ZetroStruct {
+                    name,
+                    description,
+                    is_nullable: ty.nullable,
+                    is_multiple: ty.multiple,
+                    fields,
+                    lint_allow,
+                };
+                (FieldKind::NestedObject(nested), None)
+            }
+            TypeKind::Named { name, generics } => {
+                self.reject_attributes(&field.attributes);
+                let kind = self.resolve_type(name, generics, ty)?;
+                if let FieldKind::StructValue(struct_name) = &kind {
+                    is_recursive = struct_name == parent;
+                    if is_recursive && !ty.nullable && !ty.multiple {
+                        self.errors.push(IdlError::new(
+                            ty.span,
+                            format!(
+                                "recursive field '{}' must be nullable and/or a list to avoid an infinite loop",
+                                field.name.name
+                            ),
+                        ));
+                        return None;
+                    }
+                }
+                (kind, field.docs.clone())
+            }
+        };
+
+        Some(ZetroField {
+            description,
+            name: field.name.name.clone(),
+            kind,
+            is_nullable: ty.nullable,
+            is_multiple: ty.multiple,
+            is_recursive,
+        })
+    }
+
+    fn resolve_type(
+        &mut self,
+        name: &Ident,
+        generics: &[TypeExpr],
+        ty: &TypeExpr,
+    ) -> Option<FieldKind> {
+        if !generics.is_empty() {
+            self.errors.push(IdlError::new(
+                ty.span,
+                format!("generic types are not supported yet ('{}')", name.name),
+            ));
+            return None;
+        }
+        if let Some(primitive) = FieldKind::from_primitive_name(&name.name) {
+            return Some(primitive);
+        }
+        match self.symbols.get(&name.name) {
+            Some(Symbol::Struct) => Some(FieldKind::StructValue(name.name.clone())),
+            Some(Symbol::Enum) => Some(FieldKind::EnumValue(name.name.clone())),
+            None => {
+                self.error(name, format!("unknown type '{}'", name.name));
+                None
+            }
+        }
+    }
+
+    fn lower_route(
+        &mut self,
+        item: &Item,
+        kind: RouteKind,
+        fields: &[FieldDecl],
+        lint_allow: Vec<String>,
+    ) -> Option<ZetroRoute> {
+        let description = self.require_docs(item, "route");
+        let mut request: Option<ZetroField> = None;
+        let mut response: Option<ZetroField> = None;
+
+        for field in fields {
+            let slot = match field.name.name.as_str() {
+                "request" => &mut request,
+                "response" => &mut response,
+                other => {
+                    self.error(
+                        &field.name,
+                        format!(
+                            "unknown route field '{}'. Expected 'request' or 'response'",
+                            other
+                        ),
+                    );
+                    continue;
+                }
+            };
+            if slot.is_some() {
+                self.error(
+                    &field.name,
+                    format!("duplicate field '{}'", field.name.name),
+                );
+                continue;
+            }
+            if let TypeKind::Object(_) = field.ty.kind {
+                self.errors.push(IdlError::new(
+                    field.ty.span,
+                    format!(
+                        "route {} can not be an inline object. Declare a struct instead",
+                        field.name.name
+                    ),
+                ));
+                continue;
+            }
+            *slot = self.lower_field(&item.name.name, field);
+        }
+
+        for (name, slot) in [("request", &request), ("response", &response)] {
+            if slot.is_none() && !fields.iter().any(|f| f.name.name == name) {
+                self.error(
+                    &item.name,
+                    format!("route '{}' is missing '{}'", item.name.name, name),
+                );
+            }
+        }
+
+        Some(ZetroRoute {
+            kind,
+            name: item.name.name.clone(),
+            description,
+            request_body: request?,
+            response_body: response?,
+            lint_allow,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::parse_schema;
+    use crate::common::schema::{fields::FieldKind, ZetroSchema};
+
+    /// A `.zetro` file must lower into the same IR as the equivalent JSON
+    #[test]
+    fn matches_json_schema() {
+        let idl = parse_schema(
+            "/// A message
+            struct Message {
+                text: string // Content; may contain separators
+                id: u64
+                parent: ?Message
+                /// Read receipts
+                receipts: ?[]{
+                    user: string
+                    status: Status
+                }
+            }
+
+            enum Status { SENT, READ }
+
+            /// Send a message
+            mutation SendMessage {
+                request: Message
+                response: u64 // ID of created message
+            }",
+        )
+        .unwrap();
+        let json = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": {
+                    "description": "A message",
+                    "fields": {
+                        "text": "string; Content; may contain separators",
+                        "id": "u64",
+                        "parent": "?struct~Message",
+                        "receipts": {
+                            "description": "Read receipts",
+                            "nullable": true,
+                            "multiple": true,
+                            "fields": {
+                                "user": "string",
+                                "status": "enum~Status",
+                            }
+                        }
+                    }
+                }
+            },
+            "enums": { "Status": ["SENT", "READ"] },
+            "routes": {
+                "SendMessage": {
+                    "kind": "mutation",
+                    "description": "Send a message",
+                    "request": "struct~Message",
+                    "response": "u64; ID of created message",
+                }
+            }
+        }))
+        .unwrap();
+
+        // The IR has no PartialEq, but its debug output is exhaustive
+        assert_eq!(format!("{:#?}", idl), format!("{:#?}", json));
+        match &idl.structs[0].fields[1].kind {
+            FieldKind::StructValue(name) => assert_eq!(name, "Message"),
+            _ => panic!("expected field to be a struct reference"),
+        }
+    }
+
+    #[test]
+    fn reports_all_semantic_errors() {
+        let errors = parse_schema(
+            "struct A {
+                a: Missing
+                b: A
+                c: Map<string, u64>
+            }
+            /// B
+            query B {
+                request: string
+            }",
+        )
+        .unwrap_err();
+
+        let messages = errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 5, "{:?}", messages);
+        assert!(messages[0].starts_with("struct 'A' needs a description"));
+        assert!(messages[1].starts_with("unknown type 'Missing'"));
+        assert!(messages[2].starts_with("recursive field 'b'"));
+        assert!(messages[3].starts_with("generic types are not supported"));
+        assert!(messages[4].starts_with("route 'B' is missing 'response'"));
+    }
+
+    /// `@allow` suppresses lint rules on structs, enums, routes and inline
+    /// objects. Anything else is an error.
+    #[test]
+    fn lint_allow_attributes() {
+        let schema = parse_schema(
+            "/// A
+            @allow(\"unused-type\", \"struct-name-case\")
+            struct a {
+                /// Nested
+                @allow(\"field-name-case\")
+                b: { c_d: u8 }
+            }
+            @allow(\"large-enum\")
+            enum E { X }",
+        )
+        .unwrap();
+        assert_eq!(
+            schema.structs[0].lint_allow,
+            vec!["unused-type", "struct-name-case"]
+        );
+        match &schema.structs[0].fields[0].kind {
+            FieldKind::NestedObject(nested) => {
+                assert_eq!(nested.lint_allow, vec!["field-name-case"])
+            }
+            _ => panic!("expected field to be a nested object"),
+        }
+        assert_eq!(schema.enums[0].lint_allow, vec!["large-enum"]);
+
+        let errors = parse_schema(
+            "/// A
+            @allow(unused_type)
+            @deprecated
+            struct A {
+                @allow(\"field-name-case\")
+                b: u8
+            }",
+        )
+        .unwrap_err();
+        let messages = errors
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(messages[0].starts_with("expected a rule name in quotes"));
+        assert!(messages[1].starts_with("unknown attribute '@deprecated'"));
+        assert!(messages[2].starts_with("'@allow' can only be used on"));
+    }
+}
@@ -0,0 +1,81 @@
+//! The `.zetro` interface definition language.
+//!
+//! A `.zetro` file describes the same schema as `schema.json`, but with a
+//! real grammar instead of stringly typed field expressions:
+//!
+//! ```text
+//! /// Resembles a single chat message in a room
+//! struct Message {
+//!     id: u64 // Unique id for this message
+//!     author: AuthorRef
+//!     tags: ?[]string
+//! }
+//!
+//! enum RoomStatus { ACTIVE, DISABLED }
+//!
+//! /// Get all rooms
+//! query GetRooms {
+//!     request: GetRoomsRequest
+//!     response: GetRoomsResponse
+//! }
+//! ```
+//!
+//! Source text goes through `lexer` -> `parser` -> `lower`, the last of which
+//! produces a `ZetroSchema`. `printer` goes the other way, which is how JSON
+//! schemas are converted.
+
+use crate::common::schema::ZetroSchema;
+
+pub(crate) mod ast;
+pub(crate) mod lexer;
+pub(crate) mod lower;
+pub(crate) mod parser;
+pub(crate) mod printer;
+
+/// A byte range in the source text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// A span covering both `self` and `other`
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct IdlError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl IdlError {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parses and lowers a `.zetro` file. All syntax errors are reported; lowering
+/// only runs (and reports its own errors) if the syntax was valid.
+pub(crate) fn parse_schema(source: &str) -> Result<ZetroSchema, Vec<IdlError>> {
+    let (tokens, mut errors) = lexer::tokenize(source);
+    let (document, parse_errors) = parser::parse(tokens);
+    errors.extend(parse_errors);
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|e| e.span.start);
+        return Err(errors);
+    }
+
+    lower::lower(&document)
+}
@@ -0,0 +1,622 @@
+use super::{
+    ast::{
+        Attribute, AttributeArg, Document, FieldDecl, Ident, Item, ItemKind, TypeExpr, TypeKind,
+        VariantDecl,
+    },
+    lexer::{Token, TokenKind},
+    IdlError, Span,
+};
+use crate::common::schema::routes::RouteKind;
+
+/// Keywords that start a top-level item. Used to resynchronize after errors.
+const ITEM_KEYWORDS: [&str; 4] = ["struct", "enum", "query", "mutation"];
+
+/// How deep generics and inline objects may nest, like serde_json's
+/// recursion limit. Deeper types are errors instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+type ParseResult<T> = Result<T, IdlError>;
+
+/// Parses a token stream (as produced by `lexer::tokenize`) into a document.
+///
+/// The parser never gives up on the first error: a broken field skips to the
+/// next line of its struct, and a broken item skips to the next item keyword.
+/// Every error encountered is returned alongside whatever could be parsed.
+pub(crate) fn parse(tokens: Vec<Token>) -> (Document, Vec<IdlError>) {
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+        errors: Vec::new(),
+    };
+    let document = parser.parse_document();
+
+    (document, parser.errors)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// How many generics and inline objects enclose the current type
+    depth: usize,
+    errors: Vec<IdlError>,
+}
+
+impl Parser {
+    fn parse_document(&mut self) -> Document {
+        let mut items: Vec<Item> = Vec::new();
+
+        loop {
+            self.skip_comments();
+            if self.peek().kind == TokenKind::Eof {
+                break;
+            }
+            match self.parse_item() {
+                Ok(item) => items.push(item),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.recover_item();
+                }
+            }
+        }
+
+        Document { items }
+    }
+
+    fn parse_item(&mut self) -> ParseResult<Item> {
+        let docs = self.parse_docs();
+        let attributes = self.parse_attributes()?;
+
+        let keyword = self.next();
+        let keyword = match &keyword.kind {
+            TokenKind::Ident(v) if ITEM_KEYWORDS.contains(&v.as_str()) => v.clone(),
+            other => {
+                return Err(IdlError::new(
+                    keyword.span,
+                    format!(
+                        "expected 'struct', 'enum', 'query' or 'mutation', found {}",
+                        other.describe()
+                    ),
+                ))
+            }
+        };
+        let name = self.parse_name()?;
+
+        let kind = match keyword.as_str() {
+            "struct" => ItemKind::Struct(self.parse_fields()?),
+            "enum" => ItemKind::Enum(self.parse_variants()?),
+            "query" => ItemKind::Route(RouteKind::Query, self.parse_fields()?),
+            _ => ItemKind::Route(RouteKind::Mutation, self.parse_fields()?),
+        };
+
+        Ok(Item {
+            docs,
+            attributes,
+            name,
+            kind,
+        })
+    }
+
+    /// Parses `{ field* }`. Errors in individual fields are recorded and
+    /// skipped.
+    fn parse_fields(&mut self) -> ParseResult<Vec<FieldDecl>> {
+        let open = self.expect(TokenKind::LBrace)?;
+        let mut fields: Vec<FieldDecl> = Vec::new();
+
+        loop {
+            self.skip_comments();
+            match self.peek().kind {
+                TokenKind::RBrace => {
+                    self.next();
+                    break;
+                }
+                TokenKind::Eof => {
+                    return Err(IdlError::new(open.span, "unclosed '{'"));
+                }
+                _ => {}
+            }
+            let start = self.pos;
+            match self.parse_field() {
+                Ok(field) => fields.push(field),
+                Err(e) => {
+                    self.recover_field(start, e.span);
+                    self.errors.push(e);
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+
+    fn parse_field(&mut self) -> ParseResult<FieldDecl> {
+        let mut docs = self.parse_docs();
+        let attributes = self.parse_attributes()?;
+        let name = self.parse_name()?;
+        self.expect(TokenKind::Colon)?;
+        let ty = self.parse_type()?;
+
+        // Separators are optional
+        if matches!(
+            self.peek_raw().kind,
+            TokenKind::Comma | TokenKind::Semicolon
+        ) {
+            self.next();
+        }
+
+        // A `//` comment on the same line describes the field
+        let last_line = self.previous().line;
+        let trailing = self.peek_raw();
+        if let TokenKind::LineComment(text) = &trailing.kind {
+            if trailing.line == last_line {
+                let text = text.clone();
+                self.pos += 1;
+                docs = Some(match docs {
+                    Some(d) => format!("{}\n{}", d, text),
+                    None => text,
+                });
+            }
+        }
+
+        Ok(FieldDecl {
+            docs,
+            attributes,
+            name,
+            ty,
+        })
+    }
+
+    fn parse_type(&mut self) -> ParseResult<TypeExpr> {
+        let start = self.peek().span;
+
+        let nullable = self.eat(TokenKind::Question);
+        let multiple = if self.peek().kind == TokenKind::LBracket {
+            self.next();
+            self.expect(TokenKind::RBracket)?;
+            true
+        } else {
+            false
+        };
+        if self.peek().kind == TokenKind::Question {
+            return Err(IdlError::new(
+                self.peek().span,
+                "the nullable operator '?' must come before '[]'",
+            ));
+        }
+
+        let kind = if self.peek().kind == TokenKind::LBrace {
+            let open = self.peek().span;
+            TypeKind::Object(self.nested(open, Self::parse_fields)?)
+        } else {
+            let name = self.parse_ident("a type")?;
+            let mut generics: Vec<TypeExpr> = Vec::new();
+            let open = self.peek().span;
+            if self.eat(TokenKind::LAngle) {
+                generics = self.nested(open, |parser| {
+                    let mut generics: Vec<TypeExpr> = Vec::new();
+                    loop {
+                        generics.push(parser.parse_type()?);
+                        if !parser.eat(TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    parser.expect(TokenKind::RAngle)?;
+                    Ok(generics)
+                })?;
+            }
+            TypeKind::Named { name, generics }
+        };
+
+        Ok(TypeExpr {
+            nullable,
+            multiple,
+            kind,
+            span: start.to(self.previous().span),
+        })
+    }
+
+    /// Parses what follows the `<` or `{` at `open`, one level deeper. Fails
+    /// at that token past `MAX_DEPTH`.
+    fn nested<T>(
+        &mut self,
+        open: Span,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        if self.depth >= MAX_DEPTH {
+            return Err(IdlError::new(
+                open,
+                format!("types can't be nested more than {} levels deep", MAX_DEPTH),
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_variants(&mut self) -> ParseResult<Vec<VariantDecl>> {
+        let open = self.expect(TokenKind::LBrace)?;
+        let mut variants: Vec<VariantDecl> = Vec::new();
+
+        loop {
+            self.skip_comments();
+            let token = self.peek().clone();
+            match token.kind {
+                TokenKind::RBrace => {
+                    self.next();
+                    break;
+                }
+                TokenKind::Eof => {
+                    return Err(IdlError::new(open.span, "unclosed '{'"));
+                }
+                TokenKind::DocComment(_) => {
+                    self.next();
+                    self.errors.push(IdlError::new(
+                        token.span,
+                        "doc comments are not supported on enum variants",
+                    ));
+                    continue;
+                }
+                _ => {}
+            }
+
+            let attributes = self.parse_attributes();
+            let variant = attributes.and_then(|attributes| {
+                let name = self.parse_name()?;
+                Ok(VariantDecl { attributes, name })
+            });
+            match variant {
+                Ok(variant) => {
+                    variants.push(variant);
+                    self.eat(TokenKind::Comma);
+                }
+                Err(e) => {
+                    self.errors.push(e);
+                    // Skip the offending token
+                    if self.peek().kind != TokenKind::RBrace {
+                        self.next();
+                    }
+                }
+            }
+        }
+
+        Ok(variants)
+    }
+
+    fn parse_attributes(&mut self) -> ParseResult<Vec<Attribute>> {
+        let mut attributes: Vec<Attribute> = Vec::new();
+
+        while self.peek().kind == TokenKind::At {
+            let start = self.next().span;
+            let name = self.parse_ident("an attribute name")?;
+            let mut args: Vec<AttributeArg> = Vec::new();
+
+            if self.eat(TokenKind::LParen) {
+                while self.peek().kind != TokenKind::RParen {
+                    let token = self.next();
+                    args.push(match token.kind {
+                        TokenKind::Ident(v) => AttributeArg::Ident(Ident {
+                            name: v,
+                            span: token.span,
+                        }),
+                        TokenKind::Str(v) => AttributeArg::Str(v, token.span),
+                        TokenKind::Int(v) => AttributeArg::Int(v, token.span),
+                        other => {
+                            return Err(IdlError::new(
+                                token.span,
+                                format!(
+                                    "expected an attribute argument, found {}",
+                                    other.describe()
+                                ),
+                            ))
+                        }
+                    });
+                    if !self.eat(TokenKind::Comma) {
+                        break;
+                    }
+                }
+                self.expect(TokenKind::RParen)?;
+            }
+
+            attributes.push(Attribute {
+                name,
+                args,
+                span: start.to(self.previous().span),
+            });
+            self.skip_comments();
+        }
+
+        Ok(attributes)
+    }
+
+    /// Collects consecutive `///` lines into a single description
+    fn parse_docs(&mut self) -> Option<String> {
+        let mut lines: Vec<String> = Vec::new();
+
+        loop {
+            self.skip_comments();
+            match &self.peek_raw().kind {
+                TokenKind::DocComment(text) => {
+                    lines.push(text.clone());
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
+    /// Item, field and variant names can be identifiers or string literals.
+    /// The latter allows names that are not valid identifiers.
+    fn parse_name(&mut self) -> ParseResult<Ident> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Ident(name) | TokenKind::Str(name) => Ok(Ident {
+                name,
+                span: token.span,
+            }),
+            other => Err(IdlError::new(
+                token.span,
+                format!("expected a name, found {}", other.describe()),
+            )),
+        }
+    }
+
+    fn parse_ident(&mut self, expected: &str) -> ParseResult<Ident> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Ident(name) => Ok(Ident {
+                name,
+                span: token.span,
+            }),
+            other => Err(IdlError::new(
+                token.span,
+                format!("expected {}, found {}", expected, other.describe()),
+            )),
+        }
+    }
+
+    /// Skips to the next token that could start an item
+    fn recover_item(&mut self) {
+        let mut depth = 0;
+        loop {
+            let token = self.peek_raw();
+            match &token.kind {
+                TokenKind::Eof => return,
+                TokenKind::Ident(v) if depth == 0 && ITEM_KEYWORDS.contains(&v.as_str()) => return,
+                TokenKind::DocComment(_) | TokenKind::At if depth == 0 => return,
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace => depth = (depth - 1).max(0),
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skips the rest of a field that starts at token `start` and has an
+    /// error at `error`. A field that ends early at the end of its line, like
+    /// `a: Map<string`, resumes at the next line. Otherwise this skips to the
+    /// first token after the line of the error that is not nested in braces,
+    /// or to the closing brace of the current block.
+    fn recover_field(&mut self, start: usize, error: Span) {
+        let culprit = (start..self.tokens.len()).find(|&i| self.tokens[i].span == error);
+        let Some(culprit) = culprit else {
+            return;
+        };
+        let error_line = self.tokens[culprit].line;
+        if culprit > start && self.tokens[culprit - 1].line < error_line {
+            self.pos = culprit;
+            return;
+        }
+
+        let mut depth = 0;
+        loop {
+            let token = self.peek_raw();
+            match &token.kind {
+                TokenKind::Eof => return,
+                TokenKind::RBrace if depth == 0 => return,
+                _ if depth == 0 && token.line > error_line => return,
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace => depth -= 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn skip_comments(&mut self) {
+        while let TokenKind::LineComment(_) = self.tokens[self.pos].kind {
+            self.pos += 1;
+        }
+    }
+
+    /// Next token, without skipping comments
+    fn peek_raw(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    /// Next token that is not a plain comment. Does not consume anything, so
+    /// a trailing comment is still visible through `peek_raw` afterwards.
+    fn peek(&self) -> &Token {
+        let mut pos = self.pos;
+        while let TokenKind::LineComment(_) = self.tokens[pos].kind {
+            pos += 1;
+        }
+        &self.tokens[pos]
+    }
+
+    /// Consumes the next token that is not a plain comment. Keeps returning
+    /// `Eof` at the end of the stream.
+    fn next(&mut self) -> Token {
+        self.skip_comments();
+        let token = self.tokens[self.pos].clone();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// The last consumed token
+    fn previous(&self) -> &Token {
+        &self.tokens[self.pos.saturating_sub(1)]
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.peek().kind == kind {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> ParseResult<Token> {
+        if self.peek().kind == kind {
+            Ok(self.next())
+        } else {
+            let found = self.peek();
+            Err(IdlError::new(
+                found.span,
+                format!(
+                    "expected {}, found {}",
+                    kind.describe(),
+                    found.kind.describe()
+                ),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        ast::{ItemKind, TypeKind},
+        lexer::tokenize,
+    };
+    use super::parse;
+
+    #[test]
+    fn parses_items() {
+        let (tokens, _) = tokenize(
+            "/// A message
+            struct Message {
+                /// Unique id
+                id: u64
+                tags: ?[]string, // Optional tags
+                meta: Map<string, u64>
+                nested: []{
+                    a: bool
+                } // Nested
+            }
+
+            enum Status { ACTIVE, DISABLED, }
+
+            @deprecated
+            query GetMessages {
+                request: Status
+                response: []Message
+            }",
+        );
+        let (doc, errors) = parse(tokens);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(doc.items.len(), 3);
+
+        let message = &doc.items[0];
+        assert_eq!(message.docs.as_deref(), Some("A message"));
+        match &message.kind {
+            ItemKind::Struct(fields) => {
+                assert_eq!(fields.len(), 4);
+                assert_eq!(fields[0].docs.as_deref(), Some("Unique id"));
+                assert_eq!(fields[1].docs.as_deref(), Some("Optional tags"));
+                assert!(fields[1].ty.nullable && fields[1].ty.multiple);
+                match &fields[2].ty.kind {
+                    TypeKind::Named { name, generics } => {
+                        assert_eq!(name.name, "Map");
+                        assert_eq!(generics.len(), 2);
+                    }
+                    _ => panic!("expected a named type"),
+                }
+                assert!(matches!(fields[3].ty.kind, TypeKind::Object(_)));
+                assert_eq!(fields[3].docs.as_deref(), Some("Nested"));
+            }
+            _ => panic!("expected a struct"),
+        }
+
+        assert_eq!(doc.items[2].attributes[0].name.name, "deprecated");
+    }
+
+    /// Errors in one field or item must not hide errors in the rest of the file
+    #[test]
+    fn recovers_from_errors() {
+        let (tokens, _) = tokenize(
+            "struct A {
+                a: [string
+                b: string
+                c u64
+            }
+            struct { }
+            enum B { X }",
+        );
+        let (doc, errors) = parse(tokens);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert_eq!(doc.items.len(), 2);
+        match &doc.items[0].kind {
+            ItemKind::Struct(fields) => {
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].name.name, "b");
+            }
+            _ => panic!("expected a struct"),
+        }
+    }
+
+    /// A field that breaks at the end of its line doesn't take the next field
+    /// with it
+    #[test]
+    fn recovers_at_the_end_of_a_line() {
+        let (tokens, _) = tokenize(
+            "struct A {
+                a: Map<string
+                b: string // Trailing
+                c: [
+                d: bool
+            }",
+        );
+        let (doc, errors) = parse(tokens);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        match &doc.items[0].kind {
+            ItemKind::Struct(fields) => {
+                let names = fields
+                    .iter()
+                    .map(|f| f.name.name.as_str())
+                    .collect::<Vec<_>>();
+                assert_eq!(names, vec!["b", "d"]);
+                assert_eq!(fields[0].docs.as_deref(), Some("Trailing"));
+            }
+            _ => panic!("expected a struct"),
+        }
+    }
+
+    /// Deeply nested types are an error at the first `<` or `{` past the
+    /// limit, not a stack overflow
+    #[test]
+    fn limits_nesting() {
+        for (open, close) in [("B<", ">"), ("{ a: ", "}")] {
+            let text = format!(
+                "struct A {{ a: {}string{} }}",
+                open.repeat(200_000),
+                close.repeat(200_000)
+            );
+            let (tokens, _) = tokenize(&text);
+            let (_, errors) = parse(tokens);
+            assert!(!errors.is_empty());
+            let offset = "struct A { a: ".len() + open.len() * super::MAX_DEPTH;
+            let offset = offset + open.find(['<', '{']).unwrap();
+            assert_eq!(errors[0].span.start, offset, "{}", open);
+            assert!(errors[0].message.contains("nested"), "{:?}", errors[0]);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::common::schema::{
+    fields::{FieldKind, ZetroField},
+    normalize_description,
+    routes::{RouteKind, ZetroRoute},
+    ZetroSchema,
+};
+
+const INDENT: &str = "    ";
+
+/// Prints a schema as `.zetro` source. Parsing the output yields the same
+/// schema again.
+pub(crate) fn print_schema(schema: &ZetroSchema) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+
+    for _struct in &schema.structs {
+        let mut block = String::new();
+        push_docs(&mut block, &_struct.description, "");
+        push_lint_allow(&mut block, &_struct.lint_allow, "");
+        block.push_str(&format!("struct {} {{\n", print_name(&_struct.name)));
+        push_fields(&mut block, &_struct.fields, 1);
+        block.push('}');
+        blocks.push(block);
+    }
+    for _enum in &schema.enums {
+        let mut block = String::new();
+        push_lint_allow(&mut block, &_enum.lint_allow, "");
+        block.push_str(&format!("enum {} {{\n", print_name(&_enum.name)));
+        for variant in &_enum.variants {
+            block.push_str(&format!("{}{},\n", INDENT, print_name(variant)));
+        }
+        block.push('}');
+        blocks.push(block);
+    }
+    for route in schema.queries.iter().chain(schema.mutations.iter()) {
+        blocks.push(print_route(route));
+    }
+
+    let mut printed = blocks.join("\n\n");
+    printed.push('\n');
+    printed
+}
+
+fn print_route(route: &ZetroRoute) -> String {
+    let mut block = String::new();
+    push_docs(&mut block, &route.description, "");
+    push_lint_allow(&mut block, &route.lint_allow, "");
+    block.push_str(&format!(
+        "{} {} {{\n",
+        match route.kind {
+            RouteKind::Query => "query",
+            RouteKind::Mutation => "mutation",
+        },
+        print_name(&route.name)
+    ));
+    push_fields(
+        &mut block,
+        &[route.request_body.clone(), route.response_body.clone()],
+        1,
+    );
+    block.push('}');
+    block
+}
+
+fn push_fields(out: &mut String, fields: &[ZetroField], depth: usize) {
+    let indent = INDENT.repeat(depth);
+
+    for field in fields {
+        let mut prefix = String::new();
+        if field.is_nullable {
+            prefix.push('?');
+        }
+        if field.is_multiple {
+            prefix.push_str("[]");
+        }
+
+        // Nested objects carry their description on the struct
+        let description = match &field.kind {
+            FieldKind::NestedObject(s) => Some(&s.description),
+            _ => field.description.as_ref(),
+        };
+        // Single-line descriptions trail the field, others go above it
+        let description = description.map(|d| normalize_description(d));
+        let trailing = match description {
+            Some(d) if !d.contains('\n') && !d.is_empty() => Some(d),
+            Some(d) => {
+                push_docs(out, &d, &indent);
+                None
+            }
+            None => None,
+        };
+        if let FieldKind::NestedObject(nested) = &field.kind {
+            push_lint_allow(out, &nested.lint_allow, &indent);
+        }
+
+        out.push_str(&format!(
+            "{}{}: {}",
+            indent,
+            print_name(&field.name),
+            prefix
+        ));
+        match &field.kind {
+            FieldKind::NestedObject(nested) => {
+                out.push_str("{\n");
+                push_fields(out, &nested.fields, depth + 1);
+                out.push_str(&format!("{}}}", indent));
+            }
+            kind => out.push_str(&print_type_name(kind)),
+        }
+        if let Some(d) = trailing {
+            out.push_str(&format!(" // {}", d));
+        }
+        out.push('\n');
+    }
+}
+
+fn print_type_name(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::StructValue(name) | FieldKind::EnumValue(name) => print_name(name),
+        FieldKind::NestedObject(s) => print_name(&s.name),
+        primitive => primitive.primitive_name().unwrap().to_owned(),
+    }
+}
+
+fn push_docs(out: &mut String, docs: &str, indent: &str) {
+    for line in normalize_description(docs).split('\n') {
+        if line.is_empty() {
+            out.push_str(&format!("{}///\n", indent));
+        } else {
+            out.push_str(&format!("{}/// {}\n", indent, line));
+        }
+    }
+}
+
+fn push_lint_allow(out: &mut String, rules: &[String], indent: &str) {
+    if rules.is_empty() {
+        return;
+    }
+    let rules = rules
+        .iter()
+        .map(|r| quote(r))
+        .collect::<Vec<_>>()
+        .join(", ");
+    out.push_str(&format!("{}@allow({})\n", indent, rules));
+}
+
+/// Names that are not valid identifiers are printed as string literals
+fn print_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_ident = match chars.next() {
+        Some(c) => {
+            (c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    };
+
+    if is_ident {
+        name.to_owned()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::parse_schema, print_schema};
+    use crate::common::schema::ZetroSchema;
+
+    /// Converting a JSON schema to `.zetro` and parsing it back must not lose
+    /// anything
+    #[test]
+    fn round_trips_json_schemas() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": {
+                    "description": "A message\nspanning two lines",
+                    "fields": {
+                        "id": "u64; Unique id",
+                        "odd-name": "?[]string",
+                        "parent": "?struct~Message",
+                        "receipts": {
+                            "description": "Read receipts",
+                            "multiple": true,
+                            "allow": ["field-name-case"],
+                            "fields": {
+                                "status": "enum~Status; Delivery status",
+                            }
+                        }
+                    }
+                }
+            },
+            "enums": { "Status": { "variants": ["SENT", "READ"], "allow": ["unused-type"] } },
+            "routes": {
+                "GetMessage": {
+                    "kind": "query",
+                    "allow": ["large-mutation-response", "unused-type"],
+                    "description": "Get a message",
+                    "request": "u64",
+                    "response": "?struct~Message; The message, if any",
+                }
+            }
+        }))
+        .unwrap();
+
+        let printed = print_schema(&schema);
+        let parsed = parse_schema(&printed).unwrap();
+        assert_eq!(format!("{:#?}", parsed), format!("{:#?}", schema));
+        assert_eq!(print_schema(&parsed), printed);
+    }
+}
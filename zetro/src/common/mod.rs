@@ -1 +1,5 @@
+pub(crate) mod fake;
+pub(crate) mod idl;
 pub(crate) mod schema;
+pub(crate) mod source;
+pub(crate) mod wire;
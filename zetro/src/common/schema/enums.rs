@@ -2,13 +2,48 @@ use super::{ErrorKind, Offender, SchemaError};
 
 /// Denotes an enum.
 #[derive(Debug, Clone)]
-pub(crate) struct ZetroEnum {
+pub struct ZetroEnum {
     pub name: String,
     pub variants: Vec<String>,
+    /// Lint rules suppressed for this enum
+    pub lint_allow: Vec<String>,
 }
 
 impl ZetroEnum {
+    /// Enums are either a list of variants, or an object with a `variants`
+    /// list and an `allow` list of suppressed lint rules.
     pub fn from_value(enum_name: String, value: &serde_json::Value) -> Result<Self, SchemaError> {
+        let mut lint_allow: Vec<String> = Vec::new();
+
+        let value = match value.as_object() {
+            Some(object) => {
+                for key in object.keys() {
+                    if key != "variants" && key != "allow" {
+                        return Err(SchemaError {
+                            kind: ErrorKind::UnrecognizedField(key.to_owned()),
+                            offender: Offender::Enum(enum_name),
+                        });
+                    }
+                }
+                if let Some(allow) = object.get("allow") {
+                    lint_allow = super::parse_lint_allow(
+                        allow,
+                        Offender::Field(enum_name.clone(), String::from("allow")),
+                    )?;
+                }
+                match object.get("variants") {
+                    Some(v) => v,
+                    None => {
+                        return Err(SchemaError {
+                            kind: ErrorKind::MissingField(String::from("variants")),
+                            offender: Offender::Enum(enum_name),
+                        })
+                    }
+                }
+            }
+            None => value,
+        };
+
         if let Some(array) = value.as_array() {
             let mut variants: Vec<String> = Vec::new();
 
@@ -29,12 +64,25 @@ impl ZetroEnum {
             Ok(Self {
                 name: enum_name,
                 variants,
+                lint_allow,
             })
         } else {
-            return Err(SchemaError {
+            Err(SchemaError {
                 kind: ErrorKind::BadFieldValue(enum_name.clone(), String::from("list of strings")),
                 offender: Offender::Enum(enum_name),
-            });
+            })
+        }
+    }
+
+    /// The inverse of `from_value`. The object form is only used when lint
+    /// rules are suppressed.
+    pub fn to_value(&self) -> serde_json::Value {
+        if self.lint_allow.is_empty() {
+            return self.variants.clone().into();
         }
+        serde_json::json!({
+            "variants": self.variants,
+            "allow": self.lint_allow,
+        })
     }
 }
@@ -1,11 +1,11 @@
 #[derive(Debug)]
-pub(crate) struct SchemaError {
+pub struct SchemaError {
     pub kind: ErrorKind,
     pub offender: Offender,
 }
 
 #[derive(Debug)]
-pub(crate) enum Offender {
+pub enum Offender {
     Field(String, String), // (struct/enum/route name, field name)
     Struct(String),        // (struct name)
     Enum(String),          // (enum name)
@@ -14,7 +14,7 @@ pub(crate) enum Offender {
 }
 
 #[derive(Debug)]
-pub(crate) enum ErrorKind {
+pub enum ErrorKind {
     InvalidReference(String),      // (name of invalid reference)
     UnrecognizedField(String),     // (field name)
     MissingField(String),          // (field name)
@@ -53,3 +53,5 @@ impl std::fmt::Display for SchemaError {
         )
     }
 }
+
+impl std::error::Error for SchemaError {}
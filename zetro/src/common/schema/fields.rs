@@ -4,7 +4,7 @@ use super::{
 };
 
 #[derive(Debug, Clone)]
-pub(crate) enum FieldKind {
+pub enum FieldKind {
     Int8,
     UInt8,
     Int16,
@@ -22,6 +22,46 @@ pub(crate) enum FieldKind {
     NestedObject(ZetroStruct), // A nested object
 }
 
+impl FieldKind {
+    /// Gets the primitive kind for a dtype name, eg. `u64` or `string`
+    pub fn from_primitive_name(name: &str) -> Option<Self> {
+        match name {
+            "string" => Some(Self::StringValue),
+            "bool" => Some(Self::Boolean),
+            "i8" => Some(Self::Int8),
+            "u8" => Some(Self::UInt8),
+            "i16" => Some(Self::Int16),
+            "u16" => Some(Self::UInt16),
+            "i32" => Some(Self::Int32),
+            "u32" => Some(Self::UInt32),
+            "i64" => Some(Self::Int64),
+            "u64" => Some(Self::UInt64),
+            "f32" => Some(Self::Float32),
+            "f64" => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    /// The inverse of `from_primitive_name`. `None` for non-primitives.
+    pub fn primitive_name(&self) -> Option<&'static str> {
+        match self {
+            Self::StringValue => Some("string"),
+            Self::Boolean => Some("bool"),
+            Self::Int8 => Some("i8"),
+            Self::UInt8 => Some("u8"),
+            Self::Int16 => Some("i16"),
+            Self::UInt16 => Some("u16"),
+            Self::Int32 => Some("i32"),
+            Self::UInt32 => Some("u32"),
+            Self::Int64 => Some("i64"),
+            Self::UInt64 => Some("u64"),
+            Self::Float32 => Some("f32"),
+            Self::Float64 => Some("f64"),
+            Self::StructValue(_) | Self::EnumValue(_) | Self::NestedObject(_) => None,
+        }
+    }
+}
+
 /// Denotes a single field in a schema. Fields will always be of string type.
 /// The syntax for a field is as follows:
 ///     <nullable><multiple><dtype>~<extra>; <description>
@@ -37,8 +77,10 @@ pub(crate) enum FieldKind {
 /// The description, if present must be put after a semicolon-space construct,
 /// like this:
 ///     ?string; A nullable string. Notice the semicolon and space
+/// Whitespace around the description is ignored, and an empty description is
+/// the same as none. `zetro fmt` rewrites fields in the canonical form.
 #[derive(Debug, Clone)]
-pub(crate) struct ZetroField {
+pub struct ZetroField {
     /// Optional description for the field
     pub description: Option<String>,
     /// Property name for field
@@ -67,9 +109,9 @@ impl ZetroField {
             let nested_struct = ZetroStruct::from_value(nested_struct_name, value)?;
 
             return Ok(Self {
-                is_nullable: nested_struct.is_nullable.clone(),
+                is_nullable: nested_struct.is_nullable,
                 is_recursive: false,
-                is_multiple: nested_struct.is_multiple.clone(),
+                is_multiple: nested_struct.is_multiple,
                 kind: FieldKind::NestedObject(nested_struct),
                 description: None,
                 name: field_name,
@@ -78,7 +120,15 @@ impl ZetroField {
 
         let mut value = match value.as_str() {
             Some(v) => v,
-            None => todo!(),
+            None => {
+                return Err(SchemaError {
+                    kind: ErrorKind::BadFieldValue(
+                        field_name.clone(),
+                        String::from("string or object"),
+                    ),
+                    offender: Offender::Field(struct_name, field_name),
+                });
+            }
         };
 
         let kind: FieldKind;
@@ -87,75 +137,72 @@ impl ZetroField {
         let mut is_recursive = false;
         let description: Option<String>;
 
-        if value.starts_with("?") {
+        if let Some(rest) = value.strip_prefix('?') {
             is_nullable = true;
-            value = &value[1..];
+            value = rest;
         } else {
             is_nullable = false;
         }
-        if value.starts_with("[]") {
+        if let Some(rest) = value.strip_prefix("[]") {
             is_multiple = true;
-            value = &value[2..];
+            value = rest;
         } else {
             is_multiple = false;
         }
 
-        // A value can be followed by a '; ' to denote description
-        let _parts = value.split("; ").collect::<Vec<_>>();
-        let _dtype_parts = _parts[0];
-        if let Some(d) = _parts.get(1) {
-            description = Some(d.to_string());
-        } else {
-            description = None;
+        // A value can be followed by a '; ' to denote description. Only the
+        // first '; ' counts, so descriptions may contain it as well.
+        match value.split_once("; ") {
+            Some((expression, d)) => {
+                value = expression;
+                let d = d.trim();
+                description = if d.is_empty() {
+                    None
+                } else {
+                    Some(d.to_string())
+                };
+            }
+            None => description = None,
         }
 
         // A value have a '~' to add extra information
-        let _dtype_parts = _dtype_parts.split("~").collect::<Vec<_>>();
-        let dtype = *_dtype_parts.get(0).unwrap();
+        let _dtype_parts = value.split('~').collect::<Vec<_>>();
+        let dtype = *_dtype_parts.first().unwrap();
         let extra = _dtype_parts.get(1);
 
-        if dtype == "string" {
-            kind = FieldKind::StringValue;
-        } else if dtype == "i8" {
-            kind = FieldKind::Int8;
-        } else if dtype == "u8" {
-            kind = FieldKind::UInt8;
-        } else if dtype == "i16" {
-            kind = FieldKind::Int16;
-        } else if dtype == "u16" {
-            kind = FieldKind::UInt16;
-        } else if dtype == "i32" {
-            kind = FieldKind::Int32;
-        } else if dtype == "u32" {
-            kind = FieldKind::UInt32;
-        } else if dtype == "i64" {
-            kind = FieldKind::Int64;
-        } else if dtype == "u64" {
-            kind = FieldKind::UInt64;
-        } else if dtype == "f32" {
-            kind = FieldKind::Float32;
-        } else if dtype == "f64" {
-            kind = FieldKind::Float64;
-        } else if dtype == "bool" {
-            kind = FieldKind::Boolean;
-        } else if dtype == "enum" {
-            let enum_name = extra.expect("enum declarations must contain enum name");
-            kind = FieldKind::EnumValue(enum_name.to_string());
-        } else if dtype == "struct" {
-            let _struct_name = extra.expect("struct declarations must contain struct name");
-            is_recursive = _struct_name.to_string() == struct_name;
-            if is_recursive && !is_nullable && !is_multiple {
-                // Disallow recursive types to be both non-null and non-multiple
-                // to avoid infinite recursion
-                return Err(SchemaError {
-                    kind: ErrorKind::BadFieldValue(
-                        field_name.clone(),
-                        String::from("nullable and/or multiple to avoid an infinite loop."),
-                    ),
-                    offender: Offender::Field(struct_name, field_name),
-                });
+        if let Some(primitive) = FieldKind::from_primitive_name(dtype) {
+            kind = primitive;
+        } else if dtype == "enum" || dtype == "struct" {
+            // Enums and structs are referenced by name, eg. `enum~Name`
+            let name = match extra {
+                Some(name) if !name.is_empty() => name.to_string(),
+                _ => {
+                    return Err(SchemaError {
+                        kind: ErrorKind::BadFieldValue(
+                            field_name.clone(),
+                            format!("{}~<name>", dtype),
+                        ),
+                        offender: Offender::Field(struct_name, field_name),
+                    });
+                }
+            };
+            if dtype == "enum" {
+                kind = FieldKind::EnumValue(name);
+            } else {
+                is_recursive = name == struct_name;
+                if is_recursive && !is_nullable && !is_multiple {
+                    // Disallow recursive types to be both non-null and non-multiple
+                    // to avoid infinite recursion
+                    return Err(SchemaError {
+                        kind: ErrorKind::BadFieldValue(
+                            field_name.clone(),
+                            String::from("nullable and/or multiple to avoid an infinite loop."),
+                        ),
+                        offender: Offender::Field(struct_name, field_name),
+                    });
+                }
+                kind = FieldKind::StructValue(name);
             }
-            kind = FieldKind::StructValue(_struct_name.to_string());
         } else {
             return Err(SchemaError {
                 kind: ErrorKind::BadFieldValue(
@@ -175,6 +222,32 @@ impl ZetroField {
             is_recursive,
         })
     }
+
+    /// The inverse of `from_value`. Fields are written in their canonical form,
+    /// eg. `?[]struct~Name; Description`.
+    pub fn to_value(&self) -> serde_json::Value {
+        let dtype = match &self.kind {
+            FieldKind::StructValue(name) => format!("struct~{}", name),
+            FieldKind::EnumValue(name) => format!("enum~{}", name),
+            FieldKind::NestedObject(nested) => return nested.to_value(),
+            primitive => primitive.primitive_name().unwrap().to_owned(),
+        };
+
+        let mut expression = String::new();
+        if self.is_nullable {
+            expression.push('?');
+        }
+        if self.is_multiple {
+            expression.push_str("[]");
+        }
+        expression.push_str(&dtype);
+        if let Some(d) = &self.description {
+            expression.push_str("; ");
+            expression.push_str(&super::normalize_description(d));
+        }
+
+        serde_json::Value::String(expression)
+    }
 }
 
 #[cfg(test)]
@@ -183,7 +256,7 @@ mod tests {
 
     use serde_json::json;
 
-    use super::super::{FieldKind, ZetroField};
+    use super::super::{ErrorKind, FieldKind, ZetroField};
 
     /// Ensure field parsing works correctly
     #[test]
@@ -205,8 +278,8 @@ mod tests {
                 panic!("expected type to be string");
             }
         }
-        assert_eq!(string_non_null.is_nullable, false);
-        assert_eq!(string_non_null.is_multiple, false);
+        assert!(!string_non_null.is_nullable);
+        assert!(!string_non_null.is_multiple);
         assert_eq!(
             string_non_null.description,
             Some(String::from("a non-null string"))
@@ -228,8 +301,8 @@ mod tests {
                 panic!("expected type to be enum");
             }
         }
-        assert_eq!(enum_list_nullable.is_nullable, true);
-        assert_eq!(enum_list_nullable.is_multiple, true);
+        assert!(enum_list_nullable.is_nullable);
+        assert!(enum_list_nullable.is_multiple);
         assert_eq!(
             enum_list_nullable.description,
             Some(String::from("a nullable enum"))
@@ -257,16 +330,98 @@ mod tests {
                     }
                 }
                 assert_eq!(zetro_struct.fields[0].description, None);
-                assert_eq!(zetro_struct.fields[0].is_multiple, false);
-                assert_eq!(zetro_struct.fields[0].is_nullable, false);
-                assert_eq!(zetro_struct.is_nullable, false);
+                assert!(!zetro_struct.fields[0].is_multiple);
+                assert!(!zetro_struct.fields[0].is_nullable);
+                assert!(!zetro_struct.is_nullable);
                 assert_eq!(zetro_struct.description, String::from("nested struct"));
             }
             _ => {
                 panic!("expected type to be enum");
             }
         }
-        assert_eq!(nested_struct.is_nullable, false);
-        assert_eq!(nested_struct.is_multiple, false);
+        assert!(!nested_struct.is_nullable);
+        assert!(!nested_struct.is_multiple);
+    }
+
+    /// Whitespace around descriptions is ignored and written back
+    /// canonically
+    #[test]
+    fn normalizes_expressions() {
+        let field = ZetroField::from_value(
+            String::from("ExampleStruct"),
+            String::from("exampleField"),
+            &json!("?[]struct~Other;   A list;  of things "),
+        )
+        .unwrap();
+
+        match &field.kind {
+            FieldKind::StructValue(name) => assert_eq!(name, "Other"),
+            _ => panic!("expected type to be struct"),
+        }
+        assert!(field.is_nullable);
+        assert!(field.is_multiple);
+        assert_eq!(
+            field.to_value(),
+            json!("?[]struct~Other; A list;  of things")
+        );
+
+        // Empty descriptions are dropped
+        let field = ZetroField::from_value(
+            String::from("ExampleStruct"),
+            String::from("exampleField"),
+            &json!("u8; "),
+        )
+        .unwrap();
+        assert_eq!(field.description, None);
+        assert_eq!(field.to_value(), json!("u8"));
+    }
+
+    /// Only the first '; ' separates the dtype from the description
+    #[test]
+    fn description_with_separator() {
+        let field = ZetroField::from_value(
+            String::from("ExampleStruct"),
+            String::from("exampleField"),
+            &json!("u64; first part; second part"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            field.description,
+            Some(String::from("first part; second part"))
+        );
+    }
+
+    /// Values found by the `schema` fuzz target are errors, not panics
+    #[test]
+    fn rejects_malformed_fields() {
+        for value in [
+            json!(true),
+            json!(1),
+            json!(null),
+            json!(["u8"]),
+            json!("enum"),
+            json!("?[]struct"),
+            json!("struct~; no name"),
+            // Whitespace is only allowed in the description
+            json!("u64;A number"),
+            json!("u64 ; A number"),
+            json!("? u8"),
+            json!("?[] u8"),
+            json!("struct ~Other"),
+        ] {
+            let error = ZetroField::from_value(
+                String::from("ExampleStruct"),
+                String::from("exampleField"),
+                &value,
+            )
+            .unwrap_err();
+            assert!(
+                matches!(error.kind, ErrorKind::BadFieldValue(..)),
+                "{}: {:?}",
+                value,
+                error
+            );
+        }
     }
 }
@@ -0,0 +1,167 @@
+//! The JSON form of a schema that external plugins receive. Unlike the schema
+//! file format, types are structured values instead of field expressions, and
+//! every position and wire name is spelled out. `docs/plugin-protocol.md`
+//! describes the format; bump `IR_VERSION` on any incompatible change.
+
+use serde_json::{json, Value};
+
+use super::{
+    fields::{FieldKind, ZetroField},
+    routes::{RouteKind, ZetroRoute},
+    structs::ZetroStruct,
+    ZetroSchema,
+};
+
+/// Version of the IR format
+pub(crate) const IR_VERSION: u32 = 1;
+
+/// Spells the type of a field in the language of a target
+type TypeName<'a> = Option<&'a dyn Fn(&ZetroField) -> String>;
+
+impl ZetroSchema {
+    pub fn to_ir(&self) -> Value {
+        self.ir(None)
+    }
+
+    /// The IR, with the type of every field in the target's language as
+    /// `languageType`
+    pub(crate) fn to_target_ir(&self, type_name: &dyn Fn(&ZetroField) -> String) -> Value {
+        self.ir(Some(type_name))
+    }
+
+    fn ir(&self, type_name: TypeName) -> Value {
+        json!({
+            "structs": self.structs.iter().map(|s| struct_ir(s, type_name)).collect::<Vec<_>>(),
+            "enums": self.enums.iter().map(|e| json!({
+                "name": e.name,
+                "variants": e.variants.iter().enumerate().map(|(i, v)| json!({
+                    "name": v,
+                    "value": i,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+            "queries": self.queries.iter().map(|r| route_ir(r, type_name)).collect::<Vec<_>>(),
+            "mutations": self.mutations.iter().map(|r| route_ir(r, type_name)).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn struct_ir(_struct: &ZetroStruct, type_name: TypeName) -> Value {
+    json!({
+        "name": _struct.name,
+        "description": _struct.description,
+        "fields": _struct.fields.iter().enumerate().map(|(i, f)| {
+            let mut field = field_ir(f, type_name);
+            field["position"] = json!(i);
+            field
+        }).collect::<Vec<_>>(),
+    })
+}
+
+fn field_ir(field: &ZetroField, type_name: TypeName) -> Value {
+    let kind = match &field.kind {
+        FieldKind::StructValue(name) => json!({ "kind": "struct", "name": name }),
+        FieldKind::EnumValue(name) => json!({ "kind": "enum", "name": name }),
+        FieldKind::NestedObject(nested) => {
+            json!({ "kind": "object", "struct": struct_ir(nested, type_name) })
+        }
+        primitive => json!({ "kind": "primitive", "name": primitive.primitive_name() }),
+    };
+
+    let mut ir = json!({
+        "name": field.name,
+        "description": field.description,
+        "type": kind,
+        "nullable": field.is_nullable,
+        "list": field.is_multiple,
+        "recursive": field.is_recursive,
+    });
+    if let Some(type_name) = type_name {
+        ir["languageType"] = json!(type_name(field));
+    }
+    ir
+}
+
+fn route_ir(route: &ZetroRoute, type_name: TypeName) -> Value {
+    json!({
+        "name": route.name,
+        "description": route.description,
+        "kind": match route.kind {
+            RouteKind::Query => "query",
+            RouteKind::Mutation => "mutation",
+        },
+        "methodCode": route.kind.to_method_code(),
+        "wireName": route.encrypt_route_name(),
+        "request": field_ir(&route.request_body, type_name),
+        "response": field_ir(&route.response_body, type_name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::common::schema::ZetroSchema;
+
+    #[test]
+    fn describes_types_and_positions() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": {
+                    "description": "A message",
+                    "fields": {
+                        "text": "?string; Body",
+                        "meta": { "description": "Metadata", "fields": { "tags": "[]enum~Tag" } }
+                    }
+                }
+            },
+            "enums": { "Tag": ["A", "B"] },
+            "routes": {
+                "Send": {
+                    "kind": "mutation",
+                    "description": "Send",
+                    "request": "struct~Message",
+                    "response": "u64"
+                }
+            }
+        }))
+        .unwrap();
+        let ir = schema.to_ir();
+
+        assert_eq!(
+            ir["structs"][0]["fields"][1],
+            json!({
+                "name": "text",
+                "description": "Body",
+                "type": { "kind": "primitive", "name": "string" },
+                "nullable": true,
+                "list": false,
+                "recursive": false,
+                "position": 1,
+            })
+        );
+        let meta = &ir["structs"][0]["fields"][0]["type"];
+        assert_eq!(meta["kind"], "object");
+        assert_eq!(
+            meta["struct"]["fields"][0]["type"],
+            json!({ "kind": "enum", "name": "Tag" })
+        );
+        assert_eq!(
+            ir["enums"][0]["variants"][1],
+            json!({ "name": "B", "value": 1 })
+        );
+
+        let route = &ir["mutations"][0];
+        assert_eq!(route["methodCode"], 2);
+        assert_eq!(route["request"]["type"]["name"], "Message");
+        assert_eq!(route["wireName"], schema.mutations[0].encrypt_route_name());
+        assert!(ir["queries"].as_array().unwrap().is_empty());
+        assert!(route["request"].get("languageType").is_none());
+
+        let ir = schema.to_target_ir(&|field| format!("{}!", field.name));
+        assert_eq!(ir["mutations"][0]["response"]["languageType"], "response!");
+        assert_eq!(
+            ir["structs"][0]["fields"][0]["type"]["struct"]["fields"][0]["languageType"],
+            "tags!"
+        );
+    }
+}
@@ -9,18 +9,27 @@ use self::{
 pub(crate) mod enums;
 pub(crate) mod errors;
 pub(crate) mod fields;
+pub(crate) mod ir;
 pub(crate) mod routes;
 pub(crate) mod structs;
 
 /// Represents the format of a schema JSON file.
 #[derive(Debug, Clone)]
-pub(crate) struct ZetroSchema {
+pub struct ZetroSchema {
     pub structs: Vec<ZetroStruct>,
     pub enums: Vec<ZetroEnum>,
     pub queries: Vec<ZetroRoute>,
     pub mutations: Vec<ZetroRoute>,
 }
 
+/// Names of the structs and enums that are used, directly or indirectly, by
+/// at least one route
+#[derive(Debug)]
+pub struct ReachableTypes {
+    pub structs: std::collections::HashSet<String>,
+    pub enums: std::collections::HashSet<String>,
+}
+
 /// Stores all the structs in the current schema. Used to check for invalid
 /// structs.
 type ReferenceManifest<'a> = std::collections::HashMap<&'a String, bool>;
@@ -149,6 +158,79 @@ impl ZetroSchema {
         Ok(schema)
     }
 
+    /// The inverse of `from_value`. Since `serde_json` maps are ordered, the
+    /// value is canonical: every key is sorted and every field is written in
+    /// its canonical form.
+    pub fn to_value(&self) -> serde_json::Value {
+        use serde_json::{Map, Value};
+
+        let structs = self
+            .structs
+            .iter()
+            .map(|s| (s.name.clone(), s.to_value()))
+            .collect::<Map<_, _>>();
+        let enums = self
+            .enums
+            .iter()
+            .map(|e| (e.name.clone(), e.to_value()))
+            .collect::<Map<_, _>>();
+        let routes = self
+            .queries
+            .iter()
+            .chain(self.mutations.iter())
+            .map(|r| (r.name.clone(), r.to_value()))
+            .collect::<Map<_, _>>();
+
+        let mut value = Map::new();
+        value.insert(String::from("structs"), Value::Object(structs));
+        value.insert(String::from("enums"), Value::Object(enums));
+        value.insert(String::from("routes"), Value::Object(routes));
+        Value::Object(value)
+    }
+
+    /// Finds every struct and enum that can be reached from a route. Nested
+    /// objects are part of their parent, so they are never listed.
+    pub fn reachable_types(&self) -> ReachableTypes {
+        self.reachable_from(
+            self.queries
+                .iter()
+                .chain(self.mutations.iter())
+                .flat_map(|r| [&r.request_body, &r.response_body]),
+        )
+    }
+
+    /// Finds every struct and enum that can be reached from `roots`
+    pub fn reachable_from<'a>(
+        &self,
+        roots: impl IntoIterator<Item = &'a ZetroField>,
+    ) -> ReachableTypes {
+        let mut reachable = ReachableTypes {
+            structs: std::collections::HashSet::new(),
+            enums: std::collections::HashSet::new(),
+        };
+        let mut pending: Vec<&ZetroField> = roots.into_iter().collect();
+
+        while let Some(field) = pending.pop() {
+            match &field.kind {
+                FieldKind::StructValue(name) => {
+                    if !reachable.structs.insert(name.clone()) {
+                        continue;
+                    }
+                    if let Some(s) = self.structs.iter().find(|s| &s.name == name) {
+                        pending.extend(s.fields.iter());
+                    }
+                }
+                FieldKind::EnumValue(name) => {
+                    reachable.enums.insert(name.clone());
+                }
+                FieldKind::NestedObject(nested) => pending.extend(nested.fields.iter()),
+                _ => {}
+            }
+        }
+
+        reachable
+    }
+
     /// Checks schema for invalid references
     fn check_schema(&self) -> Result<(), SchemaError> {
         let mut struct_manifest: ReferenceManifest = std::collections::HashMap::new();
@@ -180,9 +262,10 @@ impl ZetroSchema {
         enum_manifest: &ReferenceManifest,
         _struct: &ZetroStruct,
     ) -> Result<(), SchemaError> {
-        Ok(for field in &_struct.fields {
+        for field in &_struct.fields {
             Self::check_field(struct_manifest, enum_manifest, field)?;
-        })
+        }
+        Ok(())
     }
 
     fn check_field(
@@ -190,37 +273,116 @@ impl ZetroSchema {
         enum_manifest: &ReferenceManifest,
         field: &ZetroField,
     ) -> Result<(), SchemaError> {
-        Ok(match &field.kind {
-            FieldKind::StructValue(struct_name) => {
-                if struct_manifest.get(struct_name).is_none() {
-                    return Err(SchemaError {
-                        kind: ErrorKind::InvalidReference(struct_name.to_owned()),
-                        offender: Offender::Field(struct_name.to_owned(), field.name.to_owned()),
-                    });
-                }
+        match &field.kind {
+            FieldKind::StructValue(struct_name) if struct_manifest.get(struct_name).is_none() => {
+                return Err(SchemaError {
+                    kind: ErrorKind::InvalidReference(struct_name.to_owned()),
+                    offender: Offender::Field(struct_name.to_owned(), field.name.to_owned()),
+                });
             }
-            FieldKind::EnumValue(enum_name) => {
-                if enum_manifest.get(enum_name).is_none() {
-                    return Err(SchemaError {
-                        kind: ErrorKind::InvalidReference(enum_name.to_owned()),
-                        offender: Offender::Field(enum_name.to_owned(), field.name.to_owned()),
-                    });
-                }
+            FieldKind::EnumValue(enum_name) if enum_manifest.get(enum_name).is_none() => {
+                return Err(SchemaError {
+                    kind: ErrorKind::InvalidReference(enum_name.to_owned()),
+                    offender: Offender::Field(enum_name.to_owned(), field.name.to_owned()),
+                });
             }
             FieldKind::NestedObject(obj) => {
                 Self::check_struct(struct_manifest, enum_manifest, obj)?
             }
             _ => {}
-        })
+        }
+        Ok(())
     }
 }
 
+/// Parses the `allow` list of a struct, enum or route: the lint rules that are
+/// suppressed for it. Rule names are checked by the linter, not here.
+pub(crate) fn parse_lint_allow(
+    value: &serde_json::Value,
+    offender: Offender,
+) -> Result<Vec<String>, SchemaError> {
+    let error = |offender| SchemaError {
+        kind: ErrorKind::BadFieldValue(String::from("allow"), String::from("a list of strings")),
+        offender,
+    };
+
+    let array = match value.as_array() {
+        Some(v) => v,
+        None => return Err(error(offender)),
+    };
+    let mut rules: Vec<String> = Vec::new();
+    for rule in array {
+        match rule.as_str() {
+            Some(v) => rules.push(v.to_owned()),
+            None => return Err(error(offender)),
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Trims a description, as well as trailing whitespace on each of its lines
+pub(crate) fn normalize_description(description: &str) -> String {
+    description
+        .trim()
+        .split('\n')
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
 
     use super::{ErrorKind, ZetroSchema};
 
+    /// Types are reachable through structs, nested objects and recursion, but
+    /// not from other unreachable types
+    #[test]
+    fn reachable_types() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Request": {
+                    "description": "",
+                    "fields": {
+                        "nested": {
+                            "description": "",
+                            "fields": { "user": "?struct~User" }
+                        }
+                    }
+                },
+                "User": {
+                    "description": "",
+                    "fields": { "friends": "[]struct~User", "status": "enum~Status" }
+                },
+                "Unused": {
+                    "description": "",
+                    "fields": { "kind": "enum~Kind" }
+                }
+            },
+            "enums": { "Status": ["ON"], "Kind": ["A"] },
+            "routes": {
+                "GetUser": {
+                    "kind": "query",
+                    "description": "",
+                    "request": "struct~Request",
+                    "response": "u8"
+                }
+            }
+        }))
+        .unwrap();
+
+        let reachable = schema.reachable_types();
+        let mut structs = reachable.structs.into_iter().collect::<Vec<_>>();
+        structs.sort();
+        assert_eq!(structs, vec!["Request", "User"]);
+        assert_eq!(
+            reachable.enums.into_iter().collect::<Vec<_>>(),
+            vec!["Status"]
+        );
+    }
+
     /// Ensure invalid references are declined
     #[test]
     fn invalid_references() {
@@ -237,8 +399,7 @@ mod tests {
             "enums": json!({}),
             "routes": json!({}),
         }))
-        .err()
-        .expect("expected schema error(struct)");
+        .expect_err("expected schema error(struct)");
 
         match &err.kind {
             &ErrorKind::InvalidReference(_) => {}
@@ -261,8 +422,7 @@ mod tests {
             "enums": json!({}),
             "routes": json!({}),
         }))
-        .err()
-        .expect("expected schema error(enum)");
+        .expect_err("expected schema error(enum)");
 
         match &err.kind {
             &ErrorKind::InvalidReference(_) => {}
@@ -290,8 +450,7 @@ mod tests {
             "enums": json!({}),
             "routes": json!({}),
         }))
-        .err()
-        .expect("expected schema error(nested struct)");
+        .expect_err("expected schema error(nested struct)");
 
         match &err.kind {
             &ErrorKind::InvalidReference(_) => {}
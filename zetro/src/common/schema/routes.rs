@@ -4,12 +4,14 @@ use super::{FieldKind, Offender, SchemaError, ZetroField};
 
 /// Represents a single API route
 #[derive(Debug, Clone)]
-pub(crate) struct ZetroRoute {
+pub struct ZetroRoute {
     pub kind: RouteKind,
     pub name: String,
     pub description: String,
     pub request_body: ZetroField,
     pub response_body: ZetroField,
+    /// Lint rules suppressed for this route
+    pub lint_allow: Vec<String>,
 }
 
 impl ZetroRoute {
@@ -88,41 +90,68 @@ impl ZetroRoute {
                     });
                 }
 
+                let lint_allow = match v.get("allow") {
+                    Some(allow) => super::parse_lint_allow(
+                        allow,
+                        Offender::Field(route_name.clone(), String::from("allow")),
+                    )?,
+                    None => Vec::new(),
+                };
+
                 Ok(Self {
                     name: route_name,
                     description: description.to_string(),
                     kind,
                     request_body,
                     response_body,
+                    lint_allow,
                 })
             }
-            None => {
-                return Err(SchemaError {
-                    kind: ErrorKind::BadFieldValue(route_name.clone(), String::from("an object")),
-                    offender: Offender::Route(route_name),
-                });
-            }
+            None => Err(SchemaError {
+                kind: ErrorKind::BadFieldValue(route_name.clone(), String::from("an object")),
+                offender: Offender::Route(route_name),
+            }),
+        }
+    }
+
+    /// The inverse of `from_value`
+    pub fn to_value(&self) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "kind": match self.kind {
+                RouteKind::Query => "query",
+                RouteKind::Mutation => "mutation",
+            },
+            "description": super::normalize_description(&self.description),
+            "request": self.request_body.to_value(),
+            "response": self.response_body.to_value(),
+        });
+        if !self.lint_allow.is_empty() {
+            value["allow"] = self.lint_allow.clone().into();
         }
+        value
     }
 
     /// Returns the encrypted and base64-encoded version of a route.
     /// We encrypt the route name to make reverse engineering more difficult.
-    pub(crate) fn encrypt_route_name(&self) -> String {
+    pub fn encrypt_route_name(&self) -> String {
+        Self::encrypt_name(&self.name)
+    }
+
+    /// Encrypts the name of a route, see `encrypt_route_name`
+    pub fn encrypt_name(name: &str) -> String {
         let mut route_encrypted =
             crypto::hmac::Hmac::new(crypto::sha1::Sha1::new(), "zetro".as_bytes());
-        crypto::mac::Mac::input(&mut route_encrypted, self.name.as_bytes());
+        crypto::mac::Mac::input(&mut route_encrypted, name.as_bytes());
 
-        let route_encrypted = base64::encode_config(
+        base64::encode_config(
             crypto::mac::Mac::result(&mut route_encrypted).code(),
             base64::URL_SAFE_NO_PAD,
-        );
-
-        route_encrypted
+        )
     }
 }
 
 #[derive(Debug, Clone)]
-pub(crate) enum RouteKind {
+pub enum RouteKind {
     /// A query means no content is changed in the api call.
     /// eg. fetching a list of videos
     Query,
@@ -5,7 +5,7 @@ use super::{Offender, SchemaError, ZetroField};
 /// Represents a collection of items. Akin to an object in javascript
 /// or a class in python.
 #[derive(Debug, Clone)]
-pub(crate) struct ZetroStruct {
+pub struct ZetroStruct {
     /// Name of the struct
     pub name: String,
     /// Mandatory description for this struct
@@ -17,6 +17,8 @@ pub(crate) struct ZetroStruct {
     /// A list of fields in this struct. Always ordered alphabetically from
     /// uppercase A to lowercase z
     pub fields: Vec<ZetroField>,
+    /// Lint rules suppressed for this struct and its fields
+    pub lint_allow: Vec<String>,
 }
 
 impl ZetroStruct {
@@ -36,6 +38,7 @@ impl ZetroStruct {
         let mut multiple: bool = false;
         let mut nullable: bool = false;
         let mut schema_fields: Option<&serde_json::Map<String, serde_json::Value>> = None;
+        let mut lint_allow: Vec<String> = Vec::new();
 
         for (key, val) in value {
             match key.as_str() {
@@ -87,6 +90,12 @@ impl ZetroStruct {
                         });
                     }
                 },
+                "allow" => {
+                    lint_allow = super::parse_lint_allow(
+                        val,
+                        Offender::Field(struct_name.clone(), String::from("allow")),
+                    )?
+                }
                 _ => {
                     return Err(SchemaError {
                         kind: ErrorKind::UnrecognizedField(key.to_owned()),
@@ -132,8 +141,38 @@ impl ZetroStruct {
             is_nullable: nullable,
             description,
             fields,
+            lint_allow,
         })
     }
+
+    /// The inverse of `from_value`. `nullable` and `multiple` are only written
+    /// when set.
+    pub fn to_value(&self) -> serde_json::Value {
+        let mut value = serde_json::Map::new();
+        value.insert(
+            String::from("description"),
+            super::normalize_description(&self.description).into(),
+        );
+        value.insert(
+            String::from("fields"),
+            self.fields
+                .iter()
+                .map(|f| (f.name.clone(), f.to_value()))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        );
+        if self.is_nullable {
+            value.insert(String::from("nullable"), true.into());
+        }
+        if self.is_multiple {
+            value.insert(String::from("multiple"), true.into());
+        }
+        if !self.lint_allow.is_empty() {
+            value.insert(String::from("allow"), self.lint_allow.clone().into());
+        }
+
+        value.into()
+    }
 }
 
 pub(crate) fn generate_nested_struct_name(struct_name: &str, field_name: &str) -> String {
@@ -199,10 +238,7 @@ mod tests {
                 "example": "string; example field"
             }
         });
-        assert_eq!(
-            ZetroStruct::from_value("TestStruct".to_string(), &json).is_err(),
-            true
-        );
+        assert!(ZetroStruct::from_value("TestStruct".to_string(), &json).is_err());
     }
 
     /// Description is a mandatory field in structs. Reject nested structs with
@@ -217,9 +253,6 @@ mod tests {
                 }
             }
         });
-        assert_eq!(
-            ZetroStruct::from_value("TestStruct".to_string(), &json).is_err(),
-            true
-        );
+        assert!(ZetroStruct::from_value("TestStruct".to_string(), &json).is_err());
     }
 }
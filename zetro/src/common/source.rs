@@ -0,0 +1,440 @@
+use super::{
+    idl,
+    schema::{errors::SchemaError, ZetroSchema},
+};
+
+/// Formats a schema file can be written in. All of them are decoded into the
+/// same `serde_json::Value` tree, which is then handed to
+/// `ZetroSchema::from_value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaFormat {
+    /// Plain JSON. The default for unknown extensions.
+    Json,
+    /// JSON5 / JSONC: JSON with comments, trailing commas, unquoted keys, etc.
+    Json5,
+    Yaml,
+    Toml,
+    /// The `.zetro` IDL. Unlike the other formats, it is lowered directly into
+    /// a `ZetroSchema`.
+    Zetro,
+}
+
+impl SchemaFormat {
+    /// Get format from file extension
+    pub fn from_ext(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "json5" => Some(Self::Json5),
+            "jsonc" => Some(Self::Json5),
+            "yaml" => Some(Self::Yaml),
+            "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "zetro" => Some(Self::Zetro),
+            _ => None,
+        }
+    }
+
+    /// Get format from a file path. Files with unknown (or no) extensions are
+    /// treated as JSON.
+    pub fn from_path(path: &str) -> Self {
+        std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_ext)
+            .unwrap_or(Self::Json)
+    }
+}
+
+/// An error raised while reading or decoding a schema file, before it is
+/// interpreted as a schema.
+#[derive(Debug)]
+pub struct SourceError {
+    /// Path of the offending file
+    pub file: String,
+    /// One-based line of the error, if the decoder reported one
+    pub line: Option<usize>,
+    /// One-based column of the error, if the decoder reported one
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{}:{}:{}: {}", self.file, line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "{}:{}: {}", self.file, line, self.message),
+            _ => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+/// An error raised while loading a schema file
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read or decoded. `.zetro` files may report
+    /// several errors at once.
+    Source(Vec<SourceError>),
+    /// The file was decoded, but is not a valid schema
+    Schema(SchemaError),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Source(errors) => write!(
+                f,
+                "{}",
+                errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+            LoadError::Schema(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Reads the schema file at `path` and parses it according to its extension.
+pub(crate) fn read_schema(path: &str) -> Result<ZetroSchema, LoadError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(LoadError::Source(vec![SourceError {
+                file: path.to_owned(),
+                line: None,
+                column: None,
+                message: format!("error reading file: {}", e),
+            }]))
+        }
+    };
+
+    parse_schema(&contents, SchemaFormat::from_path(path), path)
+}
+
+/// Parses the contents of a schema file. `file_name` is only used for error
+/// messages.
+pub(crate) fn parse_schema(
+    contents: &str,
+    format: SchemaFormat,
+    file_name: &str,
+) -> Result<ZetroSchema, LoadError> {
+    if format == SchemaFormat::Zetro {
+        return idl::parse_schema(contents).map_err(|errors| {
+            LoadError::Source(
+                errors
+                    .into_iter()
+                    .map(|e| {
+                        let (line, column) = offset_to_position(contents, e.span.start);
+                        SourceError {
+                            file: file_name.to_owned(),
+                            line: Some(line),
+                            column: Some(column),
+                            message: e.message,
+                        }
+                    })
+                    .collect(),
+            )
+        });
+    }
+
+    let value =
+        parse_schema_value(contents, format, file_name).map_err(|e| LoadError::Source(vec![e]))?;
+    ZetroSchema::from_value(&value).map_err(LoadError::Schema)
+}
+
+/// Decodes the contents of a JSON, JSON5, YAML or TOML schema file into a value
+/// tree. `file_name` is only used for error messages.
+pub(crate) fn parse_schema_value(
+    contents: &str,
+    format: SchemaFormat,
+    file_name: &str,
+) -> Result<serde_json::Value, SourceError> {
+    let error = |line: Option<usize>, column: Option<usize>, message: String| SourceError {
+        file: file_name.to_owned(),
+        line,
+        column,
+        message,
+    };
+
+    match format {
+        SchemaFormat::Json => serde_json::from_str::<serde_json::Value>(contents).map_err(|e| {
+            // serde_json reports line 0 for errors that have no position
+            let line = if e.line() == 0 { None } else { Some(e.line()) };
+            let column = line.map(|_| e.column());
+            // The position is already part of our output, so strip serde_json's
+            // " at line X column Y" suffix.
+            let message = e.to_string();
+            let message = match message.rfind(" at line ") {
+                Some(idx) if line.is_some() => message[..idx].to_owned(),
+                _ => message,
+            };
+            error(line, column, message)
+        }),
+        SchemaFormat::Json5 => {
+            json5::from_str::<serde_json::Value>(contents).map_err(|e| match e {
+                json5::Error::Message { msg, location } => match location {
+                    Some(l) => error(Some(l.line), Some(l.column), msg),
+                    None => error(None, None, msg),
+                },
+            })
+        }
+        SchemaFormat::Yaml => serde_yaml::from_str::<serde_json::Value>(contents).map_err(|e| {
+            let location = e.location();
+            let message = e.to_string();
+            // serde_yaml appends " at line X column Y" as well
+            let message = match message.rfind(" at line ") {
+                Some(idx) if location.is_some() => message[..idx].to_owned(),
+                _ => message,
+            };
+            match location {
+                Some(l) => error(Some(l.line()), Some(l.column()), message),
+                None => error(None, None, message),
+            }
+        }),
+        SchemaFormat::Toml => toml::from_str::<serde_json::Value>(contents).map_err(|e| {
+            let message = e.message().to_owned();
+            match e.span() {
+                Some(span) => {
+                    let (line, column) = offset_to_position(contents, span.start);
+                    error(Some(line), Some(column), message)
+                }
+                None => error(None, None, message),
+            }
+        }),
+        SchemaFormat::Zetro => Err(error(
+            None,
+            None,
+            String::from("`.zetro` files can not be decoded into a value tree"),
+        )),
+    }
+}
+
+/// Prints a schema in the given format. The output is canonical: parsing it
+/// and printing it again yields the same text.
+///
+/// Comments that are not part of a description can't be represented in a
+/// `ZetroSchema`, so they are not preserved.
+pub(crate) fn print_schema(schema: &ZetroSchema, format: SchemaFormat) -> String {
+    let value = schema.to_value();
+
+    let mut printed = match format {
+        // JSON is valid JSON5 as well
+        SchemaFormat::Json | SchemaFormat::Json5 => serde_json::to_string_pretty(&value).unwrap(),
+        SchemaFormat::Yaml => serde_yaml::to_string(&value).unwrap(),
+        SchemaFormat::Toml => toml::to_string_pretty(&value).unwrap(),
+        SchemaFormat::Zetro => idl::printer::print_schema(schema),
+    };
+    if !printed.ends_with('\n') {
+        printed.push('\n');
+    }
+    printed
+}
+
+/// Converts a byte offset in `contents` into a one-based (line, column) pair.
+pub(crate) fn offset_to_position(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{parse_schema, parse_schema_value, print_schema, LoadError, SchemaFormat};
+
+    fn expected_value() -> serde_json::Value {
+        json!({
+            "structs": {
+                "Message": {
+                    "description": "A chat message",
+                    "fields": {
+                        "id": "u64; Unique id",
+                        "tags": "?[]string",
+                    }
+                }
+            },
+            "enums": {
+                "RoomStatus": ["ACTIVE", "DISABLED"]
+            },
+            "routes": {}
+        })
+    }
+
+    /// Every format must decode into the exact same value tree
+    #[test]
+    fn formats_decode_identically() {
+        let json5 = r#"
+            // Comments are allowed in JSON5 and JSONC
+            {
+                structs: {
+                    Message: {
+                        description: "A chat message",
+                        fields: {
+                            id: "u64; Unique id",
+                            tags: "?[]string", /* trailing commas too */
+                        },
+                    },
+                },
+                enums: { RoomStatus: ["ACTIVE", "DISABLED",], },
+                routes: {},
+            }
+        "#;
+        let yaml = r#"
+# Comments are allowed in YAML
+structs:
+  Message:
+    description: A chat message
+    fields:
+      id: "u64; Unique id"
+      tags: "?[]string"
+enums:
+  RoomStatus: [ACTIVE, DISABLED]
+routes: {}
+"#;
+        let toml = r#"
+# Comments are allowed in TOML
+[structs.Message]
+description = "A chat message"
+fields = { id = "u64; Unique id", tags = "?[]string" }
+
+[enums]
+RoomStatus = ["ACTIVE", "DISABLED"]
+
+[routes]
+"#;
+
+        for (contents, format) in [
+            (json5, SchemaFormat::Json5),
+            (yaml, SchemaFormat::Yaml),
+            (toml, SchemaFormat::Toml),
+        ] {
+            let value = parse_schema_value(contents, format, "schema").unwrap();
+            assert_eq!(value, expected_value(), "format: {:?}", format);
+        }
+    }
+
+    /// Decoding errors must point into the original file
+    #[test]
+    fn error_positions() {
+        let err = parse_schema_value("{\n  \"structs\": {,\n}", SchemaFormat::Json, "a.json")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(15)));
+
+        let err = parse_schema_value(
+            "{\n  structs: {\n    a: ]\n}",
+            SchemaFormat::Json5,
+            "a.json5",
+        )
+        .unwrap_err();
+        assert_eq!(err.line, Some(3));
+
+        let err = parse_schema_value("structs:\n  a: [\n  b: c", SchemaFormat::Yaml, "a.yaml")
+            .unwrap_err();
+        assert!(err.line.is_some());
+
+        let err = parse_schema_value(
+            "[structs]\na = \"ok\"\nb = = 1\n",
+            SchemaFormat::Toml,
+            "a.toml",
+        )
+        .unwrap_err();
+        assert_eq!(err.line, Some(3));
+        assert!(err.to_string().starts_with("a.toml:3:"));
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(SchemaFormat::from_path("schema.json"), SchemaFormat::Json);
+        assert_eq!(SchemaFormat::from_path("schema.jsonc"), SchemaFormat::Json5);
+        assert_eq!(
+            SchemaFormat::from_path("dir/schema.YML"),
+            SchemaFormat::Yaml
+        );
+        assert_eq!(SchemaFormat::from_path("schema.toml"), SchemaFormat::Toml);
+        assert_eq!(SchemaFormat::from_path("schema"), SchemaFormat::Json);
+        assert_eq!(SchemaFormat::from_path("api.zetro"), SchemaFormat::Zetro);
+    }
+
+    /// `.zetro` errors are positioned like any other decoding error
+    #[test]
+    fn idl_error_positions() {
+        let err = parse_schema(
+            "/// A\nstruct A {\n  a: Missing\n}",
+            SchemaFormat::Zetro,
+            "a.zetro",
+        )
+        .unwrap_err();
+        match err {
+            LoadError::Source(errors) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].to_string(), "a.zetro:3:6: unknown type 'Missing'");
+            }
+            LoadError::Schema(e) => panic!("expected a source error. Got: {:#?}", e),
+        }
+    }
+
+    /// Printing is canonical: a messy schema is normalized, and printing the
+    /// result again changes nothing
+    #[test]
+    fn print_is_canonical() {
+        let messy = r#"{
+            "routes": {
+                "GetMessage": {
+                    "response": "?struct~Message;   The message  ",
+                    "request": "u64",
+                    "description": "  Get a message ",
+                    "kind": "query"
+                }
+            },
+            "enums": { "Status": ["SENT", "READ"] },
+            "structs": {
+                "Message": {
+                    "fields": {
+                        "status": "enum~Status",
+                        "id": "u64;  Unique id",
+                        "receipts": {
+                            "multiple": true,
+                            "nullable": false,
+                            "fields": { "read": "?[]bool; " },
+                            "description": "Read receipts"
+                        }
+                    },
+                    "description": "A message"
+                }
+            }
+        }"#;
+        let schema = parse_schema(messy, SchemaFormat::Json, "messy.json").unwrap();
+
+        for format in [
+            SchemaFormat::Json,
+            SchemaFormat::Yaml,
+            SchemaFormat::Toml,
+            SchemaFormat::Zetro,
+        ] {
+            let printed = print_schema(&schema, format);
+            let reparsed = parse_schema(&printed, format, "printed").unwrap();
+            assert_eq!(print_schema(&reparsed, format), printed, "{:?}", format);
+            assert_eq!(
+                print_schema(&reparsed, SchemaFormat::Json),
+                print_schema(&schema, SchemaFormat::Json),
+                "{:?}",
+                format
+            );
+        }
+
+        let printed = print_schema(&schema, SchemaFormat::Json);
+        assert!(printed.contains(r#""response": "?struct~Message; The message""#));
+        assert!(printed.contains(r#""description": "Get a message""#));
+        assert!(!printed.contains("nullable"));
+        assert!(printed.find("\"enums\"").unwrap() < printed.find("\"structs\"").unwrap());
+    }
+}
@@ -0,0 +1,809 @@
+//! Payloads as they are sent on the wire, read without generated code.
+//!
+//! A request is `[method code, [[route name, body], ...]]`, with encrypted
+//! route names. A response is `[[[route name, body], ...], null]`, or
+//! `[null, [code, message]]` for an error. Bodies are untagged (structs are
+//! arrays in field order) or tagged (structs are objects with camelCase
+//! keys). Enums are numbers in both cases.
+
+use convert_case::{Case, Casing};
+use serde_json::{json, Map, Value};
+
+use super::schema::{
+    fields::{FieldKind, ZetroField},
+    routes::{RouteKind, ZetroRoute},
+    structs::ZetroStruct,
+    ZetroSchema,
+};
+
+/// A value that doesn't match the schema
+#[derive(Debug, PartialEq)]
+pub(crate) struct Mismatch {
+    /// Where the value is in the decoded payload, eg. `query.GetRooms.rooms[0]`
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Decodes a request into `{"query": {"RouteName": body}}` (or `mutation`).
+/// If a route is called more than once, the routes are a list of
+/// `{"RouteName": body}` objects instead. Values that don't match the schema
+/// are kept as they are and reported.
+pub(crate) fn decode_request(schema: &ZetroSchema, payload: &Value) -> (Value, Vec<Mismatch>) {
+    let mut decoder = Decoder::new(schema);
+    let decoded = match payload.as_array().map(Vec::as_slice) {
+        Some([code, operations]) => {
+            let kind = match code.as_u64() {
+                Some(1) => Some(RouteKind::Query),
+                Some(2) => Some(RouteKind::Mutation),
+                _ => {
+                    decoder.mismatch("$[0]", format!("unknown method code {}", code));
+                    None
+                }
+            };
+            let key = match kind {
+                Some(RouteKind::Query) => "query",
+                Some(RouteKind::Mutation) => "mutation",
+                None => "unknown",
+            };
+            let routes = decoder.operations(operations, key, kind, Body::Request);
+            json!({ key: routes })
+        }
+        _ => {
+            decoder.mismatch("$", "expected [method code, [[route, body], ...]]");
+            payload.clone()
+        }
+    };
+    (decoded, decoder.mismatches)
+}
+
+/// Decodes a response into `{"data": {"RouteName": body}}` (see
+/// `decode_request`), or `{"error": {"code": code, "message": message}}`
+pub(crate) fn decode_response(schema: &ZetroSchema, payload: &Value) -> (Value, Vec<Mismatch>) {
+    let mut decoder = Decoder::new(schema);
+    let decoded = match payload.as_array().map(Vec::as_slice) {
+        Some([Value::Null, error]) => match error.as_array().map(Vec::as_slice) {
+            Some([code, message]) => {
+                if !code.is_i64() {
+                    decoder.mismatch("error.code", format!("expected a number, got {}", code));
+                }
+                if !message.is_string() {
+                    decoder.mismatch(
+                        "error.message",
+                        format!("expected a string, got {}", message),
+                    );
+                }
+                json!({ "error": { "code": code, "message": message } })
+            }
+            _ => {
+                decoder.mismatch("error", "expected [code, message]");
+                json!({ "error": error })
+            }
+        },
+        Some([data, Value::Null]) => {
+            json!({ "data": decoder.operations(data, "data", None, Body::Response) })
+        }
+        _ => {
+            decoder.mismatch(
+                "$",
+                "expected [[[route, body], ...], null] or [null, [code, message]]",
+            );
+            payload.clone()
+        }
+    };
+    (decoded, decoder.mismatches)
+}
+
+#[derive(Clone, Copy)]
+enum Body {
+    Request,
+    Response,
+}
+
+/// Decodes a request or response body of type `field`, as a server with
+/// the given tagging reads it. Values that don't match the schema are
+/// reported at `path`.
+pub(crate) fn decode_body(
+    schema: &ZetroSchema,
+    field: &ZetroField,
+    value: &Value,
+    untagged: bool,
+    path: &str,
+) -> (Value, Vec<Mismatch>) {
+    let mut decoder = Decoder::new(schema);
+    decoder.untagged = Some(untagged);
+    let decoded = decoder.field(field, value, path);
+    (decoded, decoder.mismatches)
+}
+
+struct Decoder<'a> {
+    schema: &'a ZetroSchema,
+    /// How structs are encoded. `None` accepts both.
+    untagged: Option<bool>,
+    mismatches: Vec<Mismatch>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(schema: &'a ZetroSchema) -> Self {
+        Self {
+            schema,
+            untagged: None,
+            mismatches: Vec::new(),
+        }
+    }
+
+    fn mismatch(&mut self, path: &str, message: impl Into<String>) {
+        self.mismatches.push(Mismatch {
+            path: path.to_owned(),
+            message: message.into(),
+        });
+    }
+
+    /// Finds the route with the encrypted name `wire_name`. Routes of the
+    /// other kind are found too, but reported at `path.RouteName`.
+    fn route(
+        &mut self,
+        wire_name: &str,
+        kind: Option<&RouteKind>,
+        path: &str,
+    ) -> Option<&'a ZetroRoute> {
+        let schema = self.schema;
+        let route = schema
+            .queries
+            .iter()
+            .chain(schema.mutations.iter())
+            .find(|route| route.encrypt_route_name() == wire_name)?;
+        let path = format!("{}.{}", path, route.name);
+        match (kind, &route.kind) {
+            (Some(RouteKind::Query), RouteKind::Mutation) => self.mismatch(
+                &path,
+                format!("{} is a mutation, but was sent as a query", route.name),
+            ),
+            (Some(RouteKind::Mutation), RouteKind::Query) => self.mismatch(
+                &path,
+                format!("{} is a query, but was sent as a mutation", route.name),
+            ),
+            _ => {}
+        }
+        Some(route)
+    }
+
+    /// Decodes a list of `[route name, body]` operations
+    fn operations(
+        &mut self,
+        operations: &Value,
+        path: &str,
+        kind: Option<RouteKind>,
+        body: Body,
+    ) -> Value {
+        let operations = match operations.as_array() {
+            Some(v) => v,
+            None => {
+                self.mismatch(
+                    path,
+                    format!("expected a list of operations, got {}", operations),
+                );
+                return operations.clone();
+            }
+        };
+
+        let mut decoded: Vec<(String, Value)> = Vec::new();
+        for (i, operation) in operations.iter().enumerate() {
+            let (wire_name, value) = match operation.as_array().map(Vec::as_slice) {
+                Some([Value::String(name), value]) => (name, value),
+                _ => {
+                    self.mismatch(
+                        &format!("{}[{}]", path, i),
+                        format!("expected [route name, body], got {}", operation),
+                    );
+                    continue;
+                }
+            };
+
+            match self.route(wire_name, kind.as_ref(), path) {
+                Some(route) => {
+                    let path = format!("{}.{}", path, route.name);
+                    let field = match body {
+                        Body::Request => &route.request_body,
+                        Body::Response => &route.response_body,
+                    };
+                    decoded.push((route.name.clone(), self.field(field, value, &path)));
+                }
+                None => {
+                    self.mismatch(
+                        &format!("{}.{}", path, wire_name),
+                        "no route has this encrypted name",
+                    );
+                    decoded.push((wire_name.clone(), value.clone()));
+                }
+            }
+        }
+
+        let mut names = decoded.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        if names.len() == decoded.len() {
+            Value::Object(decoded.into_iter().collect())
+        } else {
+            Value::Array(
+                decoded
+                    .into_iter()
+                    .map(|(name, value)| json!({ name: value }))
+                    .collect(),
+            )
+        }
+    }
+
+    fn field(&mut self, field: &ZetroField, value: &Value, path: &str) -> Value {
+        if value.is_null() {
+            if !field.is_nullable {
+                self.mismatch(path, "null, but the field is not nullable");
+            }
+            return Value::Null;
+        }
+        if !field.is_multiple {
+            return self.single(&field.kind, value, path);
+        }
+
+        match value.as_array() {
+            Some(items) => Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| self.single(&field.kind, item, &format!("{}[{}]", path, i)))
+                    .collect(),
+            ),
+            None => {
+                self.mismatch(path, format!("expected a list, got {}", value));
+                value.clone()
+            }
+        }
+    }
+
+    /// Decodes one value of a type, ie. an item of a list
+    fn single(&mut self, kind: &FieldKind, value: &Value, path: &str) -> Value {
+        let schema = self.schema;
+        match kind {
+            FieldKind::StructValue(name) => match schema.structs.iter().find(|s| &s.name == name) {
+                Some(_struct) => self.structure(_struct, value, path),
+                None => value.clone(),
+            },
+            FieldKind::NestedObject(_struct) => self.structure(_struct, value, path),
+            FieldKind::EnumValue(name) => {
+                let variants = schema
+                    .enums
+                    .iter()
+                    .find(|e| &e.name == name)
+                    .map(|e| e.variants.as_slice())
+                    .unwrap_or_default();
+                match value.as_u64().and_then(|i| variants.get(i as usize)) {
+                    Some(variant) => json!(variant),
+                    None => {
+                        self.mismatch(path, format!("{} is not a variant of {}", value, name));
+                        value.clone()
+                    }
+                }
+            }
+            primitive => {
+                if !primitive_matches(primitive, value) {
+                    self.mismatch(
+                        path,
+                        format!(
+                            "expected {}, got {}",
+                            primitive.primitive_name().unwrap(),
+                            value
+                        ),
+                    );
+                }
+                value.clone()
+            }
+        }
+    }
+
+    /// Decodes an untagged (array) or tagged (object) struct into an object
+    fn structure(&mut self, _struct: &ZetroStruct, value: &Value, path: &str) -> Value {
+        let mut decoded = Map::new();
+        match (value, self.untagged) {
+            (Value::Array(_), Some(false)) => {
+                self.mismatch(path, format!("expected {} as an object", _struct.name));
+            }
+            (Value::Object(_), Some(true)) => {
+                self.mismatch(path, format!("expected {} as an array", _struct.name));
+            }
+            _ => {}
+        }
+        match value {
+            Value::Array(items) => {
+                if items.len() != _struct.fields.len() {
+                    self.mismatch(
+                        path,
+                        format!(
+                            "{} has {} fields, got {} values",
+                            _struct.name,
+                            _struct.fields.len(),
+                            items.len()
+                        ),
+                    );
+                }
+                for (field, item) in _struct.fields.iter().zip(items) {
+                    let value = self.field(field, item, &format!("{}.{}", path, field.name));
+                    decoded.insert(field.name.clone(), value);
+                }
+                // Values past the last field are kept as they are, under a key
+                // no field can have
+                for (i, item) in items.iter().enumerate().skip(_struct.fields.len()) {
+                    decoded.insert(format!("unexpected[{}]", i), item.clone());
+                }
+            }
+            Value::Object(object) => {
+                for field in &_struct.fields {
+                    let path = format!("{}.{}", path, field.name);
+                    let value = object
+                        .get(&field.name)
+                        .or_else(|| object.get(&field.name.to_case(Case::Camel)));
+                    match value {
+                        Some(value) => {
+                            let value = self.field(field, value, &path);
+                            decoded.insert(field.name.clone(), value);
+                        }
+                        None if field.is_nullable => {
+                            decoded.insert(field.name.clone(), Value::Null);
+                        }
+                        None => self.mismatch(&path, "missing"),
+                    }
+                }
+                for key in object.keys() {
+                    let known = _struct
+                        .fields
+                        .iter()
+                        .any(|f| &f.name == key || &f.name.to_case(Case::Camel) == key);
+                    if !known {
+                        self.mismatch(
+                            &format!("{}.{}", path, key),
+                            format!("{} has no such field", _struct.name),
+                        );
+                        decoded.insert(key.clone(), object[key].clone());
+                    }
+                }
+            }
+            _ => {
+                self.mismatch(path, format!("expected {}, got {}", _struct.name, value));
+                return value.clone();
+            }
+        }
+        Value::Object(decoded)
+    }
+}
+
+/// Encodes a readable request or response body of type `field`, see
+/// `encode_request`
+pub(crate) fn encode_body(
+    schema: &ZetroSchema,
+    field: &ZetroField,
+    value: &Value,
+    untagged: bool,
+    path: &str,
+) -> Result<Value, Vec<Mismatch>> {
+    let mut encoder = Encoder {
+        schema,
+        untagged,
+        mismatches: Vec::new(),
+    };
+    let encoded = encoder.field(field, value, path);
+    match encoder.mismatches.is_empty() {
+        true => Ok(encoded),
+        false => Err(encoder.mismatches),
+    }
+}
+
+/// Encodes a readable request, as `decode_request` prints it, into the body
+/// a client sends. Structs are arrays when `untagged`, and objects with
+/// camelCase keys otherwise. Every value that doesn't match the schema is
+/// reported.
+pub(crate) fn encode_request(
+    schema: &ZetroSchema,
+    request: &Value,
+    untagged: bool,
+) -> Result<Value, Vec<Mismatch>> {
+    let mut encoder = Encoder {
+        schema,
+        untagged,
+        mismatches: Vec::new(),
+    };
+    let invalid = |path: &str, message: &str| {
+        Err(vec![Mismatch {
+            path: path.to_owned(),
+            message: message.to_owned(),
+        }])
+    };
+
+    let (key, routes) = match request.as_object() {
+        Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+        _ => return invalid("$", "expected {\"query\": {...}} or {\"mutation\": {...}}"),
+    };
+    let (kind, other) = match key.as_str() {
+        "query" => (RouteKind::Query, "mutation"),
+        "mutation" => (RouteKind::Mutation, "query"),
+        _ => return invalid(key, "expected `query` or `mutation`"),
+    };
+
+    // (path, route name, body) of every call
+    let mut calls: Vec<(String, &String, &Value)> = Vec::new();
+    match routes {
+        Value::Object(routes) => {
+            for (name, body) in routes {
+                calls.push((format!("{}.{}", key, name), name, body));
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                match item.as_object() {
+                    Some(call) if call.len() == 1 => {
+                        let (name, body) = call.iter().next().unwrap();
+                        calls.push((format!("{}[{}].{}", key, i, name), name, body));
+                    }
+                    _ => encoder
+                        .mismatch(&format!("{}[{}]", key, i), "expected {\"RouteName\": body}"),
+                }
+            }
+        }
+        _ => return invalid(key, "expected an object or a list of routes"),
+    }
+
+    let (routes, others) = match kind {
+        RouteKind::Query => (&schema.queries, &schema.mutations),
+        RouteKind::Mutation => (&schema.mutations, &schema.queries),
+    };
+    let mut operations: Vec<Value> = Vec::new();
+    for (path, name, body) in calls {
+        match routes.iter().find(|route| &route.name == name) {
+            Some(route) => {
+                let body = encoder.field(&route.request_body, body, &path);
+                operations.push(json!([route.encrypt_route_name(), body]));
+            }
+            None if others.iter().any(|route| &route.name == name) => {
+                encoder.mismatch(&path, format!("{} is a {}, not a {}", name, other, key))
+            }
+            None => encoder.mismatch(&path, format!("no {} is named {}", key, name)),
+        }
+    }
+
+    if !encoder.mismatches.is_empty() {
+        return Err(encoder.mismatches);
+    }
+    Ok(json!([kind.to_method_code(), operations]))
+}
+
+struct Encoder<'a> {
+    schema: &'a ZetroSchema,
+    untagged: bool,
+    mismatches: Vec<Mismatch>,
+}
+
+impl<'a> Encoder<'a> {
+    fn mismatch(&mut self, path: &str, message: impl Into<String>) {
+        self.mismatches.push(Mismatch {
+            path: path.to_owned(),
+            message: message.into(),
+        });
+    }
+
+    fn field(&mut self, field: &ZetroField, value: &Value, path: &str) -> Value {
+        if value.is_null() {
+            if !field.is_nullable {
+                self.mismatch(path, "null, but the field is not nullable");
+            }
+            return Value::Null;
+        }
+        if !field.is_multiple {
+            return self.single(&field.kind, value, path);
+        }
+
+        match value.as_array() {
+            Some(items) => Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| self.single(&field.kind, item, &format!("{}[{}]", path, i)))
+                    .collect(),
+            ),
+            None => {
+                self.mismatch(path, format!("expected a list, got {}", value));
+                Value::Null
+            }
+        }
+    }
+
+    fn single(&mut self, kind: &FieldKind, value: &Value, path: &str) -> Value {
+        let schema = self.schema;
+        match kind {
+            FieldKind::StructValue(name) => match schema.structs.iter().find(|s| &s.name == name) {
+                Some(_struct) => self.structure(_struct, value, path),
+                None => Value::Null,
+            },
+            FieldKind::NestedObject(_struct) => self.structure(_struct, value, path),
+            FieldKind::EnumValue(name) => {
+                let variants = schema
+                    .enums
+                    .iter()
+                    .find(|e| &e.name == name)
+                    .map(|e| e.variants.as_slice())
+                    .unwrap_or_default();
+                let position = value
+                    .as_str()
+                    .and_then(|v| variants.iter().position(|variant| variant == v));
+                match position {
+                    Some(i) => json!(i),
+                    None => {
+                        self.mismatch(
+                            path,
+                            format!("expected one of {}, got {}", variants.join(", "), value),
+                        );
+                        Value::Null
+                    }
+                }
+            }
+            primitive => {
+                if !primitive_matches(primitive, value) {
+                    self.mismatch(
+                        path,
+                        format!(
+                            "expected {}, got {}",
+                            primitive.primitive_name().unwrap(),
+                            value
+                        ),
+                    );
+                }
+                value.clone()
+            }
+        }
+    }
+
+    fn structure(&mut self, _struct: &ZetroStruct, value: &Value, path: &str) -> Value {
+        let object = match value.as_object() {
+            Some(v) => v,
+            None => {
+                self.mismatch(path, format!("expected {}, got {}", _struct.name, value));
+                return Value::Null;
+            }
+        };
+
+        let mut values: Vec<(String, Value)> = Vec::new();
+        for field in &_struct.fields {
+            let path = format!("{}.{}", path, field.name);
+            let wire_name = field.name.to_case(Case::Camel);
+            let value = match object.get(&field.name).or_else(|| object.get(&wire_name)) {
+                Some(value) => self.field(field, value, &path),
+                None if field.is_nullable => Value::Null,
+                None => {
+                    self.mismatch(&path, "missing");
+                    Value::Null
+                }
+            };
+            values.push((wire_name, value));
+        }
+        for key in object.keys() {
+            let known = _struct
+                .fields
+                .iter()
+                .any(|f| &f.name == key || &f.name.to_case(Case::Camel) == key);
+            if !known {
+                self.mismatch(
+                    &format!("{}.{}", path, key),
+                    format!("{} has no such field", _struct.name),
+                );
+            }
+        }
+
+        if self.untagged {
+            Value::Array(values.into_iter().map(|(_, value)| value).collect())
+        } else {
+            Value::Object(values.into_iter().collect())
+        }
+    }
+}
+
+/// Whether `value` fits a primitive type, including the range of integers
+fn primitive_matches(kind: &FieldKind, value: &Value) -> bool {
+    let signed = |min: i64, max: i64| value.as_i64().is_some_and(|v| v >= min && v <= max);
+    let unsigned = |max: u64| value.as_u64().is_some_and(|v| v <= max);
+    match kind {
+        FieldKind::Int8 => signed(i8::MIN.into(), i8::MAX.into()),
+        FieldKind::Int16 => signed(i16::MIN.into(), i16::MAX.into()),
+        FieldKind::Int32 => signed(i32::MIN.into(), i32::MAX.into()),
+        FieldKind::Int64 => value.is_i64(),
+        FieldKind::UInt8 => unsigned(u8::MAX.into()),
+        FieldKind::UInt16 => unsigned(u16::MAX.into()),
+        FieldKind::UInt32 => unsigned(u32::MAX.into()),
+        FieldKind::UInt64 => value.is_u64(),
+        FieldKind::Float32 | FieldKind::Float64 => value.is_number(),
+        FieldKind::Boolean => value.is_boolean(),
+        FieldKind::StringValue => value.is_string(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{decode_request, decode_response, encode_request};
+    use crate::common::schema::{routes::ZetroRoute, ZetroSchema};
+
+    fn schema() -> ZetroSchema {
+        ZetroSchema::from_value(&json!({
+            "structs": {
+                "Room": {
+                    "description": "",
+                    "fields": { "name": "string", "status": "enum~Status", "topic": "?string" }
+                },
+                "GetRoomsRequest": { "description": "", "fields": { "limit": "u8" } }
+            },
+            "enums": { "Status": ["ACTIVE", "DISABLED"] },
+            "routes": {
+                "GetRooms": {
+                    "kind": "query",
+                    "description": "",
+                    "request": "struct~GetRoomsRequest",
+                    "response": "[]struct~Room"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    /// Untagged and tagged payloads decode to the same readable JSON, and
+    /// values that don't fit the schema are reported with their path
+    #[test]
+    fn decodes_payloads() {
+        let schema = schema();
+        let route = ZetroRoute::encrypt_name("GetRooms");
+
+        let (request, mismatches) = decode_request(&schema, &json!([1, [[route, [300]]]]));
+        assert_eq!(
+            request,
+            json!({ "query": { "GetRooms": { "limit": 300 } } })
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "query.GetRooms.limit: expected u8, got 300"
+        );
+        assert_eq!(mismatches.len(), 1);
+
+        let (request, mismatches) = decode_request(&schema, &json!([2, [[route, { "limit": 1 }]]]));
+        assert_eq!(
+            request,
+            json!({ "mutation": { "GetRooms": { "limit": 1 } } })
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "mutation.GetRooms: GetRooms is a query, but was sent as a mutation"
+        );
+
+        let untagged = json!([[[route, [["General", 0, null], ["Old", 1, "x"]]]], null]);
+        let tagged = json!([[[route, [
+            { "name": "General", "status": 0 },
+            { "name": "Old", "status": 1, "topic": "x" }
+        ]]], null]);
+        let expected = json!({ "data": { "GetRooms": [
+            { "name": "General", "status": "ACTIVE", "topic": null },
+            { "name": "Old", "status": "DISABLED", "topic": "x" }
+        ] } });
+        for payload in [untagged, tagged] {
+            let (response, mismatches) = decode_response(&schema, &payload);
+            assert_eq!(response, expected);
+            assert!(mismatches.is_empty(), "{:?}", mismatches);
+        }
+
+        let (_, mismatches) = decode_response(&schema, &json!([[[route, [["A", 5]]]], null]));
+        let mismatches = mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            mismatches,
+            vec![
+                "data.GetRooms[0]: Room has 3 fields, got 2 values",
+                "data.GetRooms[0].status: 5 is not a variant of Status"
+            ]
+        );
+
+        let (response, mismatches) = decode_response(&schema, &json!([null, [404, "Not found"]]));
+        assert_eq!(
+            response,
+            json!({ "error": { "code": 404, "message": "Not found" } })
+        );
+        assert!(mismatches.is_empty());
+
+        let (_, mismatches) = decode_request(&schema, &json!([1, [["nope", 1]]]));
+        assert_eq!(
+            mismatches[0].to_string(),
+            "query.nope: no route has this encrypted name"
+        );
+    }
+
+    /// Extra values of an untagged struct are kept in the output, marked as
+    /// unexpected
+    #[test]
+    fn keeps_unexpected_values() {
+        let schema = schema();
+        let route = ZetroRoute::encrypt_name("GetRooms");
+
+        let payload = json!([[[route, [["General", 0, null, 7, "x"]]]], null]);
+        let (response, mismatches) = decode_response(&schema, &payload);
+        assert_eq!(
+            response,
+            json!({ "data": { "GetRooms": [{
+                "name": "General",
+                "status": "ACTIVE",
+                "topic": null,
+                "unexpected[3]": 7,
+                "unexpected[4]": "x"
+            }] } })
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "data.GetRooms[0]: Room has 3 fields, got 5 values"
+        );
+    }
+
+    /// Encoding checks the readable request against the schema, and decodes
+    /// back to the same request
+    #[test]
+    fn encodes_requests() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Filter": {
+                    "description": "",
+                    "fields": { "roomStatus": "?enum~Status", "query": "string" }
+                }
+            },
+            "enums": { "Status": ["ACTIVE", "DISABLED"] },
+            "routes": {
+                "Search": { "kind": "query", "description": "", "request": "[]struct~Filter", "response": "u8" },
+                "Ping": { "kind": "mutation", "description": "", "request": "u8", "response": "u8" }
+            }
+        }))
+        .unwrap();
+        let search = ZetroRoute::encrypt_name("Search");
+
+        let request =
+            json!({ "query": { "Search": [{ "query": "hi", "roomStatus": "DISABLED" }] } });
+        let encoded = encode_request(&schema, &request, true).unwrap();
+        assert_eq!(encoded, json!([1, [[search, [["hi", 1]]]]]));
+        assert_eq!(decode_request(&schema, &encoded), (request.clone(), vec![]));
+        assert_eq!(
+            encode_request(&schema, &request, false).unwrap(),
+            json!([1, [[search, [{ "query": "hi", "roomStatus": 1 }]]]])
+        );
+
+        let errors = |request| {
+            encode_request(&schema, &request, true)
+                .unwrap_err()
+                .into_iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            errors(json!({ "query": [{ "Search": [{ "roomStatus": "OPEN", "color": 1 }] }] })),
+            vec![
+                "query[0].Search[0].query: missing",
+                "query[0].Search[0].roomStatus: expected one of ACTIVE, DISABLED, got \"OPEN\"",
+                "query[0].Search[0].color: Filter has no such field",
+            ]
+        );
+        assert_eq!(
+            errors(json!({ "query": { "Ping": 1, "Pong": 1 } })),
+            vec![
+                "query.Ping: Ping is a mutation, not a query",
+                "query.Pong: no query is named Pong"
+            ]
+        );
+        assert_eq!(
+            errors(json!({ "mutation": { "Ping": 256 } })),
+            vec!["mutation.Ping: expected u8, got 256"]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! External plugins: executables named `zetro-plugin-<name>` on `PATH`.
+//!
+//! A plugin receives a JSON request on stdin with its arguments, the target
+//! and the schema IR (see `common::schema::ir`), after the generator renamed
+//! everything for its language and dropped unused types. It answers on stdout
+//! with code fragments to append to the generated file, and/or extra files.
+//! `docs/plugin-protocol.md` describes both messages.
+
+use std::{
+    io::Write,
+    path::{Component, Path},
+    process::{Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+use super::{generator, GeneratedFile};
+use crate::{
+    common::schema::{ir::IR_VERSION, ZetroSchema},
+    utilities::{EmitLang, PluginArg, PluginCall, ZetroArgs},
+};
+
+/// What a plugin generated
+#[derive(Debug, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct PluginOutput {
+    /// Must be the version of the request
+    version: u32,
+    /// Appended to the generated file, in order
+    #[serde(default)]
+    pub fragments: Vec<String>,
+    /// Paths are relative to the directory of the generated file
+    #[serde(default)]
+    pub files: Vec<GeneratedFile>,
+    /// Set by the plugin to fail generation
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Runs the executable of `plugin` for `schema`
+pub(crate) fn run(
+    plugin: &PluginCall,
+    schema: &ZetroSchema,
+    args: &ZetroArgs,
+) -> Result<PluginOutput, String> {
+    if !plugin
+        .name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid plugin name: '{}'", plugin.name));
+    }
+
+    let program = format!("zetro-plugin-{}", plugin.name);
+    call(&program, &request(plugin, schema, args))
+        .map_err(|e| format!("plugin '{}' ({}): {}", plugin.name, program, e))
+}
+
+fn request(plugin: &PluginCall, schema: &ZetroSchema, args: &ZetroArgs) -> Value {
+    let plugin_args = plugin
+        .args
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                PluginArg::Bool(v) => json!(v),
+                PluginArg::Int(v) => json!(v),
+                PluginArg::Float(v) => json!(v),
+                PluginArg::Str(v) => json!(v),
+            };
+            (key.clone(), value)
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    json!({
+        "version": IR_VERSION,
+        "plugin": { "name": plugin.name, "args": plugin_args },
+        "target": target_ir(args),
+        "schema": schema.to_target_ir(&|field| generator(args.language).type_name(field)),
+    })
+}
+
+/// The options of a target that plugins and templates see
+pub(super) fn target_ir(args: &ZetroArgs) -> Value {
+    json!({
+        "language": match args.language {
+            EmitLang::Rust => "rust",
+            EmitLang::Typescript => "typescript",
+        },
+        "outFile": args.out_file,
+        "untagged": args.untagged,
+        "mangle": args.mangle.unwrap_or(false),
+    })
+}
+
+/// Sends `request` to `program` and reads its response
+fn call(program: &str, request: &Value) -> Result<PluginOutput, String> {
+    let mut child = Command::new(program)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => String::from(
+                "Unrecognized plugin. It is not built in, and no executable with this name was \
+                found on PATH",
+            ),
+            _ => format!("error starting plugin: {}", e),
+        })?;
+
+    // Write on another thread, so that a plugin answering before it read the
+    // whole request can't block on a full pipe
+    let mut stdin = child.stdin.take().unwrap();
+    let request = request.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(request.as_bytes()));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("error running plugin: {}", e))?;
+    let written = writer.join().unwrap();
+    if !output.status.success() {
+        return Err(format!("plugin exited with {}", output.status));
+    }
+    written.map_err(|e| format!("error sending the request: {}", e))?;
+
+    let response = serde_json::from_slice::<PluginOutput>(&output.stdout)
+        .map_err(|e| format!("invalid response: {}", e))?;
+    if let Some(e) = response.error {
+        return Err(e);
+    }
+    if response.version != IR_VERSION {
+        return Err(format!(
+            "plugin answered with protocol version {}, expected {}",
+            response.version, IR_VERSION
+        ));
+    }
+    for file in &response.files {
+        let relative = Path::new(&file.path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+        if !relative {
+            return Err(format!(
+                "file paths must be relative and stay in the output directory. Got: '{}'",
+                file.path
+            ));
+        }
+    }
+
+    Ok(response)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::call;
+    use crate::generators::GeneratedFile;
+
+    /// Writes an executable shell script for each (name, body) and returns
+    /// their paths. All scripts are written before any runs: executing a file
+    /// while another thread's child still holds it open for writing fails with
+    /// ETXTBSY.
+    fn plugins(scripts: &[(&str, &str)]) -> Vec<String> {
+        let dir = std::env::temp_dir().join(format!("zetro-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        scripts
+            .iter()
+            .map(|(name, body)| {
+                let path = dir.join(name);
+                std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exchanges_json() {
+        let failing = [
+            ("fails", "exit 3"),
+            ("garbage", "echo nope"),
+            ("old", r#"echo '{"version":0}'"#),
+            ("error", r#"echo '{"version":1,"error":"bad args"}'"#),
+            (
+                "escape",
+                r#"echo '{"version":1,"files":[{"path":"../x","contents":""}]}'"#,
+            ),
+        ];
+        let mut scripts = vec![(
+            "echo",
+            r#"cat > /dev/null
+echo '{"version":1,"fragments":["// echo"],"files":[{"path":"a/b.ts","contents":"x"}]}'"#,
+        )];
+        scripts.extend(failing);
+        let paths = plugins(&scripts);
+
+        let request = serde_json::json!({ "version": 1, "plugin": { "args": {}, "name": "echo" } });
+        let output = call(&paths[0], &request).unwrap();
+        assert_eq!(output.fragments, vec!["// echo"]);
+        assert_eq!(
+            output.files,
+            vec![GeneratedFile {
+                path: String::from("a/b.ts"),
+                contents: String::from("x"),
+            }]
+        );
+
+        for ((name, _), path) in failing.iter().zip(&paths[1..]) {
+            assert!(call(path, &request).is_err(), "{}", name);
+        }
+        assert!(call("zetro-plugin-does-not-exist", &request).is_err());
+    }
+}
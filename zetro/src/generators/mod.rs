@@ -1,3 +1,545 @@
+use convert_case::{Case, Casing};
+
+use crate::{
+    common::schema::{
+        enums::ZetroEnum,
+        fields::{FieldKind, ZetroField},
+        structs::ZetroStruct,
+        ReachableTypes, ZetroSchema,
+    },
+    utilities::{EmitLang, ZetroArgs},
+};
+
+pub(crate) mod external;
+pub(crate) mod plugins;
 /// Generators for various languages
 pub(crate) mod rust;
+mod split;
+#[cfg(feature = "templates")]
+pub(crate) mod template;
 pub(crate) mod typescript;
+
+pub(crate) use plugins::plugin_help;
+use plugins::{Plugin, Resolved};
+
+/// Every language Zetro generates code in
+pub(crate) static GENERATORS: [&dyn Generator; 2] = [&rust::Rust, &typescript::TypeScript];
+
+/// Generates the types of a schema in one language. `generate` runs the steps
+/// in order: rename, drop unused types, then emit the prelude, structs, enums
+/// and the code of each plugin.
+pub(crate) trait Generator: Sync {
+    fn language(&self) -> EmitLang;
+    /// Name of the language, for messages
+    fn name(&self) -> &'static str;
+    /// Which side of the API the generated code runs on
+    fn side(&self) -> Side;
+    /// Built-in plugins for this language
+    fn plugins(&self) -> &'static [&'static dyn Plugin];
+
+    /// Rejects options the language doesn't support
+    fn check(&self, _args: &ZetroArgs) -> Result<(), String> {
+        Ok(())
+    }
+    /// Name of a field in generated code
+    fn field_name(&self, name: &str, args: &ZetroArgs) -> String;
+    /// Name of an enum variant in generated code
+    fn variant_name(&self, name: &str, args: &ZetroArgs) -> String;
+    /// The type of a field in generated code
+    fn type_name(&self, field: &ZetroField) -> String;
+    /// How a struct declares a field, eg. `pub id: u64`
+    fn field_declaration(&self, field: &ZetroField) -> String;
+
+    /// Imports, attributes and Zetro's own types, like `ZetroServerError`
+    fn prelude(&self, schema: &ZetroSchema, args: &ZetroArgs, usage: &TypeUsage) -> Vec<String>;
+    fn emit_structs(
+        &self,
+        scope: &mut Vec<String>,
+        structs: &[ZetroStruct],
+        untagged: bool,
+        usage: &TypeUsage,
+    );
+    fn emit_enums(&self, scope: &mut Vec<String>, enums: &[ZetroEnum], usage: &TypeUsage);
+
+    // Split output, see `split`
+    /// File name extension of generated files, eg. `rs`
+    fn extension(&self) -> &'static str;
+    /// Name of the index file, which declares the other modules
+    fn index_file(&self) -> &'static str;
+    /// Name of the module for a route, or for `shared`
+    fn module_name(&self, name: &str) -> String;
+    /// Words that can't be module names, eg. keywords
+    fn reserved_module_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Code at the top of a module, before its imports
+    fn module_prelude(
+        &self,
+        _structs: &[ZetroStruct],
+        _args: &ZetroArgs,
+        _usage: &TypeUsage,
+    ) -> Vec<String> {
+        Vec::new()
+    }
+    /// Imports `items` from the module `module`
+    fn import(&self, module: &str, items: &[String]) -> String;
+    /// Declares `module` in the index and re-exports everything in it
+    fn reexport(&self, module: &str) -> String;
+    /// Whether code in the index can use the items it re-exports without
+    /// importing them
+    fn reexports_in_scope(&self) -> bool;
+    /// The items that code (de)serializing `field` in `directions` refers to,
+    /// as (type name, item name)
+    fn field_references(
+        &self,
+        field: &ZetroField,
+        directions: Directions,
+        untagged: bool,
+    ) -> Vec<(String, String)>;
+}
+
+/// The generator of `language`
+pub(crate) fn generator(language: EmitLang) -> &'static dyn Generator {
+    *GENERATORS
+        .iter()
+        .find(|g| g.language() == language)
+        .expect("every language has a generator")
+}
+
+/// Code generated for a target
+#[derive(Debug)]
+pub(crate) struct Generated {
+    /// Contents of the out file
+    pub code: String,
+    /// Extra files from external plugins
+    pub files: Vec<GeneratedFile>,
+}
+
+#[derive(Debug, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct GeneratedFile {
+    /// Relative to the directory of the out file
+    pub path: String,
+    pub contents: String,
+}
+
+impl Generated {
+    /// Every file to write as (path, contents), starting with `out_file`
+    pub fn outputs<'a>(&'a self, out_file: &str) -> Vec<(String, &'a str)> {
+        let dir = std::path::Path::new(out_file)
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new(""));
+
+        let mut outputs = vec![(out_file.to_owned(), self.code.as_str())];
+        for file in &self.files {
+            outputs.push((
+                dir.join(&file.path).to_string_lossy().into_owned(),
+                file.contents.as_str(),
+            ));
+        }
+        outputs
+    }
+}
+
+/// Generates the contents of `args.out_file` in the language of the target,
+/// with the target's header (if any) on top.
+pub(crate) fn generate(schema: &ZetroSchema, args: &ZetroArgs) -> Result<Generated, String> {
+    let generator = generator(args.language);
+    generator.check(args)?;
+    if args.split && args.template.is_some() {
+        return Err(String::from(
+            "A template can't be combined with split output",
+        ));
+    }
+    // Resolve plugins first, so that invalid arguments fail before any work
+    let plugins = args
+        .plugins
+        .iter()
+        .map(|call| plugins::resolve(generator, call))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut schema = schema.clone();
+    rename_schema(generator, &mut schema, args);
+    let both = plugins.iter().any(|plugin| match plugin {
+        Resolved::BuiltIn(plugin, _) => plugin.needs_both_directions(),
+        Resolved::External(_) => false,
+    });
+    let usage =
+        TypeUsage::new(&schema, generator.side(), args.keep_unused_types).in_both_directions(both);
+    usage.prune(&mut schema);
+
+    let mut files: Vec<GeneratedFile> = Vec::new();
+    let mut scope = match &args.template {
+        #[cfg(feature = "templates")]
+        Some(path) => vec![template::render(path, &schema, args)?],
+        #[cfg(not(feature = "templates"))]
+        Some(_) => {
+            return Err(String::from(
+                "Templates need the `templates` feature of zetro",
+            ))
+        }
+        None if args.split => {
+            // The types are in the modules, so the index only has the prelude's own
+            let index_schema = ZetroSchema {
+                structs: Vec::new(),
+                enums: Vec::new(),
+                ..schema.clone()
+            };
+            let modules =
+                split::emit_modules(generator, &schema, args, &usage, !plugins.is_empty());
+            files.extend(modules.files);
+            let mut scope = modules.imports;
+            scope.extend(generator.prelude(&index_schema, args, &usage));
+            scope.extend(modules.reexports);
+            scope
+        }
+        None => {
+            let mut scope = generator.prelude(&schema, args, &usage);
+            generator.emit_structs(&mut scope, &schema.structs, args.untagged, &usage);
+            generator.emit_enums(&mut scope, &schema.enums, &usage);
+            scope
+        }
+    };
+
+    for plugin in plugins {
+        match plugin {
+            Resolved::BuiltIn(plugin, plugin_args) => {
+                plugin.generate(&mut scope, &schema, args, &plugin_args)?
+            }
+            Resolved::External(call) => {
+                let output = external::run(call, &schema, args)?;
+                scope.extend(output.fragments);
+                files.extend(output.files);
+            }
+        }
+    }
+
+    // Templates write their own header
+    let code = match &args.template {
+        Some(_) => scope.join("\n\n"),
+        None => format!(
+            "// This is an autogenerated file. DO NOT EDIT\n\n{}",
+            scope.join("\n\n")
+        ),
+    };
+    Ok(Generated {
+        code: with_header(args, code),
+        files,
+    })
+}
+
+/// The code that declares the struct or enum `name`, as a tagged target
+/// without plugins has it. `None` if the schema has no such type.
+pub(crate) fn declaration(schema: &ZetroSchema, args: &ZetroArgs, name: &str) -> Option<String> {
+    let generator = generator(args.language);
+    let mut schema = schema.clone();
+    rename_schema(generator, &mut schema, args);
+    let usage = TypeUsage::new(&schema, generator.side(), args.keep_unused_types);
+    let name = name.to_case(Case::Pascal);
+
+    let mut scope: Vec<String> = Vec::new();
+    if let Some(_struct) = schema.structs.iter().find(|s| s.name == name) {
+        generator.emit_structs(&mut scope, std::slice::from_ref(_struct), false, &usage);
+    } else if let Some(_enum) = schema.enums.iter().find(|e| e.name == name) {
+        generator.emit_enums(&mut scope, std::slice::from_ref(_enum), &usage);
+    } else {
+        return None;
+    }
+    // TypeScript marks where the structs and enums start and end
+    scope.retain(|block| !block.starts_with("/* ="));
+    Some(scope.join("\n\n"))
+}
+
+/// How the generated code declares `field`, eg. `pub with_status: Option<RoomStatus>`
+pub(crate) fn field_declaration(field: &ZetroField, args: &ZetroArgs) -> String {
+    let generator = generator(args.language);
+    let mut field = field.clone();
+    rename_field(generator, &mut field, args);
+    generator.field_declaration(&field)
+}
+
+/// Puts the target's header (if any) on top of `code`
+fn with_header(args: &ZetroArgs, code: String) -> String {
+    match &args.header {
+        Some(header) if header.ends_with('\n') => format!("{}{}", header, code),
+        Some(header) => format!("{}\n{}", header, code),
+        None => code,
+    }
+}
+
+/// Renames the types and fields in `schema` to the conventions of
+/// `generator`. Structs and enums are PascalCased.
+///
+/// Route names are not altered, because the route name identifies a route
+/// on the wire and changing its case would invalidate references to it in
+/// other languages.
+fn rename_schema(generator: &dyn Generator, schema: &mut ZetroSchema, args: &ZetroArgs) {
+    for _struct in &mut schema.structs {
+        // Notice that we only change case of top-level structs.
+        // This is because nested structs will be of the type
+        // {ParentStructName}_{fieldName} and we don't want to undo that
+        _struct.name = _struct.name.to_case(Case::Pascal);
+        rename_struct(generator, _struct, args);
+    }
+    for _enum in &mut schema.enums {
+        _enum.name = _enum.name.to_case(Case::Pascal);
+        for variant in &mut _enum.variants {
+            *variant = generator.variant_name(variant, args);
+        }
+    }
+    for route in schema.queries.iter_mut().chain(schema.mutations.iter_mut()) {
+        rename_field(generator, &mut route.request_body, args);
+        rename_field(generator, &mut route.response_body, args);
+    }
+}
+
+/// Renames the fields of `_struct`, but not the struct itself
+fn rename_struct(generator: &dyn Generator, _struct: &mut ZetroStruct, args: &ZetroArgs) {
+    for field in &mut _struct.fields {
+        rename_field(generator, field, args);
+    }
+}
+
+fn rename_field(generator: &dyn Generator, field: &mut ZetroField, args: &ZetroArgs) {
+    field.name = generator.field_name(&field.name, args);
+
+    // Also rename the identifiers in field
+    match &mut field.kind {
+        FieldKind::StructValue(name) | FieldKind::EnumValue(name) => {
+            *name = name.to_case(Case::Pascal);
+        }
+        FieldKind::NestedObject(nested) => rename_struct(generator, nested, args),
+        _ => {
+            // Primitives have nothing (inside them) to rename.
+        }
+    }
+}
+
+/// `ZetroServerError`, the struct errors are sent in, with its fields named
+/// for `generator`
+fn server_error_struct(
+    generator: &dyn Generator,
+    args: &ZetroArgs,
+    description: &str,
+) -> ZetroStruct {
+    let field = |name: &str, description: &str, kind| ZetroField {
+        description: Some(String::from(description)),
+        kind,
+        name: String::from(name),
+        is_multiple: false,
+        is_nullable: false,
+        is_recursive: false,
+    };
+
+    let mut error = ZetroStruct {
+        name: String::from("ZetroServerError"),
+        description: String::from(description),
+        is_nullable: false,
+        is_multiple: false,
+        lint_allow: Vec::new(),
+        fields: vec![
+            field(
+                "message",
+                "Arbitrary, human-readable message for the end user",
+                FieldKind::StringValue,
+            ),
+            field(
+                "code",
+                "Arbitrary, response code for frontend logic",
+                FieldKind::Int16,
+            ),
+        ],
+    };
+    rename_struct(generator, &mut error, args);
+    error
+}
+
+/// The side of the API generated code runs on. Servers deserialize requests
+/// and serialize responses, while clients do the opposite.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Side {
+    Server,
+    Client,
+}
+
+/// Which (de)serialization code a type needs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Directions {
+    pub serialize: bool,
+    pub deserialize: bool,
+}
+
+impl Directions {
+    pub const BOTH: Directions = Directions {
+        serialize: true,
+        deserialize: true,
+    };
+
+    /// Whether the type is needed at all
+    pub fn any(&self) -> bool {
+        self.serialize || self.deserialize
+    }
+}
+
+/// Tracks which types a generator has to emit, and in which directions.
+/// Types that no route uses are dropped, unless `keep_unused` is set, in
+/// which case every type is emitted in both directions.
+pub(crate) struct TypeUsage {
+    serialized: ReachableTypes,
+    deserialized: ReachableTypes,
+    keep_unused: bool,
+    both: bool,
+}
+
+impl TypeUsage {
+    pub fn new(schema: &ZetroSchema, side: Side, keep_unused: bool) -> Self {
+        let routes = schema.queries.iter().chain(schema.mutations.iter());
+        let requests = schema.reachable_from(routes.clone().map(|r| &r.request_body));
+        let responses = schema.reachable_from(routes.map(|r| &r.response_body));
+
+        let (serialized, deserialized) = match side {
+            Side::Server => (responses, requests),
+            Side::Client => (requests, responses),
+        };
+
+        Self {
+            serialized,
+            deserialized,
+            keep_unused,
+            both: false,
+        }
+    }
+
+    /// Emits the types that are used in both directions, eg. for plugins that
+    /// round-trip them
+    pub fn in_both_directions(mut self, both: bool) -> Self {
+        self.both = both;
+        self
+    }
+
+    pub fn struct_directions(&self, name: &str) -> Directions {
+        if self.keep_unused {
+            return Directions::BOTH;
+        }
+        self.widen(Directions {
+            serialize: self.serialized.structs.contains(name),
+            deserialize: self.deserialized.structs.contains(name),
+        })
+    }
+
+    pub fn enum_directions(&self, name: &str) -> Directions {
+        if self.keep_unused {
+            return Directions::BOTH;
+        }
+        self.widen(Directions {
+            serialize: self.serialized.enums.contains(name),
+            deserialize: self.deserialized.enums.contains(name),
+        })
+    }
+
+    fn widen(&self, directions: Directions) -> Directions {
+        if self.both && directions.any() {
+            Directions::BOTH
+        } else {
+            directions
+        }
+    }
+
+    /// Removes the structs and enums that are not needed from `schema`
+    pub fn prune(&self, schema: &mut ZetroSchema) {
+        schema
+            .structs
+            .retain(|s| self.struct_directions(&s.name).any());
+        schema.enums.retain(|e| self.enum_directions(&e.name).any());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Directions, Side, TypeUsage};
+    use crate::common::schema::ZetroSchema;
+
+    /// Servers deserialize requests and serialize responses. Clients do the
+    /// opposite, and unused types are dropped on both sides.
+    #[test]
+    fn directions_per_side() {
+        let mut schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Request": { "description": "", "fields": { "shared": "struct~Shared" } },
+                "Response": { "description": "", "fields": { "shared": "struct~Shared" } },
+                "Shared": { "description": "", "fields": { "status": "enum~Status" } },
+                "Unused": { "description": "", "fields": {} },
+            },
+            "enums": { "Status": ["A"], "Unused": ["B"] },
+            "routes": {
+                "Do": {
+                    "kind": "mutation",
+                    "description": "",
+                    "request": "struct~Request",
+                    "response": "[]struct~Response"
+                }
+            }
+        }))
+        .unwrap();
+
+        let server = TypeUsage::new(&schema, Side::Server, false);
+        let client = TypeUsage::new(&schema, Side::Client, false);
+        let only = |serialize, deserialize| Directions {
+            serialize,
+            deserialize,
+        };
+
+        assert_eq!(server.struct_directions("Request"), only(false, true));
+        assert_eq!(server.struct_directions("Response"), only(true, false));
+        assert_eq!(client.struct_directions("Request"), only(true, false));
+        assert_eq!(client.struct_directions("Response"), only(false, true));
+        assert_eq!(server.struct_directions("Shared"), Directions::BOTH);
+        assert_eq!(server.enum_directions("Status"), Directions::BOTH);
+        assert_eq!(server.struct_directions("Unused"), only(false, false));
+
+        let kept = TypeUsage::new(&schema, Side::Server, true);
+        assert_eq!(kept.struct_directions("Unused"), Directions::BOTH);
+
+        let both = TypeUsage::new(&schema, Side::Server, false).in_both_directions(true);
+        assert_eq!(both.struct_directions("Request"), Directions::BOTH);
+        assert_eq!(both.struct_directions("Unused"), only(false, false));
+
+        server.prune(&mut schema);
+        let names = schema
+            .structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Request", "Response", "Shared"]);
+        assert_eq!(schema.enums.len(), 1);
+    }
+
+    /// Rust code only depends on warp with the warp plugin, so libraries and
+    /// `zetro::schema!` users don't need it
+    #[test]
+    fn warp_only_with_its_plugin() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {},
+            "enums": {},
+            "routes": {
+                "Ping": { "kind": "query", "description": "", "request": "u8", "response": "u8" }
+            }
+        }))
+        .unwrap();
+
+        let mut options = crate::Options::new(crate::Language::Rust);
+        let code = super::generate(&schema, &options.to_args().unwrap())
+            .unwrap()
+            .code;
+        assert!(!code.contains("warp"), "{}", code);
+
+        options.plugins.push(crate::Plugin::new("warp"));
+        let code = super::generate(&schema, &options.to_args().unwrap())
+            .unwrap()
+            .code;
+        assert!(code.contains("use warp::Filter;"));
+        assert!(code.contains("impl warp::reject::Reject for ZetroServerError {}"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Built-in plugins. Each generator lists the plugins of its language, and
+//! every plugin declares its arguments, so that they are validated (and
+//! documented in `--help`) the same way for every plugin.
+
+use std::collections::HashMap;
+
+use super::{Generator, GENERATORS};
+use crate::{
+    common::schema::ZetroSchema,
+    utilities::{PluginArg, PluginCall, ZetroArgs},
+};
+
+/// A plugin that adds code after the types of a target are generated
+pub(crate) trait Plugin: Sync {
+    /// Name used in `--add-plugin` and `zetro.toml`
+    fn name(&self) -> &'static str;
+    /// What the plugin generates, for `--help`
+    fn description(&self) -> &'static str;
+    /// The arguments the plugin accepts
+    fn args(&self) -> &'static [ArgSpec] {
+        &[]
+    }
+    /// Whether the plugin needs every type to both serialize and
+    /// deserialize, whatever side of the API the target is on
+    fn needs_both_directions(&self) -> bool {
+        false
+    }
+    /// Adds the plugin's code to `scope`. `schema` is the schema as the types
+    /// were generated from, ie. renamed and without unused types.
+    fn generate(
+        &self,
+        scope: &mut Vec<String>,
+        schema: &ZetroSchema,
+        target: &ZetroArgs,
+        args: &PluginArgs,
+    ) -> Result<(), String>;
+}
+
+/// An argument of a plugin
+pub(crate) struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub description: &'static str,
+}
+
+/// The type of an argument, with its default value
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ArgKind {
+    Bool(bool),
+}
+
+impl ArgKind {
+    fn type_name(&self) -> &'static str {
+        match self {
+            ArgKind::Bool(_) => "bool",
+        }
+    }
+
+    fn default_value(&self) -> PluginArg {
+        match self {
+            ArgKind::Bool(v) => PluginArg::Bool(*v),
+        }
+    }
+
+    /// Converts `value` to this type. Arguments from the command line are
+    /// strings, so strings are parsed.
+    fn convert(&self, value: &PluginArg) -> Option<PluginArg> {
+        match self {
+            ArgKind::Bool(_) => value.as_bool().map(PluginArg::Bool),
+        }
+    }
+}
+
+/// Arguments of a plugin, checked against its `ArgSpec`s. Every declared
+/// argument has a value of its type.
+#[derive(Debug)]
+pub(crate) struct PluginArgs {
+    values: HashMap<&'static str, PluginArg>,
+}
+
+impl PluginArgs {
+    fn validate(plugin: &dyn Plugin, call: &PluginCall) -> Result<Self, String> {
+        let specs = plugin.args();
+        let mut values = specs
+            .iter()
+            .map(|spec| (spec.name, spec.kind.default_value()))
+            .collect::<HashMap<_, _>>();
+
+        // Sorted so that errors don't depend on the order of a hash map
+        let mut given = call.args.iter().collect::<Vec<_>>();
+        given.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (key, value) in given {
+            let spec = match specs.iter().find(|spec| spec.name == key) {
+                Some(v) => v,
+                None if specs.is_empty() => {
+                    return Err(format!(
+                        "{}: unknown argument `{}`. The plugin takes no arguments",
+                        plugin.name(),
+                        key
+                    ));
+                }
+                None => {
+                    let names = specs.iter().map(|spec| spec.name).collect::<Vec<_>>();
+                    return Err(format!(
+                        "{}: unknown argument `{}`. Expected one of: {}",
+                        plugin.name(),
+                        key,
+                        names.join(", ")
+                    ));
+                }
+            };
+            let value = spec.kind.convert(value).ok_or_else(|| {
+                format!(
+                    "{}: `{}` must be a {}",
+                    plugin.name(),
+                    key,
+                    spec.kind.type_name()
+                )
+            })?;
+            values.insert(spec.name, value);
+        }
+
+        Ok(Self { values })
+    }
+
+    /// The value of a `bool` argument
+    pub fn bool(&self, name: &str) -> bool {
+        matches!(self.values.get(name), Some(PluginArg::Bool(true)))
+    }
+}
+
+/// How a plugin of a target runs
+pub(super) enum Resolved<'a> {
+    BuiltIn(&'static dyn Plugin, PluginArgs),
+    /// See `external`
+    External(&'a PluginCall),
+}
+
+/// Finds the plugin `call` refers to and validates its arguments. Names that
+/// are not built in are external plugins, unless they belong to another
+/// language.
+pub(super) fn resolve<'a>(
+    generator: &dyn Generator,
+    call: &'a PluginCall,
+) -> Result<Resolved<'a>, String> {
+    if let Some(plugin) = generator.plugins().iter().find(|p| p.name() == call.name) {
+        return Ok(Resolved::BuiltIn(
+            *plugin,
+            PluginArgs::validate(*plugin, call)?,
+        ));
+    }
+
+    for other in GENERATORS.iter() {
+        if other.plugins().iter().any(|p| p.name() == call.name) {
+            return Err(format!(
+                "The plugin '{}' generates {} and can't be used for {}",
+                call.name,
+                other.name(),
+                generator.name()
+            ));
+        }
+    }
+    Ok(Resolved::External(call))
+}
+
+/// Documentation of every plugin, for `--help`
+pub(crate) fn plugin_help() -> String {
+    let mut help =
+        String::from("Plugins (`--add-plugin=name(arg:value ...)`, or `plugins` in zetro.toml):\n");
+
+    for generator in GENERATORS.iter() {
+        for plugin in generator.plugins() {
+            help.push_str(&format!("  {} ({})\n", plugin.name(), generator.name()));
+            for line in plugin.description().lines() {
+                help.push_str(&format!("      {}\n", line));
+            }
+            for arg in plugin.args() {
+                let usage = format!("{}=<{}>", arg.name, arg.kind.type_name());
+                let default = match arg.kind.default_value() {
+                    PluginArg::Bool(v) => v.to_string(),
+                    PluginArg::Int(v) => v.to_string(),
+                    PluginArg::Float(v) => v.to_string(),
+                    PluginArg::Str(v) => format!("\"{}\"", v),
+                };
+                help.push_str(&format!(
+                    "      {:<13} {} (default: {})\n",
+                    usage, arg.description, default
+                ));
+            }
+        }
+    }
+
+    help.push_str(
+        "  Any other name runs the executable `zetro-plugin-<name>` on PATH. See\n  \
+        docs/plugin-protocol.md\n",
+    );
+    help
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve, Resolved};
+    use crate::{
+        generators::{rust::Rust, typescript::TypeScript},
+        utilities::{PluginArg, PluginCall},
+    };
+
+    fn call(name: &str, args: &[(&str, PluginArg)]) -> PluginCall {
+        PluginCall {
+            name: name.to_owned(),
+            args: args
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// Arguments are converted to their declared type and default when
+    /// missing. Unknown plugins are external, unless another language has them.
+    #[test]
+    fn validates_plugin_args() {
+        let args = |call: &PluginCall| match resolve(&Rust, call) {
+            Ok(Resolved::BuiltIn(_, args)) => Ok(args),
+            Ok(Resolved::External(_)) => panic!("{} is built in", call.name),
+            Err(e) => Err(e),
+        };
+
+        assert!(!args(&call("warp", &[])).unwrap().bool("fnv"));
+        assert!(args(&call("warp", &[("fnv", PluginArg::Bool(true))]))
+            .unwrap()
+            .bool("fnv"));
+        assert!(args(&call("warp", &[("fnv", "TRUE".into())]))
+            .unwrap()
+            .bool("fnv"));
+
+        assert_eq!(
+            args(&call("warp", &[("fnv", PluginArg::Int(1))])).unwrap_err(),
+            "warp: `fnv` must be a bool"
+        );
+        assert_eq!(
+            args(&call("warp", &[("fvn", PluginArg::Bool(true))])).unwrap_err(),
+            "warp: unknown argument `fvn`. Expected one of: fnv"
+        );
+        assert!(resolve(
+            &TypeScript,
+            &call("class-client", &[("fnv", "true".into())])
+        )
+        .is_err());
+
+        assert!(matches!(
+            resolve(&Rust, &call("graphql", &[])),
+            Ok(Resolved::External(_))
+        ));
+        assert_eq!(
+            resolve(&TypeScript, &call("warp", &[])).err().unwrap(),
+            "The plugin 'warp' generates Rust and can't be used for TypeScript"
+        );
+    }
+}
@@ -1,14 +1,25 @@
-use crate::common::schema::enums::ZetroEnum;
+use crate::{
+    common::schema::enums::ZetroEnum,
+    generators::{Directions, TypeUsage},
+};
 
-pub(super) fn generate_enums(scope: &mut Vec<String>, enums: &Vec<ZetroEnum>) {
+pub(super) fn generate_enums(scope: &mut Vec<String>, enums: &[ZetroEnum], usage: &TypeUsage) {
     for _enum in enums {
-        scope.push(generate_enum(&_enum));
+        scope.push(generate_enum(_enum, usage.enum_directions(&_enum.name)));
     }
 }
 
 /// Generates a rust enum from the corresponding zetro enum and
 /// adds it to scope
-fn generate_enum(_enum: &ZetroEnum) -> String {
+fn generate_enum(_enum: &ZetroEnum, directions: Directions) -> String {
+    let mut derives = vec!["Debug", "Copy", "Clone", "PartialEq"];
+    if directions.serialize {
+        derives.push("serde_repr::Serialize_repr");
+    }
+    if directions.deserialize {
+        derives.push("serde_repr::Deserialize_repr");
+    }
+
     let mut enum_variants: Vec<String> = Vec::new();
 
     for (i, variant_name) in _enum.variants.iter().enumerate() {
@@ -16,8 +27,8 @@ fn generate_enum(_enum: &ZetroEnum) -> String {
     }
 
     format!(
-        "{}\n{}\npub enum {} {{\n{}\n}}",
-        "#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]",
+        "#[derive({})]\n{}\npub enum {} {{\n{}\n}}",
+        derives.join(", "),
         "#[repr(u8)]",
         _enum.name,
         enum_variants.join("\n"),
@@ -1,22 +1,34 @@
-use crate::common::schema::{fields::FieldKind, structs::ZetroStruct};
+use super::utilities::doc_comment;
+use crate::{
+    common::schema::{fields::FieldKind, structs::ZetroStruct},
+    generators::{Directions, TypeUsage},
+};
 
 pub(super) fn generate_structs(
     scope: &mut Vec<String>,
-    structs: &Vec<ZetroStruct>,
+    structs: &[ZetroStruct],
     untagged_repr: bool,
+    usage: &TypeUsage,
 ) {
     for _struct in structs {
-        scope.extend(generate_struct(&_struct, untagged_repr));
-        if untagged_repr {
-            scope.extend(generate_untagged_serializer(&_struct));
-            scope.extend(generate_untagged_deserializer(&_struct));
+        let directions = usage.struct_directions(&_struct.name);
+        scope.extend(generate_struct(_struct, untagged_repr, directions));
+        if untagged_repr && directions.serialize {
+            scope.extend(generate_untagged_serializer(_struct));
+        }
+        if untagged_repr && directions.deserialize {
+            scope.extend(generate_untagged_deserializer(_struct));
         }
     }
 }
 
 /// Generates a rust struct from the corresponding zetro struct and
-/// adds it to scope
-pub(super) fn generate_struct(_struct: &ZetroStruct, untagged_repr: bool) -> Vec<String> {
+/// adds it to scope. Serde traits are only derived in the given directions.
+pub(super) fn generate_struct(
+    _struct: &ZetroStruct,
+    untagged_repr: bool,
+    directions: Directions,
+) -> Vec<String> {
     let mut struct_blocks: Vec<String> = Vec::new();
     // List of fields for this struct
     let mut struct_fields: Vec<String> = Vec::new();
@@ -24,7 +36,7 @@ pub(super) fn generate_struct(_struct: &ZetroStruct, untagged_repr: bool) -> Vec
     for field in &_struct.fields {
         if let FieldKind::NestedObject(s) = &field.kind {
             // Another struct must be created for the nested object.
-            generate_struct(s, untagged_repr)
+            generate_struct(s, untagged_repr, directions)
                 .into_iter()
                 .enumerate()
                 .for_each(|(i, mut _s)| {
@@ -37,25 +49,34 @@ pub(super) fn generate_struct(_struct: &ZetroStruct, untagged_repr: bool) -> Vec
                 });
         }
         struct_fields.push(format!(
-            "{}\tpub {}: {},",
+            "{}\t{},",
             // Field documentation
             if let Some(d) = &field.description {
-                format!("\t/// {}\n", d)
+                format!("{}\n", doc_comment(d, "\t"))
             } else {
                 String::new()
             },
-            field.name,
-            field.to_rust_dtype()
+            field.to_rust_declaration()
         ));
     }
 
     struct_blocks.push(format!(
-        "/// {}\n{}\npub struct {} {{\n{}\n}}",
-        &_struct.description,
-        if untagged_repr {
-            "#[derive(Debug, Clone)]"
+        "{}\n{}\npub struct {} {{\n{}\n}}",
+        doc_comment(&_struct.description, ""),
+        if untagged_repr || !directions.any() {
+            String::from("#[derive(Debug, Clone)]")
         } else {
-            "#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]\n#[serde(rename_all = \"camelCase\")]"
+            let mut derives = vec!["Debug", "Clone"];
+            if directions.serialize {
+                derives.push("serde_derive::Serialize");
+            }
+            if directions.deserialize {
+                derives.push("serde_derive::Deserialize");
+            }
+            format!(
+                "#[derive({})]\n#[serde(rename_all = \"camelCase\")]",
+                derives.join(", ")
+            )
         },
         _struct.name,
         struct_fields.join("\n"),
@@ -85,10 +106,11 @@ pub(super) fn generate_untagged_serializer(_struct: &ZetroStruct) -> Vec<String>
         "fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 \twhere S: serde::ser::Serializer,
 \t{{
-\t\tlet mut state = serializer.serialize_tuple(2)?;
+\t\tlet mut state = serializer.serialize_tuple({})?;
 {}
 \t\tstate.end()    
 \t}}",
+        _struct.fields.len(),
         serialize_fn_elems.join("\n")
     );
 
@@ -1,7 +1,14 @@
 use convert_case::{Case, Casing};
 
-use crate::common::schema::{
-    fields::FieldKind, fields::ZetroField, structs::ZetroStruct, ZetroSchema,
+use crate::{
+    common::schema::{
+        enums::ZetroEnum,
+        fields::{FieldKind, ZetroField},
+        structs::ZetroStruct,
+        ZetroSchema,
+    },
+    generators::{plugins::Plugin, server_error_struct, Directions, Generator, Side, TypeUsage},
+    utilities::{EmitLang, ZetroArgs},
 };
 
 mod generate_enums;
@@ -9,162 +16,188 @@ mod generate_structs;
 mod plugins;
 mod utilities;
 
-/// Generates schema [de/ser]ialization code for Rust.
-pub(crate) fn generate_schema_code(
-    mut schema: ZetroSchema,
-    args: &crate::utilities::ZetroArgs,
-) -> Result<String, String> {
-    if args.mangle.is_some() {
-        return Err(String::from(
-            "The `mangle` option is not supported for Rust. Please run without the option.",
-        ));
-    }
-
-    let mut scope: Vec<String> = Vec::new();
-
-    // Add global imports and annotations
-    scope.push(format!(
-        "{}\n{}\n",
-        "#![allow(dead_code)]\nuse warp::Filter;",
-        if args.untagged {
-            "use serde::ser::SerializeTuple;"
-        } else {
-            ""
-        }
-    ));
-
-    rustify_schema(&mut schema, &args.field_casing);
-    generate_zetro_specific(&mut scope, args.untagged);
+/// Keywords and reserved words, which can't name a module
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Generates schema [de/ser]ialization code for Rust servers.
+/// - Structs and enums are PascalCased
+/// - Field names are snake_cased. Serde renames them on the wire.
+pub(crate) struct Rust;
+
+impl Generator for Rust {
+    fn language(&self) -> EmitLang {
+        EmitLang::Rust
+    }
 
-    generate_structs::generate_structs(&mut scope, &schema.structs, args.untagged);
-    generate_enums::generate_enums(&mut scope, &schema.enums);
+    fn name(&self) -> &'static str {
+        "Rust"
+    }
 
-    for plugin in &args.plugins {
-        match plugin.name.as_str() {
-            "warp" => {
-                plugins::warp::warp(plugin, &mut scope, &schema.queries, &schema.mutations);
-            }
-            _ => return Err(format!("Unrecognized plugin: '{}'", plugin.name)),
-        }
+    // Servers only deserialize requests and serialize responses
+    fn side(&self) -> Side {
+        Side::Server
     }
 
-    // Until we are able to remove unused [de]serializers, etc. We have to
-    // allow dead code to avoid warnings.
-    // Codegen does not provide a way to add global annotations, so we simply
-    // concatenate it to the scope
-    Ok(format!(
-        "// This is an autogenerated file. DO NOT EDIT\n\n{}",
-        scope.join("\n\n")
-    ))
-}
+    fn plugins(&self) -> &'static [&'static dyn Plugin] {
+        &[&plugins::warp::Warp, &plugins::proptest::Proptest]
+    }
 
-/// "Rustify" the property names in schema:
-/// - Structs will be PascalCased
-/// - Enums will be PascalCased
-/// - Field names will be snake_cased
-fn rustify_schema(schema: &mut ZetroSchema, field_casing: &Option<Case>) {
-    let field_casing = match field_casing {
-        Some(v) => v.clone(),
-        None => Case::Snake,
-    };
-
-    // Structs
-    for _struct in &mut schema.structs {
-        // Notice that we only change case of top-level structs.
-        // This is because nested structs will be of the type
-        // {ParentStructName}_{FieldName} and we don't want to undo that
-        _struct.name = _struct.name.to_case(Case::Pascal);
-        rustify_struct(_struct, field_casing);
-    }
-    // Enums
-    for _enum in &mut schema.enums {
-        _enum.name = _enum.name.to_case(Case::Pascal);
-        for variant in &mut _enum.variants {
-            *variant = variant.to_case(Case::Pascal);
+    fn check(&self, args: &ZetroArgs) -> Result<(), String> {
+        if args.mangle.is_some() {
+            return Err(String::from(
+                "The `mangle` option is not supported for Rust. Please run without the option.",
+            ));
         }
+        Ok(())
     }
 
-    let process_field = |field: &mut ZetroField| rustify_field(field, field_casing);
+    fn field_name(&self, name: &str, _args: &ZetroArgs) -> String {
+        name.to_case(Case::Snake)
+    }
 
-    // Routes. Notice we don't alter route names.
-    // This is because the route name is used to identify a particular route,
-    // and changing its case would invalidate references to it in other
-    // languages.
-    // Queries
-    for route in &mut schema.queries {
-        process_field(&mut route.request_body);
-        process_field(&mut route.response_body);
+    fn variant_name(&self, name: &str, _args: &ZetroArgs) -> String {
+        name.to_case(Case::Pascal)
     }
-    // Mutations
-    for route in &mut schema.mutations {
-        process_field(&mut route.request_body);
-        process_field(&mut route.response_body);
+
+    fn type_name(&self, field: &ZetroField) -> String {
+        field.to_rust_dtype()
     }
-}
 
-fn rustify_struct(_struct: &mut ZetroStruct, field_casing: Case) {
-    for field in &mut _struct.fields {
-        rustify_field(field, field_casing);
+    fn field_declaration(&self, field: &ZetroField) -> String {
+        field.to_rust_declaration()
     }
-}
 
-fn rustify_field(field: &mut ZetroField, field_casing: Case) {
-    field.name = field.name.to_case(Case::Snake);
+    fn prelude(&self, schema: &ZetroSchema, args: &ZetroArgs, usage: &TypeUsage) -> Vec<String> {
+        let mut scope: Vec<String> = Vec::new();
 
-    // Also rename the identifiers in field
-    match &mut field.kind {
-        FieldKind::StructValue(struct_name) => {
-            *struct_name = struct_name.to_case(Case::Pascal);
+        // Unused types and (de)serializers are only generated when they are
+        // kept, so only then is dead code allowed to avoid warnings. Code that
+        // is `include!`d can't contain the inner attribute, so the including
+        // module has to allow dead code instead.
+        let mut header: Vec<&str> = Vec::new();
+        if args.keep_unused_types && args.inner_attributes {
+            header.push("#![allow(dead_code)]");
+        }
+        if needs_serialize_tuple(&schema.structs, args, usage) {
+            header.push("use serde::ser::SerializeTuple;");
         }
-        FieldKind::EnumValue(enum_name) => {
-            *enum_name = enum_name.to_case(Case::Pascal);
+        if !header.is_empty() {
+            scope.push(header.join("\n"));
         }
-        FieldKind::NestedObject(nested) => {
-            rustify_struct(nested, field_casing);
+
+        let error = server_error_struct(
+            self,
+            args,
+            "AUTOGENERATED. The struct that serializes error states",
+        );
+        scope.push(
+            generate_structs::generate_struct(
+                &error,
+                args.untagged,
+                Directions {
+                    serialize: true,
+                    deserialize: false,
+                },
+            )
+            .pop()
+            .unwrap(),
+        );
+        scope
+    }
+
+    fn emit_structs(
+        &self,
+        scope: &mut Vec<String>,
+        structs: &[ZetroStruct],
+        untagged: bool,
+        usage: &TypeUsage,
+    ) {
+        generate_structs::generate_structs(scope, structs, untagged, usage);
+    }
+
+    fn emit_enums(&self, scope: &mut Vec<String>, enums: &[ZetroEnum], usage: &TypeUsage) {
+        generate_enums::generate_enums(scope, enums, usage);
+    }
+
+    fn extension(&self) -> &'static str {
+        "rs"
+    }
+
+    fn index_file(&self) -> &'static str {
+        "mod.rs"
+    }
+
+    fn module_name(&self, name: &str) -> String {
+        name.to_case(Case::Snake)
+    }
+
+    fn reserved_module_names(&self) -> &'static [&'static str] {
+        &KEYWORDS
+    }
+
+    fn module_prelude(
+        &self,
+        structs: &[ZetroStruct],
+        args: &ZetroArgs,
+        usage: &TypeUsage,
+    ) -> Vec<String> {
+        if needs_serialize_tuple(structs, args, usage) {
+            vec![String::from("use serde::ser::SerializeTuple;")]
+        } else {
+            Vec::new()
         }
-        _ => {
-            // Primitives have nothing (inside them) to rename.
+    }
+
+    fn import(&self, module: &str, items: &[String]) -> String {
+        format!("use super::{}::{{{}}};", module, items.join(", "))
+    }
+
+    fn reexport(&self, module: &str) -> String {
+        // Unused when the types of a module are only used outside the crate
+        format!(
+            "pub mod {0};\n#[allow(unused_imports)]\npub use {0}::*;",
+            module
+        )
+    }
+
+    // Glob imports bring the items into the scope of `mod.rs` too
+    fn reexports_in_scope(&self) -> bool {
+        true
+    }
+
+    fn field_references(
+        &self,
+        field: &ZetroField,
+        _directions: Directions,
+        _untagged: bool,
+    ) -> Vec<(String, String)> {
+        type_references(field)
+    }
+}
+
+/// The structs and enums `field` refers to. Rust only needs the types, as
+/// (de)serialization goes through their trait implementations.
+fn type_references(field: &ZetroField) -> Vec<(String, String)> {
+    match &field.kind {
+        FieldKind::StructValue(name) | FieldKind::EnumValue(name) => {
+            vec![(name.clone(), name.clone())]
         }
+        FieldKind::NestedObject(nested) => nested.fields.iter().flat_map(type_references).collect(),
+        _ => Vec::new(),
     }
 }
 
-/// Generate some zetro-specific structs such as ZetroServerError
-fn generate_zetro_specific(scope: &mut Vec<String>, untagged_repr: bool) {
-    // ZetroServerError
-    let zetro_server_error = ZetroStruct {
-        name: String::from("ZetroServerError"),
-        description: String::from("AUTOGENERATED. The struct that serializes error states"),
-        is_nullable: false,
-        is_multiple: false,
-        fields: vec![
-            ZetroField {
-                description: Some(String::from(
-                    "Arbitrary, human-readable message for the end user",
-                )),
-                kind: FieldKind::StringValue,
-                name: String::from("message"),
-                is_multiple: false,
-                is_nullable: false,
-                is_recursive: false,
-            },
-            ZetroField {
-                description: Some(String::from("Arbitrary, response code for frontend logic")),
-                kind: FieldKind::Int16,
-                name: String::from("code"),
-                is_multiple: false,
-                is_nullable: false,
-                is_recursive: false,
-            },
-        ],
-    };
-    scope.push(
-        generate_structs::generate_struct(&zetro_server_error, untagged_repr)
-            .pop()
-            .unwrap(),
-    );
-    // Make the struct implement warp::reject::Reject (marker trait)
-    // so warp::reject::custom() works
-    scope.push(String::from(
-        "impl warp::reject::Reject for ZetroServerError {}",
-    ));
+/// Untagged structs are serialized with `SerializeTuple`, which has to be in
+/// scope
+fn needs_serialize_tuple(structs: &[ZetroStruct], args: &ZetroArgs, usage: &TypeUsage) -> bool {
+    args.untagged
+        && structs
+            .iter()
+            .any(|s| usage.struct_directions(&s.name).serialize)
 }
@@ -1 +1,2 @@
+pub(super) mod proptest;
 pub(super) mod warp;
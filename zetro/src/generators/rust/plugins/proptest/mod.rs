@@ -0,0 +1,362 @@
+use convert_case::{Case, Casing};
+
+use crate::{
+    common::schema::{
+        enums::ZetroEnum,
+        fields::{FieldKind, ZetroField},
+        structs::ZetroStruct,
+        ZetroSchema,
+    },
+    generators::plugins::{Plugin, PluginArgs},
+    utilities::ZetroArgs,
+};
+
+/// How deep generated values nest structs through nullable fields and lists.
+/// Below it those fields are `None` or empty, so that recursive types end.
+const MAX_DEPTH: u32 = 3;
+
+/// proptest takes tuples of up to 12 strategies, so longer structs are
+/// generated from tuples of tuples
+const MAX_TUPLE_LEN: usize = 10;
+
+/// Generates `proptest` strategies for every type and tests that round-trip
+/// every struct through serde_json. Structs get a test-only `PartialEq` impl
+/// to compare the decoded value with the original.
+pub(crate) struct Proptest;
+
+impl Plugin for Proptest {
+    fn name(&self) -> &'static str {
+        "proptest"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generates `#[cfg(test)]` proptest `Arbitrary` impls for every type and
+a `zetro_round_trip` test module that serializes every struct with
+serde_json and reads it back. Types derive both serde directions, and
+structs implement `PartialEq` in tests. Enums need at least one variant."
+    }
+
+    fn needs_both_directions(&self) -> bool {
+        true
+    }
+
+    fn generate(
+        &self,
+        scope: &mut Vec<String>,
+        schema: &ZetroSchema,
+        _target: &ZetroArgs,
+        _args: &PluginArgs,
+    ) -> Result<(), String> {
+        // An enum without variants has no value to pick
+        if let Some(_enum) = schema.enums.iter().find(|e| e.variants.is_empty()) {
+            return Err(format!(
+                "The proptest plugin can't generate values of enum '{}', which has no variants",
+                _enum.name
+            ));
+        }
+
+        scope.push(format!(
+            "/// How deep `Arbitrary` values nest structs through nullable fields and lists\n\
+            #[cfg(test)]\nconst ZETRO_MAX_DEPTH: u32 = {};",
+            MAX_DEPTH
+        ));
+        for _struct in &schema.structs {
+            scope.extend(generate_struct_arbitrary(_struct));
+            scope.extend(generate_struct_eq(_struct));
+        }
+        for _enum in &schema.enums {
+            scope.push(generate_enum_arbitrary(_enum));
+        }
+        scope.push(generate_round_trip_tests(&schema.structs));
+        Ok(())
+    }
+}
+
+/// Generates the `Arbitrary` impl of a struct and of its nested objects. The
+/// parameter is how deep the value is nested.
+fn generate_struct_arbitrary(_struct: &ZetroStruct) -> Vec<String> {
+    let mut impl_blocks: Vec<String> = Vec::new();
+    let mut strategies: Vec<(String, String)> = Vec::new();
+
+    for field in &_struct.fields {
+        if let FieldKind::NestedObject(o) = &field.kind {
+            impl_blocks.extend(generate_struct_arbitrary(o));
+        }
+        strategies.push((field.name.clone(), field_strategy(field)));
+    }
+
+    let body = if strategies.is_empty() {
+        format!("\t\tJust({} {{}}).boxed()", _struct.name)
+    } else {
+        let (pattern, strategy) = tuple(&strategies, "\t\t");
+        let names = strategies
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        format!(
+            "\t\t{}\n\t\t\t.prop_map(|{}| {} {{ {} }})\n\t\t\t.boxed()",
+            strategy,
+            pattern,
+            _struct.name,
+            names.join(", ")
+        )
+    };
+
+    impl_blocks.push(format!(
+        "#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for {} {{
+\ttype Parameters = u32;
+\ttype Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+\t#[allow(unused_variables)]
+\tfn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {{
+\t\tuse proptest::prelude::*;
+{}
+\t}}
+}}",
+        _struct.name, body
+    ));
+    impl_blocks
+}
+
+/// Generates a test-only `PartialEq` impl for a struct and its nested
+/// objects, which compares every field
+fn generate_struct_eq(_struct: &ZetroStruct) -> Vec<String> {
+    let mut impl_blocks: Vec<String> = Vec::new();
+    let mut comparisons: Vec<String> = Vec::new();
+
+    for field in &_struct.fields {
+        if let FieldKind::NestedObject(o) = &field.kind {
+            impl_blocks.extend(generate_struct_eq(o));
+        }
+        comparisons.push(format!("self.{0} == other.{0}", field.name));
+    }
+    if comparisons.is_empty() {
+        comparisons.push(String::from("true"));
+    }
+
+    impl_blocks.push(format!(
+        "#[cfg(test)]
+impl PartialEq for {} {{
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {{
+		{}
+	}}
+}}",
+        _struct.name,
+        comparisons.join("\n\t\t\t&& ")
+    ));
+    impl_blocks
+}
+
+/// Generates the `Arbitrary` impl of an enum, which picks any variant
+fn generate_enum_arbitrary(_enum: &ZetroEnum) -> String {
+    let variants = _enum
+        .variants
+        .iter()
+        .map(|v| format!("{}::{}", _enum.name, v))
+        .collect::<Vec<_>>();
+
+    format!(
+        "#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for {} {{
+\ttype Parameters = ();
+\ttype Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+\tfn arbitrary_with(_: Self::Parameters) -> Self::Strategy {{
+\t\tuse proptest::prelude::*;
+\t\tprop::sample::select(vec![{}]).boxed()
+\t}}
+}}",
+        _enum.name,
+        variants.join(", ")
+    )
+}
+
+/// The strategy for a field, in a scope where `depth` is the depth of its
+/// struct
+fn field_strategy(field: &ZetroField) -> String {
+    let nests = matches!(
+        field.kind,
+        FieldKind::StructValue(_) | FieldKind::NestedObject(_)
+    );
+    let mut strategy = match &field.kind {
+        // Floats are multiples of 1/4, which JSON represents exactly
+        FieldKind::Float32 => {
+            String::from("(-1_000_000i32..1_000_000).prop_map(|n| n as f32 / 4.0)")
+        }
+        FieldKind::Float64 => {
+            String::from("(-1_000_000i32..1_000_000).prop_map(|n| f64::from(n) / 4.0)")
+        }
+        FieldKind::StructValue(s) if field.is_recursive => {
+            format!("any_with::<{}>(depth + 1).prop_map(Box::new)", s)
+        }
+        FieldKind::StructValue(s) => format!("any_with::<{}>(depth + 1)", s),
+        FieldKind::NestedObject(s) => format!("any_with::<{}>(depth + 1)", s.name),
+        _ => {
+            let single = ZetroField {
+                is_nullable: false,
+                is_multiple: false,
+                ..field.clone()
+            };
+            format!("any::<{}>()", single.to_rust_dtype())
+        }
+    };
+
+    if field.is_multiple {
+        strategy = format!("prop::collection::vec({}, 0..4)", strategy);
+    }
+    if field.is_nullable {
+        strategy = format!("prop::option::of({})", strategy);
+    }
+    if nests && (field.is_nullable || field.is_multiple) {
+        let empty = if field.is_nullable {
+            "None"
+        } else {
+            "Vec::new()"
+        };
+        strategy = format!(
+            "if depth < ZETRO_MAX_DEPTH {{ {}.boxed() }} else {{ Just({}).boxed() }}",
+            strategy, empty
+        );
+    }
+    strategy
+}
+
+/// Combines named strategies into one tuple strategy. Returns the pattern
+/// that destructures its values and the strategy.
+fn tuple(strategies: &[(String, String)], indent: &str) -> (String, String) {
+    if strategies.len() <= MAX_TUPLE_LEN {
+        let names = strategies
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        let values = strategies
+            .iter()
+            .map(|(_, strategy)| format!("{}\t{},", indent, strategy))
+            .collect::<Vec<_>>();
+        return (
+            format!("({},)", names.join(", ")),
+            format!("(\n{}\n{})", values.join("\n"), indent),
+        );
+    }
+
+    let chunks = strategies
+        .chunks(MAX_TUPLE_LEN)
+        .map(|chunk| tuple(chunk, indent))
+        .collect::<Vec<_>>();
+    tuple(&chunks, indent)
+}
+
+/// Generates a test module with a property test per struct. A struct is
+/// serialized and deserialized, which has to give the original value.
+fn generate_round_trip_tests(structs: &[ZetroStruct]) -> String {
+    let tests = structs
+        .iter()
+        .map(|s| {
+            format!(
+                "\t\t#[test]
+\t\tfn round_trips_{}(value in any::<{}>()) {{
+\t\t\tlet json = serde_json::to_string(&value).unwrap();
+\t\t\tlet decoded: {1} = serde_json::from_str(&json).unwrap();
+\t\t\tprop_assert_eq!(decoded, value);
+\t\t}}",
+                s.name.to_case(Case::Snake),
+                s.name
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "#[cfg(test)]
+mod zetro_round_trip {{
+\t#[allow(unused_imports)]
+\tuse super::*;
+\tuse proptest::prelude::*;
+
+\tproptest! {{
+{}
+\t}}
+}}",
+        tests.join("\n\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{field_strategy, generate_struct_arbitrary, generate_struct_eq, tuple};
+    use crate::common::schema::ZetroSchema;
+
+    /// Recursive fields stop at the maximum depth and long structs are built
+    /// from nested tuples
+    #[test]
+    fn generates_strategies() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Room": {
+                    "description": "",
+                    "fields": {
+                        "children": "[]struct~Room",
+                        "parent": "?struct~Room",
+                        "size": "?[]u8",
+                    }
+                }
+            },
+            "enums": {},
+            "routes": {}
+        }))
+        .unwrap();
+        let room = &schema.structs[0];
+        let field = |name: &str| room.fields.iter().find(|f| f.name == name).unwrap();
+
+        assert_eq!(
+            field_strategy(field("parent")),
+            "if depth < ZETRO_MAX_DEPTH { \
+            prop::option::of(any_with::<Room>(depth + 1).prop_map(Box::new)).boxed() \
+            } else { Just(None).boxed() }"
+        );
+        assert_eq!(
+            field_strategy(field("children")),
+            "if depth < ZETRO_MAX_DEPTH { \
+            prop::collection::vec(any_with::<Room>(depth + 1).prop_map(Box::new), 0..4).boxed() \
+            } else { Just(Vec::new()).boxed() }"
+        );
+        assert_eq!(
+            field_strategy(field("size")),
+            "prop::option::of(prop::collection::vec(any::<u8>(), 0..4))"
+        );
+        assert!(generate_struct_arbitrary(room)[0]
+            .contains(".prop_map(|(children, parent, size,)| Room { children, parent, size })"));
+        assert!(generate_struct_eq(room)[0].contains(
+            "self.children == other.children\n\t\t\t&& self.parent == other.parent\n\t\t\t&& self.size == other.size"
+        ));
+
+        let strategies = (0..12)
+            .map(|i| (format!("f{}", i), String::from("any::<u8>()")))
+            .collect::<Vec<_>>();
+        let (pattern, _) = tuple(&strategies, "");
+        assert_eq!(
+            pattern,
+            "((f0, f1, f2, f3, f4, f5, f6, f7, f8, f9,), (f10, f11,),)"
+        );
+    }
+
+    /// Enums without variants are an error, since no value can be picked
+    #[test]
+    fn rejects_empty_enums() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {},
+            "enums": { "Nothing": [] },
+            "routes": {}
+        }))
+        .unwrap();
+        let mut options = crate::Options::new(crate::Language::Rust);
+        options.keep_unused_types = true;
+        options.plugins.push(crate::Plugin::new("proptest"));
+
+        let error = crate::generators::generate(&schema, &options.to_args().unwrap()).unwrap_err();
+        assert!(error.contains("'Nothing'"), "{}", error);
+    }
+}
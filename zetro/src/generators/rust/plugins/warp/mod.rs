@@ -1,25 +1,64 @@
 use convert_case::{Case, Casing};
 
 use crate::{
-    common::schema::routes::{RouteKind, ZetroRoute},
-    utilities::{parse_bool, PluginCall},
+    common::schema::{
+        routes::{RouteKind, ZetroRoute},
+        ZetroSchema,
+    },
+    generators::plugins::{ArgKind, ArgSpec, Plugin, PluginArgs},
+    utilities::ZetroArgs,
 };
 
 /// Generates backend rust code to run an HTTP API.
-pub(crate) fn warp(
-    plug: &PluginCall,
-    scope: &mut Vec<String>,
-    queries: &Vec<ZetroRoute>,
-    mutations: &Vec<ZetroRoute>,
-) {
+pub(crate) struct Warp;
+
+impl Plugin for Warp {
+    fn name(&self) -> &'static str {
+        "warp"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generates a warp server: the `ZetroQueries` and `ZetroMutations` traits
+to implement, a `ZetroContext` passed to every route and
+`generate_routes` to build the filter."
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[ArgSpec {
+            name: "fnv",
+            kind: ArgKind::Bool(false),
+            description: "Store context data in an `fnv::FnvHashMap`",
+        }]
+    }
+
+    fn generate(
+        &self,
+        scope: &mut Vec<String>,
+        schema: &ZetroSchema,
+        _target: &ZetroArgs,
+        args: &PluginArgs,
+    ) -> Result<(), String> {
+        warp(args.bool("fnv"), scope, &schema.queries, &schema.mutations);
+        Ok(())
+    }
+}
+
+fn warp(
     // Whether to use fnv::FnvHashMap instead of std::collections::HashMap
     // in the Context struct
-    let use_fnv = match plug.args.get("fnv") {
-        Some(v) => parse_bool(&v.to_lowercase()).expect("`fnv` must be 'true' or 'false'"),
-        None => false,
-    };
+    use_fnv: bool,
+    scope: &mut Vec<String>,
+    queries: &[ZetroRoute],
+    mutations: &[ZetroRoute],
+) {
+    scope.push(String::from("use warp::Filter;"));
+    // Make the error struct implement warp::reject::Reject (marker trait)
+    // so warp::reject::custom() works
+    scope.push(String::from(
+        "impl warp::reject::Reject for ZetroServerError {}",
+    ));
 
-    // First add the context struct in scope...
+    // Then add the context struct in scope...
     let (ctx_struct, ctx_impl) = generate_context_struct(use_fnv);
     scope.push(ctx_struct);
     scope.push(ctx_impl);
@@ -30,11 +69,11 @@ pub(crate) fn warp(
     scope.push(error_reply_fn);
 
     // ...then generate traits for queries and mutations
-    scope.push(generate_routes_trait("ZetroQueries", &queries));
-    scope.push(generate_routes_trait("ZetroMutations", &mutations));
+    scope.push(generate_routes_trait("ZetroQueries", queries));
+    scope.push(generate_routes_trait("ZetroMutations", mutations));
 
     // ...and finally generate the routing function
-    scope.push(generate_routing_fn(&queries, &mutations));
+    scope.push(generate_routing_fn(queries, mutations));
 }
 
 /// Generates the `ZetroContext` struct and impl block that is passed into every
@@ -125,7 +164,7 @@ fn generate_reply_fns() -> (String, String) {
 }
 
 /// Generates a trait of routes which can be implemented to serve API requests.
-fn generate_routes_trait(trait_name: &str, routes: &Vec<ZetroRoute>) -> String {
+fn generate_routes_trait(trait_name: &str, routes: &[ZetroRoute]) -> String {
     let mut trait_fns: Vec<String> = Vec::new();
 
     for route in routes {
@@ -148,7 +187,7 @@ fn generate_routes_trait(trait_name: &str, routes: &Vec<ZetroRoute>) -> String {
 /// The routing function is where the meat of the work happens.
 /// That function is responsible for receiving a ZetroContext, implementations
 /// of `ZetroQueries` and `ZetroMutations`, and returning a warp route.
-fn generate_routing_fn(queries: &Vec<ZetroRoute>, mutations: &Vec<ZetroRoute>) -> String {
+fn generate_routing_fn(queries: &[ZetroRoute], mutations: &[ZetroRoute]) -> String {
     let mut query_match_arms: Vec<String> = Vec::new();
     let mut mutation_match_arms: Vec<String> = Vec::new();
 
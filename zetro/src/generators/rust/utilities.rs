@@ -37,4 +37,19 @@ impl ZetroField {
 
         kind
     }
+
+    /// Declaration of the field in a struct, eg. `pub id: u64`
+    pub(super) fn to_rust_declaration(&self) -> String {
+        format!("pub {}: {}", self.name, self.to_rust_dtype())
+    }
+}
+
+/// Formats a description as a doc comment. Every line of a multi-line
+/// description gets its own `///` prefix.
+pub(super) fn doc_comment(description: &str, indent: &str) -> String {
+    description
+        .split('\n')
+        .map(|line| format!("{}/// {}", indent, line))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
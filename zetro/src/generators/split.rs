@@ -0,0 +1,311 @@
+//! Multi-file output. Types that only one route uses go to a module named
+//! after the route, and types that several routes (or none) use go to
+//! `shared`. The index file (`mod.rs` or `index.ts`) re-exports every module,
+//! and holds Zetro's own types and the code of plugins.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::{Directions, GeneratedFile, Generator, Side, TypeUsage};
+use crate::{
+    common::schema::{enums::ZetroEnum, structs::ZetroStruct, ZetroSchema},
+    utilities::ZetroArgs,
+};
+
+/// Module of the types that are not specific to a route
+const SHARED_MODULE: &str = "shared";
+
+/// The types that go to one file
+struct Module {
+    name: String,
+    structs: Vec<ZetroStruct>,
+    enums: Vec<ZetroEnum>,
+}
+
+/// Assigns every type of `schema` to a module. Modules are in the order of
+/// routes, starting with `shared`, and types keep the order of the schema.
+fn modules(generator: &dyn Generator, schema: &ZetroSchema) -> Vec<Module> {
+    // Type name -> the routes that use it
+    let mut users: HashMap<String, Vec<&str>> = HashMap::new();
+    let routes = schema.queries.iter().chain(schema.mutations.iter());
+    for route in routes.clone() {
+        let reachable = schema.reachable_from([&route.request_body, &route.response_body]);
+        for name in reachable.structs.iter().chain(reachable.enums.iter()) {
+            users.entry(name.clone()).or_default().push(&route.name);
+        }
+    }
+    let module_of = |name: &str| match users.get(name).map(Vec::as_slice) {
+        Some([route]) => route_module(generator, route),
+        _ => generator.module_name(SHARED_MODULE),
+    };
+
+    let mut modules = vec![Module {
+        name: generator.module_name(SHARED_MODULE),
+        structs: Vec::new(),
+        enums: Vec::new(),
+    }];
+    for route in routes {
+        let name = route_module(generator, &route.name);
+        if !modules.iter().any(|m| m.name == name) {
+            modules.push(Module {
+                name,
+                structs: Vec::new(),
+                enums: Vec::new(),
+            });
+        }
+    }
+
+    let index_of =
+        |modules: &[Module], name: String| modules.iter().position(|m| m.name == name).unwrap();
+    for _struct in &schema.structs {
+        let i = index_of(&modules, module_of(&_struct.name));
+        modules[i].structs.push(_struct.clone());
+    }
+    for _enum in &schema.enums {
+        let i = index_of(&modules, module_of(&_enum.name));
+        modules[i].enums.push(_enum.clone());
+    }
+
+    modules.retain(|m| !m.structs.is_empty() || !m.enums.is_empty());
+    modules
+}
+
+/// Name of the module of the route `route`. A name that would be the index,
+/// `shared` or a reserved word gets a `_route` suffix, eg. `type_route`.
+fn route_module(generator: &dyn Generator, route: &str) -> String {
+    let name = generator.module_name(route);
+    let index = generator.index_file().split('.').next();
+    let taken = name == generator.module_name(SHARED_MODULE)
+        || Some(name.as_str()) == index
+        || generator.reserved_module_names().contains(&name.as_str());
+    match taken {
+        true => generator.module_name(&format!("{}_route", route)),
+        false => name,
+    }
+}
+
+/// The output of `emit_modules`
+pub(super) struct Modules {
+    pub files: Vec<GeneratedFile>,
+    /// Imports the code of plugins in the index needs. They go first.
+    pub imports: Vec<String>,
+    /// Makes the modules visible from the index
+    pub reexports: Vec<String>,
+}
+
+/// Emits the types of `schema` as one file per module, and the parts of the
+/// index that refer to them.
+pub(super) fn emit_modules(
+    generator: &dyn Generator,
+    schema: &ZetroSchema,
+    args: &ZetroArgs,
+    usage: &TypeUsage,
+    with_plugins: bool,
+) -> Modules {
+    let modules = modules(generator, schema);
+
+    // Type name -> module that defines it
+    let mut defined_in: HashMap<&str, &str> = HashMap::new();
+    for module in &modules {
+        for _struct in &module.structs {
+            defined_in.insert(&_struct.name, &module.name);
+        }
+        for _enum in &module.enums {
+            defined_in.insert(&_enum.name, &module.name);
+        }
+    }
+    // Import statements for the items in `references` that `module` doesn't define
+    let imports = |module: &str, references: Vec<(String, String)>| {
+        let mut imports: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (type_name, item) in references {
+            match defined_in.get(type_name.as_str()) {
+                Some(other) if *other != module => {
+                    imports.entry(other).or_default().insert(item);
+                }
+                _ => {}
+            }
+        }
+        imports
+            .into_iter()
+            .map(|(other, items)| generator.import(other, &items.into_iter().collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut files: Vec<GeneratedFile> = Vec::new();
+    for module in &modules {
+        let mut references: Vec<(String, String)> = Vec::new();
+        for _struct in &module.structs {
+            let directions = usage.struct_directions(&_struct.name);
+            for field in &_struct.fields {
+                references.extend(generator.field_references(field, directions, args.untagged));
+            }
+        }
+
+        let mut scope = generator.module_prelude(&module.structs, args, usage);
+        scope.extend(imports(&module.name, references));
+        if !module.structs.is_empty() {
+            generator.emit_structs(&mut scope, &module.structs, args.untagged, usage);
+        }
+        if !module.enums.is_empty() {
+            generator.emit_enums(&mut scope, &module.enums, usage);
+        }
+
+        files.push(GeneratedFile {
+            path: format!("{}.{}", module.name, generator.extension()),
+            contents: super::with_header(
+                args,
+                format!(
+                    "// This is an autogenerated file. DO NOT EDIT\n\n{}",
+                    scope.join("\n\n")
+                ),
+            ),
+        });
+    }
+
+    let reexports = modules
+        .iter()
+        .map(|module| generator.reexport(&module.name))
+        .collect::<Vec<_>>();
+
+    // Plugins generate code for the routes in the index. Unless re-exports
+    // are visible in the index itself, it has to import the route types.
+    let mut index_imports = Vec::new();
+    if with_plugins && !generator.reexports_in_scope() {
+        let (request, response) = match generator.side() {
+            Side::Server => (DESERIALIZE, SERIALIZE),
+            Side::Client => (SERIALIZE, DESERIALIZE),
+        };
+        let mut references: Vec<(String, String)> = Vec::new();
+        for route in schema.queries.iter().chain(schema.mutations.iter()) {
+            references.extend(generator.field_references(
+                &route.request_body,
+                request,
+                args.untagged,
+            ));
+            references.extend(generator.field_references(
+                &route.response_body,
+                response,
+                args.untagged,
+            ));
+        }
+        // The index defines no types, so everything is imported
+        index_imports = imports("", references);
+    }
+
+    Modules {
+        files,
+        imports: index_imports,
+        reexports,
+    }
+}
+
+const SERIALIZE: Directions = Directions {
+    serialize: true,
+    deserialize: false,
+};
+const DESERIALIZE: Directions = Directions {
+    serialize: false,
+    deserialize: true,
+};
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::modules;
+    use crate::{
+        common::schema::ZetroSchema,
+        generators::typescript::TypeScript,
+        options::{Language, Options},
+    };
+
+    /// Types of a single route go to its module, others to `shared`
+    #[test]
+    fn assigns_modules() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": { "description": "", "fields": { "status": "enum~Status" } },
+                "Room": { "description": "", "fields": { "messages": "[]struct~Message" } },
+                "Unused": { "description": "", "fields": {} },
+            },
+            "enums": { "Status": ["Sent"] },
+            "routes": {
+                "GetRoom": { "kind": "query", "description": "", "request": "u64", "response": "struct~Room" },
+                "SendMessage": { "kind": "mutation", "description": "", "request": "struct~Message", "response": "bool" },
+                "Ping": { "kind": "query", "description": "", "request": "u8", "response": "u8" }
+            }
+        }))
+        .unwrap();
+
+        let modules = modules(&TypeScript, &schema)
+            .into_iter()
+            .map(|m| {
+                let mut names = m.structs.into_iter().map(|s| s.name).collect::<Vec<_>>();
+                names.extend(m.enums.into_iter().map(|e| e.name));
+                (m.name, names)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                (
+                    String::from("shared"),
+                    vec![
+                        String::from("Message"),
+                        String::from("Unused"),
+                        String::from("Status")
+                    ]
+                ),
+                (String::from("getRoom"), vec![String::from("Room")]),
+            ]
+        );
+    }
+
+    /// Routes whose module would overwrite the index or `shared`, or be a
+    /// keyword, get a suffix, and modules import what they use from others
+    #[test]
+    fn emits_modules() {
+        let route = |response: &str| json!({ "kind": "query", "description": "", "request": "u8", "response": response });
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": { "description": "", "fields": { "id": "u64" } },
+                "Kind": { "description": "", "fields": { "message": "struct~Message" } },
+                "Page": { "description": "", "fields": { "messages": "[]struct~Message" } },
+            },
+            "enums": {},
+            "routes": {
+                "Type": route("struct~Kind"),
+                "Mod": route("struct~Page"),
+                "Index": route("struct~Message"),
+                "Shared": route("u8"),
+            }
+        }))
+        .unwrap();
+
+        let generate = |language| {
+            let mut args = Options::new(language).to_args().unwrap();
+            args.split = true;
+            let generated = crate::generators::generate(&schema, &args).unwrap();
+            let files = generated
+                .files
+                .into_iter()
+                .map(|f| (f.path, f.contents))
+                .collect::<Vec<_>>();
+            (generated.code, files)
+        };
+
+        let (index, files) = generate(Language::Rust);
+        let paths = files.iter().map(|(p, _)| p.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["shared.rs", "mod_route.rs", "type_route.rs"]);
+        assert!(files[2].1.contains("use super::shared::{Message};"));
+        assert!(
+            index.contains("pub mod type_route;\n#[allow(unused_imports)]\npub use type_route::*;")
+        );
+
+        let (index, files) = generate(Language::TypeScript);
+        let paths = files.iter().map(|(p, _)| p.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["shared.ts", "mod.ts", "type.ts"]);
+        assert!(index.contains("export * from \"./mod\";"));
+        // The route named `Index` uses only shared types
+        assert!(!index.contains("indexRoute"));
+    }
+}
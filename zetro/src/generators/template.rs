@@ -0,0 +1,140 @@
+//! Template mode: a target with a `template` renders that file instead of
+//! generating the types itself. Templates use Jinja syntax (see
+//! `docs/templates.md`) and get the same schema IR as external plugins.
+
+use convert_case::{Case, Casing};
+use minijinja::{Environment, Error, ErrorKind, UndefinedBehavior, Value};
+
+use super::{external, generator};
+use crate::{
+    common::schema::{
+        fields::{FieldKind, ZetroField},
+        routes::ZetroRoute,
+        structs::ZetroStruct,
+        ZetroSchema,
+    },
+    utilities::{EmitLang, ZetroArgs},
+};
+
+/// Renders the template at `path`. `schema` is renamed and pruned for the
+/// target, like for plugins.
+pub(crate) fn render(path: &str, schema: &ZetroSchema, args: &ZetroArgs) -> Result<String, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("{}: error reading template: {}", path, e))?;
+
+    let context = serde_json::json!({
+        "schema": schema.to_target_ir(&|field| generator(args.language).type_name(field)),
+        "target": external::target_ir(args),
+    });
+    environment()
+        .render_named_str(path, &source, context)
+        .map_err(|e| e.to_string())
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    // Misspelled names fail instead of rendering as nothing
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_keep_trailing_newline(true);
+
+    env.add_filter("snake_case", |s: &str| s.to_case(Case::Snake));
+    env.add_filter("camel_case", |s: &str| s.to_case(Case::Camel));
+    env.add_filter("pascal_case", |s: &str| s.to_case(Case::Pascal));
+    env.add_filter("upper_snake_case", |s: &str| s.to_case(Case::UpperSnake));
+    env.add_filter("kebab_case", |s: &str| s.to_case(Case::Kebab));
+
+    env.add_filter("to_rust_dtype", |field: Value| {
+        Ok(generator(EmitLang::Rust).type_name(&field_from_ir(&field)?))
+    });
+    env.add_filter("to_ts_dtype", |field: Value| {
+        Ok(generator(EmitLang::Typescript).type_name(&field_from_ir(&field)?))
+    });
+    env.add_filter("encrypt_route_name", |name: &str| {
+        ZetroRoute::encrypt_name(name)
+    });
+    env
+}
+
+/// Reads back a field of the IR, for the type mapping filters
+fn field_from_ir(value: &Value) -> Result<ZetroField, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            "expected a field of the schema IR",
+        )
+    };
+    let ir = serde_json::to_value(value).map_err(|_| invalid())?;
+    let text = |v: &serde_json::Value| v.as_str().map(String::from).ok_or_else(invalid);
+
+    let kind = &ir["type"];
+    let kind = match kind["kind"].as_str() {
+        Some("primitive") => kind["name"]
+            .as_str()
+            .and_then(FieldKind::from_primitive_name)
+            .ok_or_else(invalid)?,
+        Some("struct") => FieldKind::StructValue(text(&kind["name"])?),
+        Some("enum") => FieldKind::EnumValue(text(&kind["name"])?),
+        // Only the name of a nested object is part of its type
+        Some("object") => FieldKind::NestedObject(ZetroStruct {
+            name: text(&kind["struct"]["name"])?,
+            description: String::new(),
+            is_nullable: false,
+            is_multiple: false,
+            lint_allow: Vec::new(),
+            fields: Vec::new(),
+        }),
+        _ => return Err(invalid()),
+    };
+
+    Ok(ZetroField {
+        name: text(&ir["name"])?,
+        description: None,
+        kind,
+        is_nullable: ir["nullable"].as_bool().ok_or_else(invalid)?,
+        is_multiple: ir["list"].as_bool().ok_or_else(invalid)?,
+        is_recursive: ir["recursive"].as_bool().ok_or_else(invalid)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::environment;
+    use crate::common::schema::ZetroSchema;
+
+    /// Filters read fields of the IR and names
+    #[test]
+    fn renders_helpers() {
+        let schema = ZetroSchema::from_value(&json!({
+            "structs": {
+                "Room": {
+                    "description": "",
+                    "fields": { "members": "?[]struct~Room", "room_name": "string" }
+                }
+            },
+            "enums": {},
+            "routes": {
+                "GetRoom": { "kind": "query", "description": "", "request": "u8", "response": "struct~Room" }
+            }
+        }))
+        .unwrap();
+        let context = json!({ "schema": schema.to_ir() });
+        let render = |source: &str| environment().render_str(source, &context);
+
+        let fields =
+            "{% for f in schema.structs[0].fields %}{{ f.position }} {{ f.name|pascal_case }}: \
+            {{ f|to_rust_dtype }} / {{ f|to_ts_dtype }}\n{% endfor %}";
+        assert_eq!(
+            render(fields).unwrap(),
+            "0 Members: Option<Vec<Box<Room>>> / Room[]\n1 RoomName: String / string\n"
+        );
+
+        let route = "{% set r = schema.queries[0] %}{{ r.name|snake_case }} \
+            {{ r.name|encrypt_route_name == r.wireName }}";
+        assert_eq!(render(route).unwrap(), "get_room True");
+
+        assert!(render("{{ schema.strucs }}").is_err());
+        assert!(render("{{ schema.queries[0]|to_rust_dtype }}").is_err());
+    }
+}
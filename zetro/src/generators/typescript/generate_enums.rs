@@ -5,10 +5,10 @@ use crate::common::schema::enums::ZetroEnum;
 /// Note that the generated "enum" is simply a read-only javascript object.
 /// This is because **typescript compiles enums into javascript functions**,
 /// which poses minification as well as performance problems.
-pub(super) fn generate_enums(scope: &mut Vec<String>, enums: &Vec<ZetroEnum>) {
+pub(super) fn generate_enums(scope: &mut Vec<String>, enums: &[ZetroEnum]) {
     scope.push(String::from("/* ============ Enums ============ */"));
     for _enum in enums {
-        scope.push(generate_enum(&_enum));
+        scope.push(generate_enum(_enum));
     }
     scope.push(String::from("/* ============ End Enums ============ */"));
 }
@@ -17,8 +17,9 @@ pub(super) fn generate_enum(_enum: &ZetroEnum) -> String {
     // A list of enum variants in property signature format
     let mut enum_variants: Vec<String> = Vec::new();
 
+    // Variants are numbered from 0, like the discriminants of Rust enums
     for (i, variant) in _enum.variants.iter().enumerate() {
-        enum_variants.push(format!("\t{}: {}", variant, i + 1));
+        enum_variants.push(format!("\t{}: {}", variant, i));
     }
 
     let enum_block = format!(
@@ -1,18 +1,26 @@
-use crate::common::schema::{fields::FieldKind, structs::ZetroStruct};
+use crate::{
+    common::schema::{fields::FieldKind, structs::ZetroStruct},
+    generators::TypeUsage,
+};
 
 /// Generates typescript interfaces and [de]serialization functions for each
-/// struct.
+/// struct. (De)serialization functions are only generated when `usage` says
+/// they are needed.
 pub(super) fn generate_structs(
     scope: &mut Vec<String>,
-    structs: &Vec<ZetroStruct>,
+    structs: &[ZetroStruct],
     untagged_repr: bool,
+    usage: &TypeUsage,
 ) {
     scope.push(String::from("/* ============ Structs ============ */"));
     for _struct in structs {
-        scope.extend(generate_interface(&_struct, true));
-        if untagged_repr {
-            scope.extend(generate_untagged_serializer(&_struct, true));
-            scope.extend(generate_untagged_deserializer(&_struct, true));
+        let directions = usage.struct_directions(&_struct.name);
+        scope.extend(generate_interface(_struct, true));
+        if untagged_repr && directions.serialize {
+            scope.extend(generate_untagged_serializer(_struct, true));
+        }
+        if untagged_repr && directions.deserialize {
+            scope.extend(generate_untagged_deserializer(_struct, true));
         }
     }
     scope.push(String::from("/* ============ End Structs ============ */"));
@@ -34,12 +42,7 @@ pub(super) fn generate_interface(_struct: &ZetroStruct, exported: bool) -> Vec<S
         if let Some(description) = &field.description {
             interface_fields.push(format!("\t/** {} */", description));
         }
-        interface_fields.push(format!(
-            "\t{}{}: {},",
-            field.name,
-            if field.is_nullable { "?" } else { "" },
-            field.to_ts_dtype(),
-        ));
+        interface_fields.push(format!("\t{},", field.to_ts_declaration()));
     }
 
     let interface = format!(
@@ -81,6 +84,12 @@ pub(super) fn generate_untagged_serializer(_struct: &ZetroStruct, exported: bool
                         if field.is_nullable { "?" } else { "" },
                         struct_name,
                     ));
+                } else if field.is_nullable {
+                    // The serializer doesn't take null
+                    ret_array_elems.push(format!(
+                        "\t\tobj.{0} == null ? null : serialize{1}(obj.{0})",
+                        field.name, struct_name
+                    ));
                 } else {
                     // Access property directly
                     ret_array_elems
@@ -1,158 +1,145 @@
-use crate::common::schema::{
-    fields::FieldKind, fields::ZetroField, structs::ZetroStruct, ZetroSchema,
-};
 use convert_case::{Case, Casing};
 
+use crate::{
+    common::schema::{
+        enums::ZetroEnum,
+        fields::{FieldKind, ZetroField},
+        structs::ZetroStruct,
+        ZetroSchema,
+    },
+    generators::{plugins::Plugin, server_error_struct, Directions, Generator, Side, TypeUsage},
+    utilities::{EmitLang, ZetroArgs},
+};
+
 mod generate_enums;
 mod generate_structs;
 mod plugins;
 mod utilities;
 
-/// Generates schema [de/ser]ialization code for TypeScript
-pub(crate) fn generate_schema_code(
-    mut schema: ZetroSchema,
-    args: &crate::utilities::ZetroArgs,
-) -> Result<String, String> {
-    let mut scope: Vec<String> = Vec::new();
-    let should_mangle = args.mangle.unwrap_or(false);
-
-    tsify_schema(&mut schema, &args.field_casing, should_mangle);
-    generate_zetro_specific(&mut scope, should_mangle);
-
-    generate_structs::generate_structs(&mut scope, &schema.structs, args.untagged);
-    generate_enums::generate_enums(&mut scope, &schema.enums);
-
-    for plugin in &args.plugins {
-        match plugin.name.as_str() {
-            "class-client" => {
-                plugins::class_client::class_client(
-                    &mut scope,
-                    &schema.queries,
-                    &schema.mutations,
-                    args.untagged,
-                    should_mangle,
-                );
-            }
-            _ => return Err(format!("Unrecognized plugin: '{}'", plugin.name)),
-        }
+/// Generates schema [de/ser]ialization code for TypeScript clients.
+/// - Structs and enums are PascalCased
+/// - Enum variants are UPPER_SNAKE_CASED
+/// - Field names are camelCased, unless the target sets a field casing
+///
+/// With `mangle`, an underscore is appended to field and variant names.
+pub(crate) struct TypeScript;
+
+impl Generator for TypeScript {
+    fn language(&self) -> EmitLang {
+        EmitLang::Typescript
     }
 
-    return Ok(format!(
-        "// This is an autogenerated file. DO NOT EDIT\n\n{}",
-        scope.join("\n\n")
-    ));
-}
+    fn name(&self) -> &'static str {
+        "TypeScript"
+    }
 
-/// "TypeScript-ify" the property names in schema:
-/// - Structs will be correctly cased
-/// - Enums will be UpperSnaked and an underscore will be appended
-/// - Field names will be camelCased and an underscore will be appended
-fn tsify_schema(schema: &mut ZetroSchema, field_casing: &Option<Case>, should_mangle: bool) {
-    let field_casing = match field_casing {
-        Some(v) => v.clone(),
-        None => Case::Camel,
-    };
-
-    // Structs
-    for _struct in &mut schema.structs {
-        // Notice that we only change case of top-level structs.
-        // This is because nested structs will be of the type
-        // {ParentStructName}_{fieldName} and we don't want to undo that
-        _struct.name = _struct.name.to_case(Case::Pascal);
-        tsify_struct(_struct, field_casing, should_mangle);
-    }
-    // Enums
-    for _enum in &mut schema.enums {
-        _enum.name = _enum.name.to_case(Case::Pascal);
-        for variant in &mut _enum.variants {
-            *variant = format!(
-                "{}{}",
-                variant.to_case(Case::UpperSnake),
-                if should_mangle { "_" } else { "" }
-            );
-        }
+    // Clients only serialize requests and deserialize responses
+    fn side(&self) -> Side {
+        Side::Client
+    }
+
+    fn plugins(&self) -> &'static [&'static dyn Plugin] {
+        &[&plugins::class_client::ClassClient]
     }
 
-    let process_field = |field: &mut ZetroField| tsify_field(field, field_casing, should_mangle);
+    fn field_name(&self, name: &str, args: &ZetroArgs) -> String {
+        let casing = args.field_casing.unwrap_or(Case::Camel);
+        format!("{}{}", name.to_case(casing), mangle_suffix(args))
+    }
 
-    // Routes. Notice we don't alter route names.
-    // This is because the route name is used to identify a particular route,
-    // and changing its case would invalidate references to it in other
-    // languages.
-    // Queries
-    for route in &mut schema.queries {
-        process_field(&mut route.request_body);
-        process_field(&mut route.response_body);
+    fn variant_name(&self, name: &str, args: &ZetroArgs) -> String {
+        format!("{}{}", name.to_case(Case::UpperSnake), mangle_suffix(args))
     }
-    // Mutations
-    for route in &mut schema.mutations {
-        process_field(&mut route.request_body);
-        process_field(&mut route.response_body);
+
+    fn type_name(&self, field: &ZetroField) -> String {
+        field.to_ts_dtype()
     }
-}
 
-fn tsify_struct(_struct: &mut ZetroStruct, casing: Case, should_mangle: bool) {
-    for field in &mut _struct.fields {
-        tsify_field(field, casing, should_mangle);
+    fn field_declaration(&self, field: &ZetroField) -> String {
+        field.to_ts_declaration()
     }
-}
 
-fn tsify_field(field: &mut ZetroField, casing: Case, should_mangle: bool) {
-    field.name = format!(
-        "{}{}",
-        field.name.to_case(casing),
-        if should_mangle { "_" } else { "" }
-    );
-
-    // Also rename the identifiers in field
-    match &mut field.kind {
-        FieldKind::StructValue(struct_name) => {
-            *struct_name = struct_name.to_case(Case::Pascal);
-        }
-        FieldKind::EnumValue(enum_name) => {
-            *enum_name = enum_name.to_case(Case::Pascal);
-        }
-        FieldKind::NestedObject(nested) => {
-            tsify_struct(nested, casing, should_mangle);
-        }
-        _ => {
-            // Primitives have nothing (inside them) to rename.
+    fn prelude(&self, _schema: &ZetroSchema, args: &ZetroArgs, _usage: &TypeUsage) -> Vec<String> {
+        let error = server_error_struct(
+            self,
+            args,
+            "AUTOGENERATED\nThe struct that serializes error states",
+        );
+        vec![generate_structs::generate_interface(&error, true)
+            .pop()
+            .unwrap()]
+    }
+
+    fn emit_structs(
+        &self,
+        scope: &mut Vec<String>,
+        structs: &[ZetroStruct],
+        untagged: bool,
+        usage: &TypeUsage,
+    ) {
+        generate_structs::generate_structs(scope, structs, untagged, usage);
+    }
+
+    fn emit_enums(&self, scope: &mut Vec<String>, enums: &[ZetroEnum], _usage: &TypeUsage) {
+        generate_enums::generate_enums(scope, enums);
+    }
+
+    fn extension(&self) -> &'static str {
+        "ts"
+    }
+
+    fn index_file(&self) -> &'static str {
+        "index.ts"
+    }
+
+    fn module_name(&self, name: &str) -> String {
+        name.to_case(Case::Camel)
+    }
+
+    fn import(&self, module: &str, items: &[String]) -> String {
+        format!("import {{ {} }} from \"./{}\";", items.join(", "), module)
+    }
+
+    fn reexport(&self, module: &str) -> String {
+        format!("export * from \"./{}\";", module)
+    }
+
+    fn reexports_in_scope(&self) -> bool {
+        false
+    }
+
+    // Enums are plain numbers in interfaces, so only structs are referenced
+    fn field_references(
+        &self,
+        field: &ZetroField,
+        directions: Directions,
+        untagged: bool,
+    ) -> Vec<(String, String)> {
+        match &field.kind {
+            FieldKind::StructValue(name) => {
+                let mut items = vec![(name.clone(), name.clone())];
+                if untagged && directions.serialize {
+                    items.push((name.clone(), format!("serialize{}", name)));
+                }
+                if untagged && directions.deserialize {
+                    items.push((name.clone(), format!("deserialize{}", name)));
+                }
+                items
+            }
+            FieldKind::NestedObject(nested) => nested
+                .fields
+                .iter()
+                .flat_map(|field| self.field_references(field, directions, untagged))
+                .collect(),
+            _ => Vec::new(),
         }
     }
 }
 
-/// Generate some zetro-specific structs such as ZetroServerError
-fn generate_zetro_specific(scope: &mut Vec<String>, should_mangle: bool) {
-    // ZetroServerError
-    let zetro_server_error = ZetroStruct {
-        name: String::from("ZetroServerError"),
-        description: String::from("AUTOGENERATED\nThe struct that serializes error states"),
-        is_nullable: false,
-        is_multiple: false,
-        fields: vec![
-            ZetroField {
-                description: Some(String::from(
-                    "Arbitrary, human-readable message for the end user",
-                )),
-                kind: FieldKind::StringValue,
-                name: format!("message{}", if should_mangle { "_" } else { "" }),
-                is_multiple: false,
-                is_nullable: false,
-                is_recursive: false,
-            },
-            ZetroField {
-                description: Some(String::from("Arbitrary, response code for frontend logic")),
-                kind: FieldKind::Int16,
-                name: format!("code{}", if should_mangle { "_" } else { "" }),
-                is_multiple: false,
-                is_nullable: false,
-                is_recursive: false,
-            },
-        ],
-    };
-    scope.push(
-        generate_structs::generate_interface(&zetro_server_error, true)
-            .pop()
-            .unwrap(),
-    );
+fn mangle_suffix(args: &ZetroArgs) -> &'static str {
+    if args.mangle.unwrap_or(false) {
+        "_"
+    } else {
+        ""
+    }
 }
@@ -1,15 +1,50 @@
 use convert_case::{Case, Casing};
 
-use crate::common::schema::{
-    fields::FieldKind,
-    routes::{RouteKind, ZetroRoute},
+use crate::{
+    common::schema::{
+        fields::FieldKind,
+        routes::{RouteKind, ZetroRoute},
+        ZetroSchema,
+    },
+    generators::plugins::{Plugin, PluginArgs},
+    utilities::ZetroArgs,
 };
 
 /// Generates frontend typescript code to query a server using the Zetro spec.
-pub(crate) fn class_client(
+pub(crate) struct ClassClient;
+
+impl Plugin for ClassClient {
+    fn name(&self) -> &'static str {
+        "class-client"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generates the `ZetroQuery` and `ZetroMutation` classes, with one method
+per route. Requests are sent through an `IZetroClient` you implement."
+    }
+
+    fn generate(
+        &self,
+        scope: &mut Vec<String>,
+        schema: &ZetroSchema,
+        target: &ZetroArgs,
+        _args: &PluginArgs,
+    ) -> Result<(), String> {
+        class_client(
+            scope,
+            &schema.queries,
+            &schema.mutations,
+            target.untagged,
+            target.mangle.unwrap_or(false),
+        );
+        Ok(())
+    }
+}
+
+fn class_client(
     scope: &mut Vec<String>,
-    queries: &Vec<ZetroRoute>,
-    mutations: &Vec<ZetroRoute>,
+    queries: &[ZetroRoute],
+    mutations: &[ZetroRoute],
     untagged_repr: bool,
     should_mangle: bool,
 ) {
@@ -19,7 +54,7 @@ pub(crate) fn class_client(
     scope.push(generate_client_class(
         "ZetroQuery",
         RouteKind::Query.to_method_code(),
-        &queries,
+        queries,
         untagged_repr,
         should_mangle,
     ));
@@ -29,7 +64,7 @@ pub(crate) fn class_client(
     scope.push(generate_client_class(
         "ZetroMutation",
         RouteKind::Mutation.to_method_code(),
-        &mutations,
+        mutations,
         untagged_repr,
         should_mangle,
     ));
@@ -61,7 +96,7 @@ export interface IZetroClient {{
 pub(super) fn generate_client_class(
     name: &str,
     method_code: u8,
-    routes: &Vec<ZetroRoute>,
+    routes: &[ZetroRoute],
     untagged_repr: bool,
     should_mangle: bool,
 ) -> String {
@@ -97,6 +132,11 @@ pub(super) fn generate_client_class(
                         },
                         struct_name,
                     )
+                    } else if route.request_body.is_nullable {
+                        format!(
+                            "requestBody == null ? null : serialize{}(requestBody)",
+                            struct_name
+                        )
                     } else {
                         format!("serialize{}(requestBody)", struct_name)
                     }
@@ -124,7 +164,7 @@ pub(super) fn generate_client_class(
         let response_body_expr = if untagged_repr {
             match &route.response_body.kind {
                 FieldKind::StructValue(struct_name) => {
-                    if route.request_body.is_multiple {
+                    if route.response_body.is_multiple {
                         format!(
                             "item[1]{}.map(function (elem: any) {{ return deserialize{}(elem); }})",
                             if route.response_body.is_nullable {
@@ -139,7 +179,7 @@ pub(super) fn generate_client_class(
                     }
                 }
                 FieldKind::NestedObject(s) => {
-                    if route.request_body.is_multiple {
+                    if route.response_body.is_multiple {
                         format!(
                             "item[1]{}.map(function (elem: any) {{ return deserialize{}(elem); }})",
                             if route.response_body.is_nullable {
@@ -36,4 +36,14 @@ impl ZetroField {
 
         kind
     }
+
+    /// Declaration of the field in an interface, eg. `id?: number`
+    pub(super) fn to_ts_declaration(&self) -> String {
+        format!(
+            "{}{}: {}",
+            self.name,
+            if self.is_nullable { "?" } else { "" },
+            self.to_ts_dtype()
+        )
+    }
 }
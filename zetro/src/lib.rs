@@ -0,0 +1,171 @@
+//! Zetro generates typed, compact APIs from a schema file.
+//!
+//! This crate is both the `zetro` command line tool and a library. The library
+//! loads and validates schemas, and runs generators without touching the file
+//! system:
+//!
+//! ```
+//! let schema = zetro::parse_schema(
+//!     r#"{
+//!         "structs": {},
+//!         "enums": {},
+//!         "routes": {
+//!             "Ping": { "kind": "query", "description": "Ping", "request": "u8", "response": "u8" }
+//!         }
+//!     }"#,
+//!     zetro::SchemaFormat::Json,
+//! )
+//! .unwrap();
+//!
+//! let mut options = zetro::Options::new(zetro::Language::Rust);
+//! options.untagged = true;
+//! options.plugins.push(zetro::Plugin::new("warp").arg("fnv", true));
+//!
+//! let code = zetro::generate(&schema, &options).unwrap();
+//! assert!(code.contains("pub trait ZetroQueries"));
+//! ```
+//!
+//! To generate code from a `build.rs` instead of committing it, see the
+//! `zetro-build` crate.
+//!
+//! The command line tool is behind the default `cli` feature. Libraries and
+//! build scripts can turn off default features to leave out its dependencies.
+//! Rendering templates needs the `templates` feature, which `cli` turns on.
+
+// Much of the shared code only serves the command line tool
+#![cfg_attr(not(feature = "cli"), allow(dead_code, unused_imports))]
+
+#[cfg(feature = "cli")]
+mod commands;
+mod common;
+mod generators;
+#[cfg(feature = "cli")]
+mod lint;
+#[cfg(feature = "cli")]
+mod lsp;
+mod options;
+mod project;
+mod utilities;
+
+pub use common::fake::{FakeError, FakeOptions};
+pub use common::source::{LoadError, SchemaFormat, SourceError};
+pub use options::{FieldCasing, Language, Options, Plugin};
+pub use utilities::PluginArg;
+
+/// The validated schema, as generators see it
+pub mod schema {
+    pub use crate::common::schema::{
+        enums::ZetroEnum,
+        errors::{ErrorKind, Offender, SchemaError},
+        fields::{FieldKind, ZetroField},
+        routes::{RouteKind, ZetroRoute},
+        structs::ZetroStruct,
+        ReachableTypes, ZetroSchema,
+    };
+}
+
+pub use schema::ZetroSchema;
+
+/// Expands a schema into Rust code at compile time:
+///
+/// ```ignore
+/// #[allow(dead_code)]
+/// mod api {
+///     zetro::schema!("schema.json", plugins = [warp(fnv = true)], untagged = true);
+/// }
+/// ```
+///
+/// This forwards to `zetro_macros::schema!`, where the options are listed, so
+/// the crate using it has to depend on `zetro-macros` too. `zetro-macros`
+/// runs the generators of this crate, so this crate can't depend on it.
+#[macro_export]
+macro_rules! schema {
+    ($($input:tt)*) => {
+        ::zetro_macros::schema!($($input)*);
+    };
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The schema could not be read, decoded or validated
+    Schema(LoadError),
+    /// The options are invalid, eg. an unknown plugin
+    Generation(String),
+    /// Fake values could not be generated, see `fake_structs`
+    Fake(FakeError),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Schema(e) => write!(f, "{}", e),
+            Error::Generation(e) => write!(f, "error generating code: {}", e),
+            Error::Fake(e) => write!(f, "error generating fake values: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Reads and validates a schema file. The format is picked from the extension
+/// of `path`, like `zetro --schema` does.
+pub fn load_schema(path: impl AsRef<std::path::Path>) -> Result<ZetroSchema, Error> {
+    common::source::read_schema(&path.as_ref().to_string_lossy()).map_err(Error::Schema)
+}
+
+/// Parses and validates the contents of a schema file
+pub fn parse_schema(contents: &str, format: SchemaFormat) -> Result<ZetroSchema, Error> {
+    common::source::parse_schema(contents, format, "<schema>").map_err(Error::Schema)
+}
+
+/// Generates code for `schema`. External plugins may only return code
+/// fragments here, since there is no directory to write their files to.
+pub fn generate(schema: &ZetroSchema, options: &Options) -> Result<String, Error> {
+    let args = options.to_args().map_err(Error::Generation)?;
+    let generated = generators::generate(schema, &args).map_err(Error::Generation)?;
+    if let Some(file) = generated.files.first() {
+        return Err(Error::Generation(format!(
+            "a plugin generated the file '{}'. Extra files are only supported by the zetro CLI",
+            file.path
+        )));
+    }
+    Ok(generated.code)
+}
+
+/// Generates `count` valid instances of the struct `name` with fake data, eg.
+/// for fixtures or property tests. Structs are encoded as on the wire: arrays
+/// when `untagged`, objects otherwise. The same options give the same values.
+///
+/// ```
+/// let schema = zetro::parse_schema(
+///     r#"{
+///         "structs": { "Room": { "description": "A room", "fields": { "id": "u64", "name": "string" } } },
+///         "enums": {},
+///         "routes": {}
+///     }"#,
+///     zetro::SchemaFormat::Json,
+/// )
+/// .unwrap();
+///
+/// let options = zetro::FakeOptions { seed: 42, ..Default::default() };
+/// let rooms = zetro::fake_structs(&schema, "Room", 3, false, &options).unwrap();
+/// assert!(rooms[0]["name"].is_string());
+/// assert_eq!(rooms, zetro::fake_structs(&schema, "Room", 3, false, &options).unwrap());
+/// ```
+pub fn fake_structs(
+    schema: &ZetroSchema,
+    name: &str,
+    count: usize,
+    untagged: bool,
+    options: &FakeOptions,
+) -> Result<Vec<serde_json::Value>, Error> {
+    common::fake::fake_structs(schema, name, count, untagged, options).map_err(Error::Fake)
+}
+
+/// Entry point of the `zetro` binary. Takes the arguments without the program
+/// name and returns the exit code. Not part of the library API.
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+pub fn run_cli(args: &[String]) -> u8 {
+    commands::run(args)
+}
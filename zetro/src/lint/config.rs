@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use super::{Level, Rule};
+
+/// Name of the config file looked up next to the schema
+pub(crate) const CONFIG_FILE_NAME: &str = "zetro-lint.toml";
+
+/// Lint settings. Read from a TOML file like this:
+///
+/// ```toml
+/// [rules]
+/// unused-type = "error"
+/// missing-field-description = "off"
+///
+/// [limits]
+/// max-enum-variants = 32
+/// max-mutation-response-fields = 16
+/// ```
+#[derive(Debug)]
+pub(crate) struct LintConfig {
+    levels: HashMap<Rule, Level>,
+    pub max_enum_variants: usize,
+    pub max_mutation_response_fields: usize,
+}
+
+#[derive(serde_derive::Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    rules: HashMap<String, Level>,
+    limits: RawLimits,
+}
+
+#[derive(serde_derive::Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct RawLimits {
+    max_enum_variants: Option<usize>,
+    max_mutation_response_fields: Option<usize>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            levels: HashMap::new(),
+            max_enum_variants: 64,
+            max_mutation_response_fields: 32,
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        let raw = toml::from_str::<RawConfig>(contents).map_err(|e| e.to_string())?;
+        let mut config = Self::default();
+
+        for (name, level) in raw.rules {
+            match Rule::from_name(&name) {
+                Some(rule) => {
+                    config.levels.insert(rule, level);
+                }
+                None => return Err(format!("Unknown lint rule '{}'", name)),
+            }
+        }
+        if let Some(v) = raw.limits.max_enum_variants {
+            config.max_enum_variants = v;
+        }
+        if let Some(v) = raw.limits.max_mutation_response_fields {
+            config.max_mutation_response_fields = v;
+        }
+
+        Ok(config)
+    }
+
+    pub fn read(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: error reading file: {}", path, e))?;
+        Self::from_toml(&contents).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn level(&self, rule: Rule) -> Level {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level, LintConfig, Rule};
+
+    #[test]
+    fn parses_levels_and_limits() {
+        let config = LintConfig::from_toml(
+            "[rules]\nunused-type = \"error\"\nlarge-enum = \"off\"\n\n[limits]\nmax-enum-variants = 3\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.level(Rule::UnusedType), Level::Error);
+        assert_eq!(config.level(Rule::LargeEnum), Level::Off);
+        assert_eq!(config.level(Rule::FieldNameCase), Level::Warn);
+        assert_eq!(config.max_enum_variants, 3);
+        assert_eq!(config.max_mutation_response_fields, 32);
+
+        assert!(LintConfig::from_toml("[rules]\nno-such-rule = \"warn\"").is_err());
+        assert!(LintConfig::from_toml("[rules]\nunused-type = \"loud\"").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! Schema linter.
+//!
+//! Unlike the checks in `ZetroSchema::from_value`, lint rules flag schemas
+//! that work but are probably not what the author wanted. Every rule can be
+//! turned off, or turned into an error, in a config file (see `config`), and
+//! suppressed for a single struct, enum or route with an `allow` list.
+
+use self::config::LintConfig;
+use crate::{
+    common::{
+        schema::ZetroSchema,
+        source::{self, SchemaFormat},
+    },
+    lsp::index::Index,
+};
+
+pub(crate) mod config;
+mod rules;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Rule {
+    /// A struct or enum is not reachable from any route
+    UnusedType,
+    /// A field has no description
+    MissingFieldDescription,
+    /// A struct name is not PascalCase
+    StructNameCase,
+    /// A field name is not camelCase
+    FieldNameCase,
+    /// An enum has more variants than `limits.max-enum-variants`
+    LargeEnum,
+    /// A mutation responds with a list of objects, a recursive type or more
+    /// than `limits.max-mutation-response-fields` fields
+    LargeMutationResponse,
+    /// An `allow` list names a rule that doesn't exist
+    UnknownAllow,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 7] = [
+        Rule::UnusedType,
+        Rule::MissingFieldDescription,
+        Rule::StructNameCase,
+        Rule::FieldNameCase,
+        Rule::LargeEnum,
+        Rule::LargeMutationResponse,
+        Rule::UnknownAllow,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::UnusedType => "unused-type",
+            Rule::MissingFieldDescription => "missing-field-description",
+            Rule::StructNameCase => "struct-name-case",
+            Rule::FieldNameCase => "field-name-case",
+            Rule::LargeEnum => "large-enum",
+            Rule::LargeMutationResponse => "large-mutation-response",
+            Rule::UnknownAllow => "unknown-allow",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.name() == name)
+    }
+
+    /// Level used when the config file doesn't mention the rule
+    pub fn default_level(&self) -> Level {
+        match self {
+            Rule::UnknownAllow => Level::Error,
+            _ => Level::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde_derive::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Level {
+    Off,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Warn => "warning",
+            Level::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub rule: Rule,
+    pub level: Level,
+    pub message: String,
+    /// Dotted path to the offending item, eg. `structs.Message.fields.id`
+    pub path: String,
+    /// One-based line and column of the offending item, once `locate` found
+    /// it in the schema text
+    pub position: Option<(usize, usize)>,
+}
+
+/// Runs every enabled rule on `schema`
+pub(crate) fn lint(schema: &ZetroSchema, config: &LintConfig) -> Vec<Diagnostic> {
+    rules::Linter::new(schema, config).run()
+}
+
+/// Finds where the item of each diagnostic is in the schema `text`. Items
+/// without a key of their own, eg. an `allow` list in a `.zetro` file, point
+/// at their parent.
+pub(crate) fn locate(diagnostics: &mut [Diagnostic], text: &str, format: SchemaFormat) {
+    let index = match Index::parse(text, format) {
+        Some(index) => index,
+        None => return,
+    };
+    for d in diagnostics {
+        let mut path = d.path.as_str();
+        let span = loop {
+            if let Some(span) = index.keys.get(path) {
+                break Some(span);
+            }
+            match path.rsplit_once('.') {
+                Some((parent, _)) => path = parent,
+                None => break None,
+            }
+        };
+        d.position = span.map(|span| source::offset_to_position(text, span.start));
+    }
+}
+
+/// Renders diagnostics for a terminal. `file` is only used for display.
+pub(crate) fn format_human(diagnostics: &[Diagnostic], file: &str) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        let location = match d.position {
+            Some((line, column)) => format!("{}:{}:{}", file, line, column),
+            None => file.to_owned(),
+        };
+        out.push_str(&format!(
+            "{}[{}]: {}\n --> {}: {}\n",
+            d.level.name(),
+            d.rule.name(),
+            d.message,
+            location,
+            d.path
+        ));
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.level == Level::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if !diagnostics.is_empty() {
+        out.push_str(&format!("\n{} warning(s), {} error(s)\n", warnings, errors));
+    }
+    out
+}
+
+/// Renders diagnostics as a JSON array, for CI annotations and other tools
+pub(crate) fn format_json(diagnostics: &[Diagnostic], file: &str) -> String {
+    let diagnostics = diagnostics
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "rule": d.rule.name(),
+                "level": d.level.name(),
+                "message": d.message,
+                "file": file,
+                "line": d.position.map(|(line, _)| line),
+                "column": d.position.map(|(_, column)| column),
+                "path": d.path,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&diagnostics).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{config::LintConfig, format_json, lint, locate};
+    use crate::common::source::{self, SchemaFormat};
+
+    /// Findings point at the line and column of their item, in every format
+    #[test]
+    fn locates_findings() {
+        let json = r#"{
+  "structs": {
+    "Message": {
+      "description": "A message",
+      "fields": {
+        "id": "u64; Unique id",
+        "author_name": "string; Who wrote it"
+      }
+    }
+  },
+  "enums": {},
+  "routes": {
+    "GetMessage": { "kind": "query", "description": "", "request": "u64", "response": "struct~Message" }
+  }
+}
+"#;
+        let idl = "/// A message
+struct Message {
+    /// Unique id
+    id: u64
+    /// Who wrote it
+    author_name: string
+}
+
+/// Gets a message
+query GetMessage {
+    request: u64
+    response: Message
+}
+";
+        for (text, format, position) in [
+            (json, SchemaFormat::Json, (7, 9)),
+            (idl, SchemaFormat::Zetro, (6, 5)),
+        ] {
+            let schema = source::parse_schema(text, format, "schema").unwrap();
+            let mut diagnostics = lint(&schema, &LintConfig::default());
+            locate(&mut diagnostics, text, format);
+
+            assert_eq!(diagnostics[0].path, "structs.Message.fields.author_name");
+            assert_eq!(diagnostics[0].position, Some(position));
+            let output: serde_json::Value =
+                serde_json::from_str(&format_json(&diagnostics, "schema")).unwrap();
+            assert_eq!(output[0]["line"], position.0);
+            assert_eq!(output[0]["column"], position.1);
+        }
+    }
+}
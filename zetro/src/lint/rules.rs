@@ -0,0 +1,332 @@
+use super::{config::LintConfig, Diagnostic, Level, Rule};
+use crate::common::schema::{
+    fields::{FieldKind, ZetroField},
+    structs::ZetroStruct,
+    ZetroSchema,
+};
+
+pub(super) struct Linter<'a> {
+    schema: &'a ZetroSchema,
+    config: &'a LintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(schema: &'a ZetroSchema, config: &'a LintConfig) -> Self {
+        Self {
+            schema,
+            config,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn run(mut self) -> Vec<Diagnostic> {
+        let schema = self.schema;
+        let reachable = schema.reachable_types();
+
+        for _struct in &schema.structs {
+            let path = format!("structs.{}", _struct.name);
+            let allow: Vec<&str> = _struct.lint_allow.iter().map(String::as_str).collect();
+
+            if !reachable.structs.contains(&_struct.name) {
+                self.report(
+                    Rule::UnusedType,
+                    &allow,
+                    &path,
+                    format!("struct '{}' is not used by any route", _struct.name),
+                );
+            }
+            if !is_pascal_case(&_struct.name) {
+                self.report(
+                    Rule::StructNameCase,
+                    &allow,
+                    &path,
+                    format!("struct name '{}' should be PascalCase", _struct.name),
+                );
+            }
+            self.check_struct(_struct, allow, &path);
+        }
+
+        for _enum in &schema.enums {
+            let path = format!("enums.{}", _enum.name);
+            let allow: Vec<&str> = _enum.lint_allow.iter().map(String::as_str).collect();
+            self.check_allow_list(&_enum.lint_allow, &path);
+
+            if !reachable.enums.contains(&_enum.name) {
+                self.report(
+                    Rule::UnusedType,
+                    &allow,
+                    &path,
+                    format!("enum '{}' is not used by any route", _enum.name),
+                );
+            }
+            if _enum.variants.len() > self.config.max_enum_variants {
+                self.report(
+                    Rule::LargeEnum,
+                    &allow,
+                    &path,
+                    format!(
+                        "enum '{}' has {} variants. The limit is {}",
+                        _enum.name,
+                        _enum.variants.len(),
+                        self.config.max_enum_variants
+                    ),
+                );
+            }
+        }
+
+        for route in schema.queries.iter().chain(schema.mutations.iter()) {
+            let path = format!("routes.{}", route.name);
+            self.check_allow_list(&route.lint_allow, &path);
+        }
+        for route in &schema.mutations {
+            let path = format!("routes.{}", route.name);
+            let allow: Vec<&str> = route.lint_allow.iter().map(String::as_str).collect();
+
+            let problem = match self.response_size(&route.response_body, &mut Vec::new()) {
+                Ok(size) if size > self.config.max_mutation_response_fields => Some(format!(
+                    "has {} fields. The limit is {}",
+                    size, self.config.max_mutation_response_fields
+                )),
+                Ok(_) => None,
+                Err(reason) => Some(reason),
+            };
+            if let Some(problem) = problem {
+                self.report(
+                    Rule::LargeMutationResponse,
+                    &allow,
+                    &path,
+                    format!(
+                        "the response of mutation '{}' {}. Consider returning less data and \
+                        fetching the rest with a query",
+                        route.name, problem
+                    ),
+                );
+            }
+        }
+
+        self.diagnostics
+    }
+
+    /// Checks the fields of a top-level or nested struct. Rules allowed by a
+    /// struct are allowed for all of its nested objects as well.
+    fn check_struct(&mut self, _struct: &'a ZetroStruct, allow: Vec<&'a str>, path: &str) {
+        self.check_allow_list(&_struct.lint_allow, path);
+
+        for field in &_struct.fields {
+            let field_path = format!("{}.fields.{}", path, field.name);
+
+            if !is_camel_case(&field.name) {
+                self.report(
+                    Rule::FieldNameCase,
+                    &allow,
+                    &field_path,
+                    format!("field name '{}' should be camelCase", field.name),
+                );
+            }
+            match &field.kind {
+                FieldKind::NestedObject(nested) => {
+                    let mut nested_allow = allow.clone();
+                    nested_allow.extend(nested.lint_allow.iter().map(String::as_str));
+                    self.check_struct(nested, nested_allow, &field_path);
+                }
+                _ if field.description.is_none() => self.report(
+                    Rule::MissingFieldDescription,
+                    &allow,
+                    &field_path,
+                    format!("field '{}' has no description", field.name),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_allow_list(&mut self, rules: &[String], path: &str) {
+        for rule in rules {
+            if Rule::from_name(rule).is_none() {
+                self.report(
+                    Rule::UnknownAllow,
+                    &[],
+                    &format!("{}.allow", path),
+                    format!("unknown lint rule '{}'", rule),
+                );
+            }
+        }
+    }
+
+    /// Counts the values in a response. Lists of objects and recursive types
+    /// have no upper bound, so those are reported instead.
+    fn response_size(
+        &self,
+        field: &ZetroField,
+        visiting: &mut Vec<String>,
+    ) -> Result<usize, String> {
+        let fields = match &field.kind {
+            FieldKind::StructValue(name) => {
+                if visiting.contains(name) {
+                    return Err(format!("is recursive through struct '{}'", name));
+                }
+                match self.schema.structs.iter().find(|s| &s.name == name) {
+                    Some(s) => &s.fields,
+                    None => return Ok(1),
+                }
+            }
+            FieldKind::NestedObject(nested) => &nested.fields,
+            _ => return Ok(1),
+        };
+        if field.is_multiple {
+            return Err(format!("contains a list of objects ('{}')", field.name));
+        }
+
+        if let FieldKind::StructValue(name) = &field.kind {
+            visiting.push(name.clone());
+        }
+        let mut size = 0;
+        for f in fields {
+            size += self.response_size(f, visiting)?;
+        }
+        if let FieldKind::StructValue(_) = &field.kind {
+            visiting.pop();
+        }
+
+        Ok(size)
+    }
+
+    fn report(&mut self, rule: Rule, allow: &[&str], path: &str, message: String) {
+        let level = self.config.level(rule);
+        if level == Level::Off || allow.contains(&rule.name()) {
+            return;
+        }
+        self.diagnostics.push(Diagnostic {
+            rule,
+            level,
+            message,
+            path: path.to_owned(),
+            position: None,
+        });
+    }
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_camel_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::{config::LintConfig, lint, Level, Rule};
+    use crate::common::schema::ZetroSchema;
+
+    fn schema() -> ZetroSchema {
+        ZetroSchema::from_value(&json!({
+            "structs": {
+                "Message": {
+                    "description": "A message",
+                    "fields": {
+                        "id": "u64; Unique id",
+                        "author_name": "string",
+                        "replies": "[]struct~Message; Replies",
+                        "meta": {
+                            "description": "Metadata",
+                            "allow": ["missing-field-description"],
+                            "fields": { "sentAt": "u32" }
+                        }
+                    }
+                },
+                "old_message": {
+                    "description": "Not used anymore",
+                    "allow": ["unused-type", "no-such-rule"],
+                    "fields": {}
+                },
+                "Orphan": { "description": "Not used", "fields": {} }
+            },
+            "enums": {
+                "Status": ["A", "B", "C"],
+                "Big": { "variants": ["A", "B", "C", "D"], "allow": ["unused-type"] }
+            },
+            "routes": {
+                "GetMessage": {
+                    "kind": "query",
+                    "description": "Get a message",
+                    "request": "enum~Status",
+                    "response": "struct~Message"
+                },
+                "PostMessage": {
+                    "kind": "mutation",
+                    "description": "Post a message",
+                    "request": "struct~Message",
+                    "response": "struct~Message"
+                },
+                "DeleteMessage": {
+                    "kind": "mutation",
+                    "description": "Delete a message",
+                    "request": "u64",
+                    "response": "bool"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn found(diagnostics: &[super::Diagnostic]) -> Vec<(&'static str, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.rule.name(), d.path.as_str()))
+            .collect()
+    }
+
+    /// Each rule fires on exactly the offending items, and `allow` lists
+    /// suppress them
+    #[test]
+    fn reports_each_rule() {
+        let config = LintConfig::from_toml("[limits]\nmax-enum-variants = 3").unwrap();
+        let diagnostics = lint(&schema(), &config);
+
+        assert_eq!(
+            found(&diagnostics),
+            vec![
+                ("field-name-case", "structs.Message.fields.author_name"),
+                (
+                    "missing-field-description",
+                    "structs.Message.fields.author_name"
+                ),
+                ("unused-type", "structs.Orphan"),
+                ("struct-name-case", "structs.old_message"),
+                ("unknown-allow", "structs.old_message.allow"),
+                ("large-enum", "enums.Big"),
+                ("large-mutation-response", "routes.PostMessage"),
+            ]
+        );
+        assert_eq!(diagnostics[4].level, Level::Error);
+    }
+
+    /// Rules can be turned off or made errors
+    #[test]
+    fn respects_config_levels() {
+        let config = LintConfig::from_toml(
+            "[rules]\nfield-name-case = \"off\"\nunused-type = \"error\"\n\
+            missing-field-description = \"off\"\nlarge-mutation-response = \"off\"",
+        )
+        .unwrap();
+        let diagnostics = lint(&schema(), &config);
+
+        assert_eq!(
+            found(&diagnostics),
+            vec![
+                ("unused-type", "structs.Orphan"),
+                ("struct-name-case", "structs.old_message"),
+                ("unknown-allow", "structs.old_message.allow"),
+            ]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.level == Level::Error || d.rule == Rule::StructNameCase));
+    }
+}
@@ -0,0 +1,336 @@
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+
+use super::{
+    index::{Index, SymbolKind},
+    tree::Span,
+};
+use crate::common::{
+    idl,
+    schema::ZetroSchema,
+    source::{self, LoadError, SchemaFormat},
+};
+
+/// A schema file, open in the editor or read from the workspace
+pub(crate) struct Document {
+    pub text: String,
+    pub format: SchemaFormat,
+    /// Where names are declared and used. `None` while the text doesn't
+    /// decode, except in `.zetro` files, whose parser recovers from errors.
+    pub index: Option<Index>,
+    /// `None` while the schema is invalid
+    pub schema: Option<ZetroSchema>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Struct and enum names of the last text that decoded, to complete names
+    /// while the text is being edited
+    pub names: Vec<(SymbolKind, String)>,
+}
+
+impl Document {
+    pub fn new(path: &str, text: String) -> Self {
+        let mut document = Self {
+            text: String::new(),
+            format: SchemaFormat::from_path(path),
+            index: None,
+            schema: None,
+            diagnostics: Vec::new(),
+            names: Vec::new(),
+        };
+        document.update(path, text);
+        document
+    }
+
+    /// Replaces the text and parses it again
+    pub fn update(&mut self, path: &str, text: String) {
+        self.text = text;
+        if self.format == SchemaFormat::Zetro {
+            self.update_idl();
+            return;
+        }
+
+        self.set_index(Index::parse(&self.text, self.format));
+
+        let (schema, diagnostics) = match source::parse_schema(&self.text, self.format, path) {
+            Ok(schema) => (Some(schema), Vec::new()),
+            Err(LoadError::Source(errors)) => (
+                None,
+                errors
+                    .into_iter()
+                    .map(|e| {
+                        // Lines and columns are one-based, and only lines are
+                        // reported by every format
+                        let position = Position::new(
+                            e.line.unwrap_or(1).saturating_sub(1) as u32,
+                            e.column.unwrap_or(1).saturating_sub(1) as u32,
+                        );
+                        diagnostic(Range::new(position, position), e.message)
+                    })
+                    .collect(),
+            ),
+            Err(LoadError::Schema(e)) => {
+                let span = self
+                    .index
+                    .as_ref()
+                    .and_then(|index| index.error_span(&e))
+                    .unwrap_or(0..0);
+                (None, vec![diagnostic(self.range(&span), e.to_string())])
+            }
+        };
+        self.schema = schema;
+        self.diagnostics = diagnostics;
+    }
+
+    /// Keeps the names of the last index, see `names`
+    fn set_index(&mut self, index: Option<Index>) {
+        if let Some(index) = &index {
+            self.names = index
+                .definitions
+                .iter()
+                .map(|d| (d.kind, d.name.clone()))
+                .collect();
+        }
+        self.index = index;
+    }
+
+    /// Parses a `.zetro` file, whose errors have spans
+    fn update_idl(&mut self) {
+        self.set_index(Index::parse(&self.text, self.format));
+
+        match idl::parse_schema(&self.text) {
+            Ok(schema) => {
+                self.schema = Some(schema);
+                self.diagnostics = Vec::new();
+            }
+            Err(errors) => {
+                self.schema = None;
+                self.diagnostics = errors
+                    .into_iter()
+                    .map(|e| diagnostic(self.range(&(e.span.start..e.span.end)), e.message))
+                    .collect();
+            }
+        }
+    }
+
+    /// Converts a span into an LSP range, whose columns count UTF-16 code
+    /// units
+    pub fn range(&self, span: &Span) -> Range {
+        Range::new(self.position(span.start), self.position(span.end))
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Position::new(
+            before.matches('\n').count() as u32,
+            before[line_start..].encode_utf16().count() as u32,
+        )
+    }
+
+    /// The inverse of `position`. Positions past the end of a line or of the
+    /// text are clamped to it.
+    pub fn offset(&self, position: Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match self.text[line_start..].find('\n') {
+                Some(i) => line_start += i + 1,
+                None => return self.text.len(),
+            }
+        }
+        let line = self.text[line_start..].split('\n').next().unwrap_or("");
+
+        let mut units = 0;
+        for (i, c) in line.char_indices() {
+            if units >= position.character as usize {
+                return line_start + i;
+            }
+            units += c.len_utf16();
+        }
+        line_start + line.len()
+    }
+}
+
+fn diagnostic(range: Range, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(String::from("zetro")),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Position, Range};
+
+    use super::{Document, SymbolKind};
+
+    const SCHEMA: &str = r#"{
+  "structs": {
+    "Message": {
+      "description": "A message",
+      "fields": {
+        "id": "u64; Unique id",
+        "replies": "?[]struct~Message; Replies",
+        "meta": { "description": "Metadata", "fields": { "status": "enum~Status" } }
+      }
+    }
+  },
+  "enums": { "Status": ["A", "B"] },
+  "routes": {
+    "GetMessage": {
+      "kind": "query",
+      "description": "Get a message",
+      "request": "u64",
+      "response": "struct~Message"
+    }
+  }
+}
+"#;
+
+    fn text_of<'a>(document: &'a Document, span: &super::super::tree::Span) -> &'a str {
+        &document.text[span.clone()]
+    }
+
+    /// Declarations and references are indexed by the span of the name alone
+    #[test]
+    fn indexes_names() {
+        let document = Document::new("schema.json", SCHEMA.to_owned());
+        assert!(document.diagnostics.is_empty());
+        let index = document.index.as_ref().unwrap();
+
+        let message = index.definition(SymbolKind::Struct, "Message").unwrap();
+        assert_eq!(text_of(&document, &message.span), "Message");
+        let references = index.references_to(SymbolKind::Struct, "Message");
+        assert_eq!(references.len(), 2);
+        assert!(references
+            .iter()
+            .all(|r| text_of(&document, &r.span) == "Message"));
+        assert_eq!(references[0].path, "structs.Message.fields.replies");
+        assert_eq!(references[1].path, "routes.GetMessage.response");
+
+        let status = index.references_to(SymbolKind::Enum, "Status");
+        assert_eq!(status[0].path, "structs.Message.fields.meta.fields.status");
+        assert_eq!(
+            document.names,
+            vec![
+                (SymbolKind::Struct, "Message".to_owned()),
+                (SymbolKind::Enum, "Status".to_owned())
+            ]
+        );
+    }
+
+    /// Schema errors point at the part of the text that caused them
+    #[test]
+    fn locates_schema_errors() {
+        let text = SCHEMA.replace("enum~Status", "enum~Stat");
+        let document = Document::new("schema.json", text);
+        assert_eq!(document.diagnostics.len(), 1);
+        let range = document.diagnostics[0].range;
+        let start = document.offset(range.start);
+        assert_eq!(&document.text[start..document.offset(range.end)], "Stat");
+        // The last text that decoded still has the names
+        assert!(document.schema.is_none());
+        assert_eq!(document.names.len(), 2);
+
+        let spans = [
+            ("\"kind\"", "\"knd\"", "\"GetMessage\""),
+            (
+                "\"description\": \"Metadata\"",
+                "\"colour\": 1",
+                "\"colour\"",
+            ),
+        ];
+        for (from, to, offender) in spans {
+            let document = Document::new("schema.json", SCHEMA.replace(from, to));
+            let range = document.diagnostics[0].range;
+            let start = document.offset(range.start);
+            assert_eq!(&document.text[start..document.offset(range.end)], offender);
+        }
+    }
+
+    /// Text that doesn't decode is reported where the decoder stopped
+    #[test]
+    fn reports_source_errors() {
+        let document = Document::new("schema.json", SCHEMA.replace("\"A\", ", "\"A\" "));
+        assert!(document.index.is_none());
+        assert_eq!(document.diagnostics[0].range.start.line, 11);
+
+        let document = Document::new("schema.yaml", String::from("structs: {\n"));
+        assert!(document.index.is_none());
+        assert_eq!(document.diagnostics.len(), 1);
+    }
+
+    /// Every format is indexed, with the spans of the names alone
+    #[test]
+    fn indexes_every_format() {
+        let sources = [
+            (
+                "schema.json5",
+                "{\n  // Comment\n  structs: { 'Message': { description: '', fields: { replies: '[]struct~Message' } } },\n  enums: { Status: ['A'] },\n}\n",
+            ),
+            (
+                "schema.yaml",
+                "structs:\n  Message:\n    description: ''\n    fields:\n      replies: '[]struct~Message'\nenums:\n  Status: [A]\n",
+            ),
+            (
+                "schema.toml",
+                "[structs.Message]\ndescription = \"\"\nfields = { replies = \"[]struct~Message\" }\n\n[enums]\n\"Status\" = [\"A\"]\n",
+            ),
+            (
+                "schema.zetro",
+                "/// A message\nstruct Message {\n  replies: []Message\n}\n\nenum Status { A }\n",
+            ),
+        ];
+        for (path, text) in sources {
+            let document = Document::new(path, text.to_owned());
+            assert!(
+                document.diagnostics.is_empty(),
+                "{}: {:?}",
+                path,
+                document.diagnostics
+            );
+            let index = document.index.as_ref().unwrap();
+
+            let message = index.definition(SymbolKind::Struct, "Message").unwrap();
+            assert_eq!(text_of(&document, &message.span), "Message", "{}", path);
+            let status = index.definition(SymbolKind::Enum, "Status").unwrap();
+            assert_eq!(text_of(&document, &status.span), "Status", "{}", path);
+            let references = index.references_to(SymbolKind::Struct, "Message");
+            assert_eq!(references.len(), 1, "{}", path);
+            assert_eq!(
+                text_of(&document, &references[0].span),
+                "Message",
+                "{}",
+                path
+            );
+            assert_eq!(references[0].path, "structs.Message.fields.replies");
+        }
+    }
+
+    /// `.zetro` errors keep their spans, and the syntax tree of the text up to
+    /// an error is still indexed
+    #[test]
+    fn reports_idl_errors() {
+        let text = "/// A message\nstruct Message {\n  status: Stat\n}\n\nenum Status { A }\n";
+        let document = Document::new("schema.zetro", text.to_owned());
+        assert_eq!(document.diagnostics.len(), 1);
+        let range = document.diagnostics[0].range;
+        let start = document.offset(range.start);
+        assert_eq!(&document.text[start..document.offset(range.end)], "Stat");
+        assert_eq!(document.names.len(), 2);
+    }
+
+    /// Columns count UTF-16 code units
+    #[test]
+    fn converts_positions() {
+        let document = Document::new("schema.json", String::from("{\n\"é😀\": 1}"));
+        assert_eq!(document.position(9), Position::new(1, 4));
+        assert_eq!(document.offset(Position::new(1, 4)), 9);
+        assert_eq!(document.offset(Position::new(1, 99)), document.text.len());
+        assert_eq!(document.offset(Position::new(9, 0)), document.text.len());
+        assert_eq!(
+            document.range(&(0..1)),
+            Range::new(Position::new(0, 0), Position::new(0, 1))
+        );
+    }
+}
@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Hover, HoverContents, Location,
+    MarkupContent, MarkupKind, Position, Range, TextEdit, Url, WorkspaceEdit,
+};
+
+use super::{
+    document::Document,
+    index::{Symbol, SymbolKind},
+    tree::Span,
+};
+use crate::{
+    common::{
+        schema::{
+            fields::{FieldKind, ZetroField},
+            ZetroSchema,
+        },
+        source::SchemaFormat,
+    },
+    generators,
+    options::{Language, Options},
+};
+
+/// Dtypes of fields that are not structs or enums
+const PRIMITIVES: [&str; 12] = [
+    "string", "bool", "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "f32", "f64",
+];
+
+/// Completes the dtype or the struct or enum name of the field being typed,
+/// and the `kind` of routes. Only the text of the line up to the cursor is
+/// looked at, so that it works while the file doesn't parse.
+pub(crate) fn completion(document: &Document, position: Position) -> Vec<CompletionItem> {
+    let offset = document.offset(position);
+    let line_start = document.text[..offset]
+        .rfind('\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let line = &document.text[line_start..offset];
+
+    // Only values are completed, eg. `"id": "u6`
+    let Some((key, value_start)) = typed_value(line, document.format) else {
+        return Vec::new();
+    };
+    let typed = &line[value_start..];
+    let names = |kind: Option<SymbolKind>| {
+        document
+            .names
+            .iter()
+            .filter(move |(k, _)| kind.is_none() || Some(*k) == kind)
+            .map(|(k, name)| {
+                let item_kind = match k {
+                    SymbolKind::Struct => CompletionItemKind::STRUCT,
+                    SymbolKind::Enum => CompletionItemKind::ENUM,
+                };
+                (name.clone(), item_kind, k.name())
+            })
+    };
+    let primitives = || {
+        PRIMITIVES
+            .iter()
+            .map(|p| (p.to_string(), CompletionItemKind::KEYWORD, "primitive"))
+    };
+    // After the `?` and `[]` operators
+    let dtype_start = |typed: &str| {
+        typed.len()
+            - typed
+                .trim_start_matches(|c: char| "?[] \t".contains(c))
+                .len()
+    };
+
+    let (start, items): (usize, Vec<_>) = if document.format == SchemaFormat::Zetro {
+        // Types are names, without `struct~` or `enum~`
+        let start = dtype_start(typed);
+        if !typed[start..]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_')
+        {
+            return Vec::new();
+        }
+        (start, primitives().chain(names(None)).collect())
+    } else if key == "kind" {
+        let items = ["query", "mutation"]
+            .iter()
+            .map(|k| (k.to_string(), CompletionItemKind::KEYWORD, "route kind"))
+            .collect();
+        (0, items)
+    } else if key == "description" || typed.contains(';') {
+        return Vec::new();
+    } else if let Some(tilde) = typed.rfind('~') {
+        let kind = match typed[..tilde].trim_end() {
+            dtype if dtype.ends_with("struct") => SymbolKind::Struct,
+            dtype if dtype.ends_with("enum") => SymbolKind::Enum,
+            _ => return Vec::new(),
+        };
+        (tilde + 1, names(Some(kind)).collect())
+    } else {
+        let mut items = primitives().collect::<Vec<_>>();
+        for kind in [SymbolKind::Struct, SymbolKind::Enum] {
+            items.push((
+                format!("{}~", kind.name()),
+                CompletionItemKind::KEYWORD,
+                kind.name(),
+            ));
+        }
+        (dtype_start(typed), items)
+    };
+
+    let range = document.range(&(line_start + value_start + start..offset));
+    items
+        .into_iter()
+        .map(|(label, kind, detail)| CompletionItem {
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                range,
+                label.clone(),
+            ))),
+            label,
+            kind: Some(kind),
+            detail: Some(detail.to_owned()),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// The key of the value `line` ends in, and the offset where the value
+/// starts, eg. `id` and the offset of `u6` in `"id": "u6`. Values must be
+/// quoted, except in YAML and `.zetro` files.
+fn typed_value(line: &str, format: SchemaFormat) -> Option<(&str, usize)> {
+    let separator = match format {
+        SchemaFormat::Toml => '=',
+        _ => ':',
+    };
+    let quotes: &[char] = match format {
+        SchemaFormat::Json => &['"'],
+        SchemaFormat::Zetro => &[],
+        _ => &['"', '\''],
+    };
+
+    let mut open: Option<(char, usize)> = None;
+    let mut escaped = false;
+    let mut separators: Vec<usize> = Vec::new();
+    for (i, c) in line.char_indices() {
+        match open {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some((quote, _)) if c == quote => open = None,
+            Some(_) => {}
+            None if quotes.contains(&c) => open = Some((c, i)),
+            None if c == separator => separators.push(i),
+            None => {}
+        }
+    }
+    // Plain YAML values may contain colons, and keys can't
+    let separator = match format {
+        SchemaFormat::Yaml => *separators.first()?,
+        _ => *separators.last()?,
+    };
+
+    let value_start = match open {
+        Some((_, quote)) if line[separator + 1..quote].trim().is_empty() => quote + 1,
+        None if matches!(format, SchemaFormat::Yaml | SchemaFormat::Zetro) => {
+            let rest = &line[separator + 1..];
+            line.len() - rest.trim_start().len()
+        }
+        _ => return None,
+    };
+    let key = line[..separator]
+        .trim_end()
+        .rsplit(|c: char| c == '{' || c == ',' || c.is_whitespace())
+        .next()?
+        .trim_matches(|c| c == '"' || c == '\'');
+    Some((key, value_start))
+}
+
+/// Where the struct or enum under the cursor is declared, in this document
+/// or another schema of the workspace
+pub(crate) fn definition(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+    others: &[(&Url, &Document)],
+) -> Option<Location> {
+    let index = document.index.as_ref()?;
+    let symbol = index.symbol_at(document.offset(position))?;
+    [(uri, document)]
+        .into_iter()
+        .chain(others.iter().copied())
+        .find_map(|(uri, document)| {
+            let definition = document
+                .index
+                .as_ref()?
+                .definition(symbol.kind, &symbol.name)?;
+            Some(Location::new(uri.clone(), document.range(&definition.span)))
+        })
+}
+
+/// Every field and route that uses the struct or enum under the cursor, in
+/// this document and the other schemas of the workspace
+pub(crate) fn references(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+    include_declaration: bool,
+    others: &[(&Url, &Document)],
+) -> Vec<Location> {
+    let Some(symbol) = symbol_at(document, position) else {
+        return Vec::new();
+    };
+    [(uri, document)]
+        .into_iter()
+        .chain(others.iter().copied())
+        .flat_map(|(uri, document)| {
+            occurrences(document, symbol.kind, &symbol.name, include_declaration)
+                .into_iter()
+                .map(|span| Location::new(uri.clone(), document.range(&span)))
+        })
+        .collect()
+}
+
+/// The range of the name a rename would change
+pub(crate) fn prepare_rename(document: &Document, position: Position) -> Option<Range> {
+    let symbol = symbol_at(document, position)?;
+    Some(document.range(&symbol.span))
+}
+
+/// Renames the struct or enum under the cursor, where it is declared and
+/// everywhere it is used. Schemas don't import each other, so the other
+/// schemas of the workspace are edited wherever they declare or use a type
+/// of the same kind and name.
+pub(crate) fn rename(
+    document: &Document,
+    uri: &Url,
+    position: Position,
+    new_name: &str,
+    others: &[(&Url, &Document)],
+) -> Result<WorkspaceEdit, String> {
+    let Some(symbol) = symbol_at(document, position) else {
+        return Err(String::from("There is no struct or enum to rename here"));
+    };
+
+    let valid = new_name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && new_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!(
+            "'{}' is not a valid {} name",
+            new_name,
+            symbol.kind.name()
+        ));
+    }
+
+    let mut changes = HashMap::new();
+    for (uri, document) in [(uri, document)].into_iter().chain(others.iter().copied()) {
+        let spans = occurrences(document, symbol.kind, &symbol.name, true);
+        if spans.is_empty() {
+            continue;
+        }
+        // Structs and enums generate types of the same namespace
+        let taken = document.index.as_ref().and_then(|index| {
+            index
+                .definitions
+                .iter()
+                .find(|d| d.name == new_name && !(d.kind == symbol.kind && d.name == symbol.name))
+        });
+        if let Some(taken) = taken {
+            return Err(format!(
+                "There already is a {} named '{}' in {}",
+                taken.kind.name(),
+                new_name,
+                uri
+            ));
+        }
+        let edits = spans
+            .iter()
+            .map(|span| TextEdit::new(document.range(span), new_name.to_owned()))
+            .collect();
+        changes.insert(uri.clone(), edits);
+    }
+    Ok(WorkspaceEdit::new(changes))
+}
+
+fn symbol_at(document: &Document, position: Position) -> Option<&Symbol> {
+    document
+        .index
+        .as_ref()?
+        .symbol_at(document.offset(position))
+}
+
+/// Spans of the uses of a struct or enum, and of its declaration if
+/// `include_declaration`
+fn occurrences(
+    document: &Document,
+    kind: SymbolKind,
+    name: &str,
+    include_declaration: bool,
+) -> Vec<Span> {
+    let Some(index) = &document.index else {
+        return Vec::new();
+    };
+    let declaration = index.definition(kind, name).filter(|_| include_declaration);
+    declaration
+        .into_iter()
+        .chain(index.references_to(kind, name))
+        .map(|symbol| symbol.span.clone())
+        .collect()
+}
+
+/// Shows the code generated for the struct or enum under the cursor, or for
+/// the field under it, in every language
+pub(crate) fn hover(document: &Document, position: Position) -> Option<Hover> {
+    let index = document.index.as_ref()?;
+    let schema = document.schema.as_ref()?;
+    let offset = document.offset(position);
+
+    let (span, code) = match index.symbol_at(offset) {
+        Some(symbol) => (
+            &symbol.span,
+            languages()
+                .map(|(fence, args)| {
+                    generators::declaration(schema, &args, &symbol.name).map(|c| (fence, c))
+                })
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        None => {
+            let site = index.field_at(offset)?;
+            let field = find_field(schema, &site.path)?;
+            (
+                &site.span,
+                languages()
+                    .map(|(fence, args)| (fence, generators::field_declaration(field, &args)))
+                    .collect(),
+            )
+        }
+    };
+
+    let value = code
+        .iter()
+        .map(|(fence, code)| format!("```{}\n{}\n```", fence, code))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: Some(document.range(span)),
+    })
+}
+
+/// Every language as (markdown fence, default options)
+fn languages() -> impl Iterator<Item = (&'static str, crate::utilities::ZetroArgs)> {
+    [
+        ("rust", Language::Rust),
+        ("typescript", Language::TypeScript),
+    ]
+    .into_iter()
+    .map(|(fence, language)| {
+        let args = Options::new(language)
+            .to_args()
+            .expect("default options are valid");
+        (fence, args)
+    })
+}
+
+/// The field at a path of the index, eg. `structs.Tree.fields.label.fields.text`
+/// or `routes.GetRooms.request`
+fn find_field<'a>(schema: &'a ZetroSchema, path: &str) -> Option<&'a ZetroField> {
+    let parts = path.split('.').collect::<Vec<_>>();
+    match parts.as_slice() {
+        ["routes", name, body] => {
+            let route = schema
+                .queries
+                .iter()
+                .chain(schema.mutations.iter())
+                .find(|r| r.name == *name)?;
+            match *body {
+                "request" => Some(&route.request_body),
+                _ => Some(&route.response_body),
+            }
+        }
+        ["structs", name, fields @ ..] => {
+            let mut scope = &schema.structs.iter().find(|s| s.name == *name)?.fields;
+            let mut field: Option<&ZetroField> = None;
+            for part in fields.chunks(2) {
+                let ["fields", name] = part else {
+                    return None;
+                };
+                let found = scope.iter().find(|f| f.name == *name)?;
+                if let FieldKind::NestedObject(nested) = &found.kind {
+                    scope = &nested.fields;
+                }
+                field = Some(found);
+            }
+            field
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{HoverContents, Position, Url};
+
+    use super::super::document::Document;
+
+    const SCHEMA: &str = r#"{
+  "structs": {
+    "Message": {
+      "description": "A message",
+      "fields": {
+        "id": "u64; Unique id",
+        "replies": "?[]struct~Message; Replies",
+        "status": "enum~Status"
+      }
+    }
+  },
+  "enums": { "Status": ["A", "B"] },
+  "routes": {
+    "GetMessage": {
+      "kind": "query",
+      "description": "Get a message",
+      "request": "u64",
+      "response": "struct~Message"
+    }
+  }
+}
+"#;
+
+    fn uri() -> Url {
+        Url::parse("file:///schema.json").unwrap()
+    }
+
+    /// The position of the `nth` occurrence of `needle`, plus `shift` bytes
+    fn at(document: &Document, needle: &str, nth: usize, shift: usize) -> Position {
+        let (offset, _) = document.text.match_indices(needle).nth(nth).unwrap();
+        document.position(offset + shift)
+    }
+
+    fn labels(document: &Document, position: Position) -> Vec<String> {
+        super::completion(document, position)
+            .into_iter()
+            .map(|i| i.label)
+            .collect()
+    }
+
+    /// Dtypes, names after `struct~` and `enum~`, and route kinds are
+    /// completed, even while the text doesn't parse
+    #[test]
+    fn completes() {
+        let text = SCHEMA.replace("\"u64; Unique id\"", "\"?[]u");
+        let document = Document::new("schema.json", text);
+        assert!(document.schema.is_none());
+        let position = at(&document, "?[]u", 0, 4);
+        let items = super::completion(&document, position);
+        assert!(items.iter().any(|i| i.label == "u64"));
+        assert!(items.iter().any(|i| i.label == "struct~"));
+        // Replaces what was typed after the operators
+        let Some(lsp_types::CompletionTextEdit::Edit(edit)) = &items[0].text_edit else {
+            panic!("no text edit");
+        };
+        assert_eq!(edit.range.start, at(&document, "?[]u", 0, 3));
+
+        let document = Document::new("schema.json", SCHEMA.to_owned());
+        assert_eq!(
+            labels(&document, at(&document, "struct~Message;", 0, 7)),
+            vec!["Message"]
+        );
+        assert_eq!(
+            labels(&document, at(&document, "enum~Status", 0, 5)),
+            vec!["Status"]
+        );
+        assert_eq!(
+            labels(&document, at(&document, "query", 0, 0)),
+            vec!["query", "mutation"]
+        );
+        // Keys, descriptions and text after the dtype are not completed
+        assert!(labels(&document, at(&document, "\"id\"", 0, 2)).is_empty());
+        assert!(labels(&document, at(&document, "Unique", 0, 0)).is_empty());
+        assert!(labels(&document, at(&document, "Get a", 0, 0)).is_empty());
+    }
+
+    /// Definitions and references are found from any use of a name
+    #[test]
+    fn navigates() {
+        let document = Document::new("schema.json", SCHEMA.to_owned());
+        let declaration = at(&document, "Message", 0, 0);
+        let response = at(&document, "Message", 3, 2);
+
+        let location = super::definition(&document, &uri(), response, &[]).unwrap();
+        assert_eq!(location.range.start, declaration);
+        assert!(super::definition(&document, &uri(), at(&document, "u64", 0, 0), &[]).is_none());
+
+        let references = super::references(&document, &uri(), declaration, false, &[]);
+        let starts = references.iter().map(|l| l.range.start).collect::<Vec<_>>();
+        // The third `Message` is in `GetMessage`
+        assert_eq!(
+            starts,
+            vec![
+                at(&document, "Message", 1, 0),
+                at(&document, "Message", 3, 0)
+            ]
+        );
+        assert_eq!(
+            super::references(&document, &uri(), response, true, &[]).len(),
+            3
+        );
+    }
+
+    /// Renames change the declaration and every reference, and refuse names
+    /// that are invalid or taken
+    #[test]
+    fn renames() {
+        let mut document = Document::new("schema.json", SCHEMA.to_owned());
+        let position = at(&document, "Status", 0, 1);
+        let range = super::prepare_rename(&document, position).unwrap();
+        assert_eq!(range.start, at(&document, "Status", 0, 0));
+
+        let edit = super::rename(&document, &uri(), position, "RoomStatus", &[]).unwrap();
+        let mut edits = edit.changes.unwrap().remove(&uri()).unwrap();
+        assert_eq!(edits.len(), 2);
+        // Applied from the end, so that earlier offsets stay valid
+        edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
+        for edit in edits {
+            let span = document.offset(edit.range.start)..document.offset(edit.range.end);
+            document.text.replace_range(span, &edit.new_text);
+        }
+        let text = document.text.clone();
+        document.update("schema.json", text);
+        assert!(document.diagnostics.is_empty());
+        assert!(document.text.contains("\"enum~RoomStatus\""));
+
+        let position = at(&document, "Message", 0, 0);
+        assert!(super::rename(&document, &uri(), position, "2fast", &[]).is_err());
+        let position = at(&document, "RoomStatus", 0, 0);
+        let document = Document::new(
+            "schema.json",
+            SCHEMA.replace("\"Status\"", "\"Message\", \"Message\": [\"A\"]"),
+        );
+        assert!(super::rename(&document, &uri(), position, "Message", &[]).is_err());
+        assert!(super::prepare_rename(&document, at(&document, "u64", 0, 0)).is_none());
+    }
+
+    /// Renames and references reach the other schemas of the workspace,
+    /// whatever their format
+    #[test]
+    fn renames_across_files() {
+        let document = Document::new("schema.json", SCHEMA.to_owned());
+        let client_uri = Url::parse("file:///client/schema.yaml").unwrap();
+        let client = Document::new(
+            "schema.yaml",
+            String::from("enums:\n  Status: [A, B]\nstructs:\n  Room:\n    description: ''\n    fields:\n      status: enum~Status\n"),
+        );
+        let other_uri = Url::parse("file:///other/schema.zetro").unwrap();
+        let other = Document::new(
+            "schema.zetro",
+            String::from("/// A room\nstruct Room {\n  id: u64\n}\n"),
+        );
+        let others = [(&client_uri, &client), (&other_uri, &other)];
+
+        let position = at(&document, "Status", 1, 0);
+        let references = super::references(&document, &uri(), position, true, &others);
+        assert_eq!(references.len(), 4);
+        assert_eq!(references[2].uri, client_uri);
+
+        let edit = super::rename(&document, &uri(), position, "RoomStatus", &others).unwrap();
+        let changes = edit.changes.unwrap();
+        assert_eq!(changes[&uri()].len(), 2);
+        assert_eq!(changes[&client_uri].len(), 2);
+        // Schemas without the type are left alone
+        assert!(!changes.contains_key(&other_uri));
+
+        // Another schema that already has the new name refuses the rename
+        let error = super::rename(&document, &uri(), position, "Room", &others).unwrap_err();
+        assert!(error.contains("client"), "{}", error);
+    }
+
+    /// Completion works in every format
+    #[test]
+    fn completes_every_format() {
+        let sources = [
+            (
+                "schema.yaml",
+                "structs:\n  Message:\n    fields:\n      next: struct~",
+            ),
+            (
+                "schema.json5",
+                "{ structs: { Message: { fields: { next: 'struct~",
+            ),
+            ("schema.toml", "[structs.Message.fields]\nnext = \"struct~"),
+            ("schema.zetro", "struct Message {\n  next: ?Mess"),
+        ];
+        for (path, text) in sources {
+            // The names of the last text that decoded
+            let mut document = Document::new("schema.json", SCHEMA.to_owned());
+            document.format = super::SchemaFormat::from_path(path);
+            document.text = text.to_owned();
+            let position = document.position(text.len());
+            let labels = labels(&document, position);
+            assert!(
+                labels.contains(&String::from("Message")),
+                "{}: {:?}",
+                path,
+                labels
+            );
+        }
+    }
+
+    /// Hovers show the generated code of types and fields
+    #[test]
+    fn hovers() {
+        let document = Document::new("schema.json", SCHEMA.to_owned());
+        let markdown = |position| match super::hover(&document, position).unwrap().contents {
+            HoverContents::Markup(markup) => markup.value,
+            _ => panic!("not markdown"),
+        };
+
+        let code = markdown(at(&document, "Message", 3, 0));
+        assert!(code.contains("```rust\n"));
+        assert!(code.contains("pub struct Message"));
+        assert!(code.contains("```typescript\n"));
+        assert!(code.contains("interface Message"));
+
+        let code = markdown(at(&document, "replies", 0, 0));
+        assert!(code.contains("pub replies: Option<Vec<Box<Message>>>"));
+        assert!(code.contains("replies?: Message[]"));
+
+        assert!(markdown(at(&document, "u64\"", 0, 0)).contains("request"));
+        assert!(super::hover(&document, at(&document, "fields", 0, 0)).is_none());
+    }
+}
@@ -0,0 +1,388 @@
+//! Where struct and enum names are declared and used in a schema, to
+//! navigate and rename them.
+
+use std::collections::HashMap;
+
+use super::tree::{self, inner_span, Member, Node, Span};
+use crate::common::{
+    idl::{
+        ast::{self, ItemKind, TypeKind},
+        lexer, parser,
+    },
+    schema::{
+        errors::{ErrorKind, Offender, SchemaError},
+        fields::FieldKind,
+        structs,
+    },
+    source::SchemaFormat,
+};
+
+/// What a name declares or refers to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SymbolKind {
+    Struct,
+    Enum,
+}
+
+impl SymbolKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+        }
+    }
+}
+
+/// A struct or enum name in the text
+#[derive(Debug)]
+pub(crate) struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Span of the name alone, without quotes or the rest of a field
+    pub span: Span,
+    /// Path of the key the name belongs to, see `Index::keys`
+    pub path: String,
+}
+
+/// A struct field or the body of a route
+#[derive(Debug)]
+pub(crate) struct FieldSite {
+    pub path: String,
+    /// From the start of the key to the end of the value
+    pub span: Span,
+}
+
+/// Where names are declared and used in a schema
+#[derive(Debug, Default)]
+pub(crate) struct Index {
+    /// Spans of every key of a struct, enum or route, by their dotted path, eg.
+    /// `structs.Message.fields.id`
+    pub keys: HashMap<String, Span>,
+    /// Paths of structs, nested objects, enums and routes, by the name that
+    /// errors use for them
+    pub containers: HashMap<String, String>,
+    /// Where structs and enums are declared
+    pub definitions: Vec<Symbol>,
+    /// Where fields and routes refer to structs and enums
+    pub references: Vec<Symbol>,
+    pub fields: Vec<FieldSite>,
+}
+
+impl Index {
+    /// Indexes a schema in any format. `None` if the text doesn't decode,
+    /// except for `.zetro` files, whose parser recovers from errors.
+    pub fn parse(text: &str, format: SchemaFormat) -> Option<Self> {
+        if format == SchemaFormat::Zetro {
+            let (tokens, _) = lexer::tokenize(text);
+            let (document, _) = parser::parse(tokens);
+            return Some(Self::from_idl(&document));
+        }
+        tree::parse(text, format).map(|root| Self::new(text, &root))
+    }
+
+    /// Indexes the tree of a JSON, JSON5, YAML or TOML schema
+    pub fn new(text: &str, root: &Node) -> Self {
+        let mut index = Self::default();
+        let Node::Object(sections) = root else {
+            return index;
+        };
+
+        for section in sections {
+            index
+                .keys
+                .insert(section.key.clone(), section.key_span.clone());
+            let Node::Object(members) = &section.value else {
+                continue;
+            };
+            for member in members {
+                let path = format!("{}.{}", section.key, member.key);
+                index.keys.insert(path.clone(), member.key_span.clone());
+                index.containers.insert(member.key.clone(), path.clone());
+
+                match section.key.as_str() {
+                    "structs" => {
+                        index.add_definition(text, SymbolKind::Struct, member, &path);
+                        index.add_struct(text, &member.key, &path, &member.value);
+                    }
+                    "enums" => {
+                        index.add_definition(text, SymbolKind::Enum, member, &path);
+                        index.add_keys(&path, &member.value);
+                    }
+                    "routes" => {
+                        index.add_keys(&path, &member.value);
+                        let Node::Object(route) = &member.value else {
+                            continue;
+                        };
+                        for body in route {
+                            if body.key == "request" || body.key == "response" {
+                                index.add_field(text, &format!("{}.{}", path, body.key), body);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        index
+    }
+
+    /// Indexes the syntax tree of a `.zetro` schema, which is partial while
+    /// the text has errors. Type names resolve like `idl::lower` resolves
+    /// them: primitives first, then the structs and enums of the file.
+    pub fn from_idl(document: &ast::Document) -> Self {
+        let mut index = Self::default();
+        let kinds = document
+            .items
+            .iter()
+            .filter_map(|item| match item.kind {
+                ItemKind::Struct(_) => Some((item.name.name.as_str(), SymbolKind::Struct)),
+                ItemKind::Enum(_) => Some((item.name.name.as_str(), SymbolKind::Enum)),
+                ItemKind::Route(..) => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for item in &document.items {
+            let name = &item.name;
+            let (kind, section) = match &item.kind {
+                ItemKind::Struct(_) => (SymbolKind::Struct, "structs"),
+                ItemKind::Enum(_) => (SymbolKind::Enum, "enums"),
+                ItemKind::Route(_, fields) => {
+                    let path = format!("routes.{}", name.name);
+                    index
+                        .keys
+                        .insert(path.clone(), name.span.start..name.span.end);
+                    let bodies = fields
+                        .iter()
+                        .filter(|f| f.name.name == "request" || f.name.name == "response")
+                        .collect::<Vec<_>>();
+                    index.add_idl_fields(&kinds, &path, &bodies);
+                    continue;
+                }
+            };
+            let path = format!("{}.{}", section, name.name);
+            index
+                .keys
+                .insert(path.clone(), name.span.start..name.span.end);
+            index.definitions.push(Symbol {
+                kind,
+                name: name.name.clone(),
+                span: name.span.start..name.span.end,
+                path: path.clone(),
+            });
+            if let ItemKind::Struct(fields) = &item.kind {
+                let fields = fields.iter().collect::<Vec<_>>();
+                index.add_idl_fields(&kinds, &format!("{}.fields", path), &fields);
+            }
+        }
+        index
+    }
+
+    /// Adds the fields of a struct, nested object or route, whose paths start
+    /// with `prefix`
+    fn add_idl_fields(
+        &mut self,
+        kinds: &HashMap<&str, SymbolKind>,
+        prefix: &str,
+        fields: &[&ast::FieldDecl],
+    ) {
+        for field in fields {
+            let path = format!("{}.{}", prefix, field.name.name);
+            self.keys
+                .insert(path.clone(), field.name.span.start..field.name.span.end);
+            match &field.ty.kind {
+                TypeKind::Named { name, .. } => {
+                    let kind = match FieldKind::from_primitive_name(&name.name) {
+                        Some(_) => None,
+                        None => kinds.get(name.name.as_str()),
+                    };
+                    if let Some(kind) = kind {
+                        self.references.push(Symbol {
+                            kind: *kind,
+                            name: name.name.clone(),
+                            span: name.span.start..name.span.end,
+                            path: path.clone(),
+                        });
+                    }
+                }
+                TypeKind::Object(nested) => {
+                    let nested = nested.iter().collect::<Vec<_>>();
+                    self.add_idl_fields(kinds, &format!("{}.fields", path), &nested);
+                }
+            }
+            // Nested objects are not hovered as a whole
+            let end = match field.ty.kind {
+                TypeKind::Named { .. } => field.ty.span.end,
+                TypeKind::Object(_) => field.name.span.end,
+            };
+            self.fields.push(FieldSite {
+                path,
+                span: field.name.span.start..end,
+            });
+        }
+    }
+
+    fn add_definition(&mut self, text: &str, kind: SymbolKind, member: &Member, path: &str) {
+        // Names never need escapes
+        if let Some(span) = inner_span(text, &member.key_span, &member.key) {
+            self.definitions.push(Symbol {
+                kind,
+                name: member.key.clone(),
+                span,
+                path: path.to_owned(),
+            });
+        }
+    }
+
+    fn add_keys(&mut self, path: &str, value: &Node) {
+        if let Node::Object(members) = value {
+            for member in members {
+                self.keys
+                    .insert(format!("{}.{}", path, member.key), member.key_span.clone());
+            }
+        }
+    }
+
+    /// Adds a struct or nested object named `name` at `path`
+    fn add_struct(&mut self, text: &str, name: &str, path: &str, value: &Node) {
+        self.add_keys(path, value);
+        let Node::Object(members) = value else {
+            return;
+        };
+        for member in members.iter().filter(|m| m.key == "fields") {
+            let Node::Object(fields) = &member.value else {
+                continue;
+            };
+            for field in fields {
+                let field_path = format!("{}.fields.{}", path, field.key);
+                self.keys.insert(field_path.clone(), field.key_span.clone());
+                self.add_field(text, &field_path, field);
+
+                if let Node::Object(_) = field.value {
+                    let nested = structs::generate_nested_struct_name(name, &field.key);
+                    self.containers.insert(nested.clone(), field_path.clone());
+                    self.add_struct(text, &nested, &field_path, &field.value);
+                }
+            }
+        }
+    }
+
+    fn add_field(&mut self, text: &str, path: &str, field: &Member) {
+        let end = match &field.value {
+            Node::String(value, span) => {
+                let reference = inner_span(text, span, value)
+                    .and_then(|inner| reference(value, inner.start, path));
+                if let Some(reference) = reference {
+                    self.references.push(reference);
+                }
+                span.end
+            }
+            // Nested objects are not hovered as a whole
+            _ => field.key_span.end,
+        };
+        self.fields.push(FieldSite {
+            path: path.to_owned(),
+            span: field.key_span.start..end,
+        });
+    }
+
+    /// The struct or enum name at `offset`, if any
+    pub fn symbol_at(&self, offset: usize) -> Option<&Symbol> {
+        self.definitions
+            .iter()
+            .chain(self.references.iter())
+            .find(|s| s.span.start <= offset && offset <= s.span.end)
+    }
+
+    pub fn field_at(&self, offset: usize) -> Option<&FieldSite> {
+        self.fields
+            .iter()
+            .find(|f| f.span.start <= offset && offset <= f.span.end)
+    }
+
+    pub fn definition(&self, kind: SymbolKind, name: &str) -> Option<&Symbol> {
+        self.definitions
+            .iter()
+            .find(|d| d.kind == kind && d.name == name)
+    }
+
+    pub fn references_to(&self, kind: SymbolKind, name: &str) -> Vec<&Symbol> {
+        self.references
+            .iter()
+            .filter(|r| r.kind == kind && r.name == name)
+            .collect()
+    }
+
+    /// The span of the part of the text that caused `error`
+    pub fn error_span(&self, error: &SchemaError) -> Option<Span> {
+        if let ErrorKind::InvalidReference(name) = &error.kind {
+            let field = match &error.offender {
+                Offender::Field(_, field) => field.as_str(),
+                _ => "",
+            };
+            let references = self
+                .references
+                .iter()
+                .filter(|r| &r.name == name)
+                .collect::<Vec<_>>();
+            return references
+                .iter()
+                .find(|r| r.path.rsplit('.').next() == Some(field))
+                .or(references.first())
+                .map(|r| r.span.clone());
+        }
+
+        let container = match &error.offender {
+            Offender::Field(parent, _) | Offender::Struct(parent) => {
+                self.containers.get(parent).cloned()
+            }
+            Offender::Enum(name) => Some(format!("enums.{}", name)),
+            Offender::Route(name) => Some(format!("routes.{}", name)),
+            Offender::File(_) => None,
+        };
+        let join = |key: &str| match &container {
+            Some(container) => format!("{}.{}", container, key),
+            None => key.to_owned(),
+        };
+
+        // The most precise key first
+        let mut candidates: Vec<String> = Vec::new();
+        if let ErrorKind::UnrecognizedField(key) = &error.kind {
+            candidates.push(join(key));
+        }
+        if let Offender::Field(_, field) = &error.offender {
+            candidates.push(join(&format!("fields.{}", field)));
+            candidates.push(join(field));
+        }
+        candidates.extend(container.clone());
+
+        candidates
+            .iter()
+            .find_map(|path| self.keys.get(path))
+            .cloned()
+    }
+}
+
+/// The struct or enum that a field expression like `?[]struct~Name; text`
+/// refers to. `start` is where the expression starts in the text.
+fn reference(value: &str, start: usize, path: &str) -> Option<Symbol> {
+    let expression = value.split(';').next().unwrap_or("");
+    let (dtype, rest) = expression.split_once('~')?;
+    let dtype = dtype.trim().trim_start_matches('?').trim_start();
+    let kind = match dtype.trim_start_matches("[]").trim() {
+        "struct" => SymbolKind::Struct,
+        "enum" => SymbolKind::Enum,
+        _ => return None,
+    };
+    let name = rest.split('~').next().unwrap_or("").trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let start = start + expression.len() - rest.trim_start().len();
+    Some(Symbol {
+        kind,
+        name: name.to_owned(),
+        span: start..start + name.len(),
+        path: path.to_owned(),
+    })
+}
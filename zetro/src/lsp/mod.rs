@@ -0,0 +1,418 @@
+//! Language server for schema files, see `zetro lsp`.
+//!
+//! Every schema format gets every feature. Go-to-definition, references and
+//! rename also look at the other schemas of the workspace: the open ones,
+//! and those named by a `zetro.toml` under a workspace folder. Schemas don't
+//! import each other, so types in other files match by kind and name.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{
+        Completion, GotoDefinition, HoverRequest, PrepareRenameRequest, References, Rename,
+        Request as RequestTrait,
+    },
+    CompletionOptions, GotoDefinitionResponse, HoverProviderCapability, InitializeParams, OneOf,
+    PrepareRenameResponse, PublishDiagnosticsParams, RenameOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use self::document::Document;
+
+mod document;
+mod features;
+pub(crate) mod index;
+mod tree;
+mod workspace;
+
+/// Serves the language server protocol on stdin and stdout until the client
+/// shuts the server down
+pub(crate) fn serve() -> Result<(), String> {
+    let (connection, io_threads) = Connection::stdio();
+    run(connection)?;
+    io_threads.join().map_err(|e| e.to_string())
+}
+
+fn run(connection: Connection) -> Result<(), String> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(
+                ["\"", "'", "?", "]", "~"]
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect(),
+            ),
+            ..CompletionOptions::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
+        ..ServerCapabilities::default()
+    };
+    let params = connection
+        .initialize(serde_json::to_value(capabilities).unwrap())
+        .map_err(|e| e.to_string())?;
+    let params = serde_json::from_value::<InitializeParams>(params).map_err(|e| e.to_string())?;
+    #[allow(deprecated)] // Older clients only send `root_uri`
+    let roots = match (params.workspace_folders, params.root_uri) {
+        (Some(folders), _) => folders.into_iter().map(|f| f.uri).collect(),
+        (None, Some(root)) => vec![root],
+        (None, None) => Vec::new(),
+    };
+
+    let mut server = Server {
+        connection: &connection,
+        documents: HashMap::new(),
+        roots: roots
+            .iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .collect(),
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection
+                    .handle_shutdown(&request)
+                    .map_err(|e| e.to_string())?
+                {
+                    return Ok(());
+                }
+                let response = server.request(request);
+                server.send(Message::Response(response));
+            }
+            Message::Notification(notification) => server.notification(notification),
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// Open documents
+    documents: HashMap<Url, Document>,
+    /// Workspace folders
+    roots: Vec<PathBuf>,
+}
+
+impl Server<'_> {
+    fn send(&self, message: Message) {
+        // The client is gone when sending fails, and the receiving loop ends
+        let _ = self.connection.sender.send(message);
+    }
+
+    fn notification(&mut self, notification: Notification) {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<lsp_types::DidOpenTextDocumentParams>(
+                    notification.params,
+                ) else {
+                    return;
+                };
+                let uri = params.text_document.uri;
+                let document = Document::new(uri.path(), params.text_document.text);
+                self.documents.insert(uri.clone(), document);
+                uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<lsp_types::DidChangeTextDocumentParams>(
+                    notification.params,
+                ) else {
+                    return;
+                };
+                // Changes are always the full text, see `TextDocumentSyncKind::FULL`
+                let (Some(change), Some(document)) = (
+                    params.content_changes.into_iter().last(),
+                    self.documents.get_mut(&params.text_document.uri),
+                ) else {
+                    return;
+                };
+                document.update(params.text_document.uri.path(), change.text);
+                params.text_document.uri
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Ok(params) = serde_json::from_value::<lsp_types::DidCloseTextDocumentParams>(
+                    notification.params,
+                ) {
+                    self.documents.remove(&params.text_document.uri);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        let diagnostics = self.documents[&uri].diagnostics.clone();
+        self.send(Message::Notification(Notification::new(
+            PublishDiagnostics::METHOD.to_owned(),
+            PublishDiagnosticsParams::new(uri, diagnostics, None),
+        )));
+    }
+
+    fn request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        match self.respond(request) {
+            Ok(result) => Response::new_ok(id, result),
+            Err((code, message)) => Response::new_err(id, code as i32, message),
+        }
+    }
+
+    fn respond(&self, request: Request) -> Result<serde_json::Value, (ErrorCode, String)> {
+        let result = match request.method.as_str() {
+            Completion::METHOD => {
+                let params = params::<Completion>(request)?;
+                let position = params.text_document_position;
+                let document = self.document(&position.text_document.uri)?;
+                serde_json::to_value(features::completion(document, position.position))
+            }
+            GotoDefinition::METHOD => {
+                let params = params::<GotoDefinition>(request)?;
+                let position = params.text_document_position_params;
+                let uri = &position.text_document.uri;
+                let document = self.document(uri)?;
+                let closed = self.closed_schemas();
+                serde_json::to_value(
+                    features::definition(
+                        document,
+                        uri,
+                        position.position,
+                        &self.others(uri, &closed),
+                    )
+                    .map(GotoDefinitionResponse::Scalar),
+                )
+            }
+            References::METHOD => {
+                let params = params::<References>(request)?;
+                let position = params.text_document_position;
+                let uri = &position.text_document.uri;
+                let document = self.document(uri)?;
+                let closed = self.closed_schemas();
+                serde_json::to_value(features::references(
+                    document,
+                    uri,
+                    position.position,
+                    params.context.include_declaration,
+                    &self.others(uri, &closed),
+                ))
+            }
+            HoverRequest::METHOD => {
+                let params = params::<HoverRequest>(request)?;
+                let position = params.text_document_position_params;
+                let document = self.document(&position.text_document.uri)?;
+                serde_json::to_value(features::hover(document, position.position))
+            }
+            PrepareRenameRequest::METHOD => {
+                let params = params::<PrepareRenameRequest>(request)?;
+                let document = self.document(&params.text_document.uri)?;
+                serde_json::to_value(
+                    features::prepare_rename(document, params.position)
+                        .map(PrepareRenameResponse::Range),
+                )
+            }
+            Rename::METHOD => {
+                let params = params::<Rename>(request)?;
+                let position = params.text_document_position;
+                let uri = &position.text_document.uri;
+                let document = self.document(uri)?;
+                let closed = self.closed_schemas();
+                let edit = features::rename(
+                    document,
+                    uri,
+                    position.position,
+                    &params.new_name,
+                    &self.others(uri, &closed),
+                )
+                .map_err(|e| (ErrorCode::InvalidParams, e))?;
+                serde_json::to_value(edit)
+            }
+            method => {
+                return Err((
+                    ErrorCode::MethodNotFound,
+                    format!("Unsupported method: {}", method),
+                ))
+            }
+        };
+        Ok(result.unwrap())
+    }
+
+    fn document(&self, uri: &Url) -> Result<&Document, (ErrorCode, String)> {
+        self.documents
+            .get(uri)
+            .ok_or_else(|| (ErrorCode::InvalidParams, format!("{} is not open", uri)))
+    }
+
+    /// The schemas of the workspace that are not open, read from the disk
+    fn closed_schemas(&self) -> Vec<(Url, Document)> {
+        let open = self
+            .documents
+            .keys()
+            .filter_map(|uri| uri.to_file_path().ok()?.canonicalize().ok())
+            .collect::<HashSet<_>>();
+        workspace::schema_files(&self.roots)
+            .into_iter()
+            .filter(|path| !open.contains(path))
+            .filter_map(|path| {
+                let text = std::fs::read_to_string(&path).ok()?;
+                let uri = Url::from_file_path(&path).ok()?;
+                Some((uri, Document::new(&path.to_string_lossy(), text)))
+            })
+            .collect()
+    }
+
+    /// Every schema of the workspace but the one at `uri`
+    fn others<'a>(
+        &'a self,
+        uri: &Url,
+        closed: &'a [(Url, Document)],
+    ) -> Vec<(&'a Url, &'a Document)> {
+        self.documents
+            .iter()
+            .filter(|(other, _)| *other != uri)
+            .chain(closed.iter().map(|(uri, document)| (uri, document)))
+            .collect()
+    }
+}
+
+/// Decodes the parameters of a request
+fn params<R: RequestTrait>(request: Request) -> Result<R::Params, (ErrorCode, String)> {
+    serde_json::from_value(request.params).map_err(|e| (ErrorCode::InvalidParams, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_server::{Connection, Message, Notification, Request, RequestId};
+    use serde_json::json;
+
+    /// A client opens a schema, gets its diagnostics, asks for a definition,
+    /// renames a type that a closed schema of the workspace also uses and
+    /// shuts the server down
+    #[test]
+    fn serves() {
+        let (server, client) = Connection::memory();
+        let thread = std::thread::spawn(move || super::run(server));
+        let request = |id: i32, method: &str, params| {
+            client
+                .sender
+                .send(Message::Request(Request::new(
+                    RequestId::from(id),
+                    method.to_owned(),
+                    params,
+                )))
+                .unwrap();
+        };
+        let notify = |method: &str, params| {
+            client
+                .sender
+                .send(Message::Notification(Notification::new(
+                    method.to_owned(),
+                    params,
+                )))
+                .unwrap();
+        };
+        let uri = "file:///schema.json";
+
+        // A closed schema in the workspace
+        let root = std::env::temp_dir().join(format!("zetro-lsp-server-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("zetro.toml"),
+            "schema = \"schema.yaml\"\n[[targets]]\nout-file = \"code_generated.rs\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("schema.yaml"), "enums:\n  B: [X]\n").unwrap();
+        let root_uri = lsp_types::Url::from_directory_path(&root).unwrap();
+
+        request(
+            1,
+            "initialize",
+            json!({ "capabilities": {}, "rootUri": root_uri }),
+        );
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected the initialize response");
+        };
+        assert_eq!(
+            response.result.unwrap()["capabilities"]["renameProvider"]["prepareProvider"],
+            true
+        );
+        notify("initialized", json!({}));
+
+        let text =
+            r#"{ "structs": { "A": { "description": "", "fields": { "b": "struct~B" } } } }"#;
+        notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "json", "version": 1, "text": text } }),
+        );
+        let Message::Notification(diagnostics) = client.receiver.recv().unwrap() else {
+            panic!("expected diagnostics");
+        };
+        assert_eq!(diagnostics.method, "textDocument/publishDiagnostics");
+        assert_eq!(
+            diagnostics.params["diagnostics"][0]["range"]["start"]["character"],
+            text.find("B\"").unwrap()
+        );
+
+        let text = text.replace("} } } }", "} } }, \"enums\": { \"B\": [\"X\"] } }");
+        let text = text.replace("struct~B", "enum~B");
+        notify(
+            "textDocument/didChange",
+            json!({ "textDocument": { "uri": uri, "version": 2 }, "contentChanges": [{ "text": text }] }),
+        );
+        let Message::Notification(diagnostics) = client.receiver.recv().unwrap() else {
+            panic!("expected diagnostics");
+        };
+        assert_eq!(diagnostics.params["diagnostics"], json!([]));
+
+        let position = json!({ "line": 0, "character": text.find("B\"").unwrap() });
+        request(
+            2,
+            "textDocument/definition",
+            json!({ "textDocument": { "uri": uri }, "position": position }),
+        );
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(
+            response.result.unwrap()["range"]["start"]["character"],
+            text.rfind("B\"").unwrap()
+        );
+
+        request(
+            3,
+            "textDocument/rename",
+            json!({ "textDocument": { "uri": uri }, "position": position, "newName": "C" }),
+        );
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected a response");
+        };
+        let changes = &response.result.unwrap()["changes"];
+        assert_eq!(changes[uri].as_array().unwrap().len(), 2);
+        let closed =
+            lsp_types::Url::from_file_path(root.join("schema.yaml").canonicalize().unwrap());
+        assert_eq!(changes[closed.unwrap().as_str()][0]["newText"], "C");
+        std::fs::remove_dir_all(&root).unwrap();
+
+        request(4, "textDocument/formatting", json!({}));
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected a response");
+        };
+        assert!(response.error.is_some());
+
+        request(5, "shutdown", json!(null));
+        client.receiver.recv().unwrap();
+        notify("exit", json!(null));
+        thread.join().unwrap().unwrap();
+    }
+}
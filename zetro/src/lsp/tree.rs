@@ -0,0 +1,305 @@
+//! Value trees of JSON, JSON5, YAML and TOML schemas that keep the spans of
+//! keys and strings, which the decoders of `common::source` drop.
+
+use saphyr_parser::Event;
+
+use crate::common::source::SchemaFormat;
+
+/// A byte range in the text of a document
+pub(crate) type Span = std::ops::Range<usize>;
+
+/// A value with the spans of its parts. Spans of quoted strings and keys
+/// include the quotes.
+pub(crate) enum Node {
+    Object(Vec<Member>),
+    String(String, Span),
+    /// Lists, numbers, booleans and null
+    Other,
+}
+
+pub(crate) struct Member {
+    pub key: String,
+    pub key_span: Span,
+    pub value: Node,
+}
+
+/// Parses a schema into a tree. `None` if the text doesn't decode, and for
+/// `.zetro` files, which have their own syntax tree.
+pub(crate) fn parse(text: &str, format: SchemaFormat) -> Option<Node> {
+    match format {
+        SchemaFormat::Json | SchemaFormat::Json5 => {
+            // The decoders report the errors, this only has to find the spans
+            let json5 = format == SchemaFormat::Json5;
+            let valid = match json5 {
+                true => json5::from_str::<serde_json::Value>(text).is_ok(),
+                false => serde_json::from_str::<serde::de::IgnoredAny>(text).is_ok(),
+            };
+            if !valid {
+                return None;
+            }
+            let mut parser = JsonParser {
+                text,
+                offset: 0,
+                json5,
+            };
+            parser.value()
+        }
+        SchemaFormat::Yaml => {
+            // Markers count chars, spans count bytes
+            let offsets = text
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([text.len()])
+                .collect();
+            let mut parser = YamlParser {
+                events: saphyr_parser::Parser::new_from_str(text),
+                offsets,
+            };
+            parser.document()
+        }
+        SchemaFormat::Toml => {
+            let document = toml_edit::ImDocument::parse(text).ok()?;
+            Some(toml_table(document.as_table()))
+        }
+        SchemaFormat::Zetro => None,
+    }
+}
+
+/// The span of `value` inside `span`, which is either `value` itself or
+/// `value` in quotes. `None` if the text differs from the value, eg. because
+/// of escapes.
+pub(crate) fn inner_span(text: &str, span: &Span, value: &str) -> Option<Span> {
+    let raw = text.get(span.clone())?;
+    if raw == value {
+        return Some(span.clone());
+    }
+    let quoted = raw.len() == value.len() + 2 && raw.get(1..raw.len() - 1) == Some(value);
+    quoted.then(|| span.start + 1..span.end - 1)
+}
+
+/// Parses JSON, or JSON5 with its comments, unquoted keys and single quotes.
+/// The text must be valid.
+struct JsonParser<'a> {
+    text: &'a str,
+    offset: usize,
+    json5: bool,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = &self.text[self.offset..];
+            let trimmed = rest.trim_start();
+            self.offset += rest.len() - trimmed.len();
+            if !self.json5 {
+                return;
+            }
+            let comment = if trimmed.starts_with("//") {
+                trimmed.find('\n').unwrap_or(trimmed.len())
+            } else if trimmed.starts_with("/*") {
+                trimmed.find("*/").map(|i| i + 2).unwrap_or(trimmed.len())
+            } else {
+                return;
+            };
+            self.offset += comment;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.offset).copied()
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b'{' => {
+                self.offset += 1;
+                let mut members: Vec<Member> = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        b'}' => break,
+                        b',' => self.offset += 1,
+                        _ => {
+                            let (key, key_span) = self.key()?;
+                            self.skip_whitespace();
+                            self.offset += 1; // ':'
+                            let value = self.value()?;
+                            members.push(Member {
+                                key,
+                                key_span,
+                                value,
+                            });
+                        }
+                    }
+                }
+                self.offset += 1;
+                Some(Node::Object(members))
+            }
+            b'[' => {
+                self.offset += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        b']' => break,
+                        b',' => self.offset += 1,
+                        _ => {
+                            self.value()?;
+                        }
+                    }
+                }
+                self.offset += 1;
+                Some(Node::Other)
+            }
+            b'"' | b'\'' => {
+                let (value, span) = self.string()?;
+                Some(Node::String(value, span))
+            }
+            _ => {
+                self.offset += self.bare_len();
+                Some(Node::Other)
+            }
+        }
+    }
+
+    /// Reads a key, which JSON5 allows without quotes
+    fn key(&mut self) -> Option<(String, Span)> {
+        match self.peek()? {
+            b'"' | b'\'' => self.string(),
+            _ => {
+                let start = self.offset;
+                self.offset += self.bare_len();
+                let span = start..self.offset;
+                Some((self.text[span.clone()].to_owned(), span))
+            }
+        }
+    }
+
+    /// Length of the number, literal or unquoted key at the offset
+    fn bare_len(&self) -> usize {
+        let rest = &self.text[self.offset..];
+        rest.find(|c: char| ",:]}/".contains(c) || c.is_whitespace())
+            .unwrap_or(rest.len())
+    }
+
+    /// Reads a string, and returns it decoded with its span
+    fn string(&mut self) -> Option<(String, Span)> {
+        let start = self.offset;
+        let quote = self.text[start..].chars().next()?;
+        let mut escaped = false;
+        for (i, c) in self.text[start + 1..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                c if c == quote => {
+                    self.offset = start + 1 + i + 1;
+                    let span = start..self.offset;
+                    let raw = &self.text[span.clone()];
+                    let value = match self.json5 {
+                        true => json5::from_str(raw).ok()?,
+                        false => serde_json::from_str(raw).ok()?,
+                    };
+                    return Some((value, span));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Builds a tree from the events of the first YAML document
+struct YamlParser<'a> {
+    events: saphyr_parser::Parser<'a, saphyr_parser::StrInput<'a>>,
+    /// Byte offset of every char, and of the end of the text
+    offsets: Vec<usize>,
+}
+
+impl<'a> YamlParser<'a> {
+    fn next(&mut self) -> Option<(Event<'a>, Span)> {
+        let (event, span) = self.events.next_event()?.ok()?;
+        let start = *self.offsets.get(span.start.index())?;
+        let end = *self.offsets.get(span.end.index())?;
+        Some((event, start..end))
+    }
+
+    fn document(&mut self) -> Option<Node> {
+        loop {
+            match self.next()? {
+                (Event::StreamStart | Event::DocumentStart(_), _) => {}
+                (event, span) => return self.node(event, span),
+            }
+        }
+    }
+
+    /// The node that starts with `event`
+    fn node(&mut self, event: Event<'a>, span: Span) -> Option<Node> {
+        match event {
+            Event::Scalar(value, ..) => Some(Node::String(value.into_owned(), span)),
+            Event::MappingStart(..) => {
+                let mut members: Vec<Member> = Vec::new();
+                loop {
+                    let (event, key_span) = self.next()?;
+                    let key = match event {
+                        Event::MappingEnd => break,
+                        Event::Scalar(key, ..) => Some(key.into_owned()),
+                        // Keys that are lists or mappings
+                        event => {
+                            self.node(event, key_span.clone())?;
+                            None
+                        }
+                    };
+                    let (event, span) = self.next()?;
+                    let value = self.node(event, span)?;
+                    if let Some(key) = key {
+                        members.push(Member {
+                            key,
+                            key_span,
+                            value,
+                        });
+                    }
+                }
+                Some(Node::Object(members))
+            }
+            Event::SequenceStart(..) => {
+                loop {
+                    match self.next()? {
+                        (Event::SequenceEnd, _) => break,
+                        (event, span) => self.node(event, span)?,
+                    };
+                }
+                Some(Node::Other)
+            }
+            Event::Alias(_) => Some(Node::Other),
+            _ => None,
+        }
+    }
+}
+
+fn toml_table(table: &dyn toml_edit::TableLike) -> Node {
+    let members = table
+        .iter()
+        .filter_map(|(name, item)| {
+            let (key, _) = table.get_key_value(name)?;
+            Some(Member {
+                key: name.to_owned(),
+                key_span: key.span()?,
+                value: toml_item(item),
+            })
+        })
+        .collect();
+    Node::Object(members)
+}
+
+fn toml_item(item: &toml_edit::Item) -> Node {
+    if let Some(table) = item.as_table_like() {
+        return toml_table(table);
+    }
+    match item.as_value() {
+        Some(toml_edit::Value::String(value)) => match value.span() {
+            Some(span) => Node::String(value.value().clone(), span),
+            None => Node::Other,
+        },
+        _ => Node::Other,
+    }
+}
@@ -0,0 +1,79 @@
+//! The schemas of the workspace, for references and renames across files
+
+use std::path::{Path, PathBuf};
+
+use crate::project::{ProjectConfig, CONFIG_FILE_NAME};
+
+/// Directories that hold dependencies or build outputs, not configs
+const SKIPPED_DIRS: [&str; 2] = ["node_modules", "target"];
+
+/// The schema files named by the `zetro.toml` files under `roots`, as
+/// canonical paths. Hidden directories are skipped, and symbolic links are
+/// not followed.
+pub(crate) fn schema_files(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut configs: Vec<PathBuf> = Vec::new();
+    let mut dirs = roots.to_vec();
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    dirs.push(entry.path());
+                }
+            } else if name == CONFIG_FILE_NAME {
+                configs.push(entry.path());
+            }
+        }
+    }
+
+    // Invalid configs are reported by the commands that use them
+    let mut schemas = configs
+        .iter()
+        .filter_map(|config| ProjectConfig::read(&config.to_string_lossy()).ok())
+        .filter_map(|config| Path::new(&config.schema_file).canonicalize().ok())
+        .collect::<Vec<_>>();
+    schemas.sort();
+    schemas.dedup();
+    schemas
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    /// Schemas are found through the configs of every project, but not in
+    /// hidden or build directories
+    #[test]
+    fn finds_schemas() {
+        let root = std::env::temp_dir().join(format!("zetro-lsp-workspace-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let config = "schema = \"schema.json\"\n[[targets]]\nout-file = \"code_generated.rs\"\n";
+        for dir in ["server", "client/app", ".git/modules", "node_modules/dep"] {
+            let dir = root.join(dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("zetro.toml"), config).unwrap();
+            std::fs::write(dir.join("schema.json"), "{}").unwrap();
+        }
+        // Points at a schema that doesn't exist
+        std::fs::create_dir_all(root.join("broken")).unwrap();
+        std::fs::write(root.join("broken/zetro.toml"), config).unwrap();
+
+        let schemas = super::schema_files(std::slice::from_ref(&root));
+        let root = root.canonicalize().unwrap();
+        assert_eq!(
+            schemas,
+            vec![
+                root.join("client/app/schema.json"),
+                root.join("server/schema.json")
+            ]
+        );
+        assert!(super::schema_files(&[PathBuf::from("/does/not/exist")]).is_empty());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
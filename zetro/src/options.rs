@@ -0,0 +1,119 @@
+use convert_case::Case;
+
+use crate::utilities::{EmitLang, PluginArg, PluginCall, ZetroArgs};
+
+/// Language of the generated code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Rust,
+    TypeScript,
+}
+
+/// Casing of field names in generated code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldCasing {
+    Snake,
+    Camel,
+}
+
+/// A plugin to run after the types are generated, eg. `warp` for Rust or
+/// `class-client` for TypeScript
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plugin {
+    pub name: String,
+    pub args: std::collections::BTreeMap<String, PluginArg>,
+}
+
+impl Plugin {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_lowercase(),
+            args: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Adds an argument, eg. `Plugin::new("warp").arg("fnv", true)`
+    pub fn arg(mut self, key: &str, value: impl Into<PluginArg>) -> Self {
+        self.args.insert(key.to_lowercase(), value.into());
+        self
+    }
+}
+
+/// Options of a generator. These are the same as the options of a target in
+/// `zetro.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub language: Language,
+    /// Encode structs as arrays instead of objects
+    pub untagged: bool,
+    /// Can't be combined with `untagged`
+    pub field_casing: Option<FieldCasing>,
+    /// Mangle names in generated TypeScript. Not supported for Rust.
+    pub mangle: Option<bool>,
+    /// Generate types that no route uses
+    pub keep_unused_types: bool,
+    /// Text to put at the top of the generated code
+    pub header: Option<String>,
+    /// Start generated Rust with `#![allow(dead_code)]` when unused types are
+    /// kept. Turn this off for code that is `include!`d, and allow dead code
+    /// on the including module instead.
+    pub inner_attributes: bool,
+    /// A template to render instead of generating the types, see
+    /// `docs/templates.md`. Plugins still run after it.
+    pub template: Option<std::path::PathBuf>,
+    pub plugins: Vec<Plugin>,
+}
+
+impl Options {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            untagged: false,
+            field_casing: None,
+            mangle: None,
+            keep_unused_types: false,
+            header: None,
+            inner_attributes: true,
+            template: None,
+            plugins: Vec::new(),
+        }
+    }
+
+    /// The options in the form the generators take. There are no files
+    /// involved, so the paths are left empty.
+    pub(crate) fn to_args(&self) -> Result<ZetroArgs, String> {
+        let field_casing = self.field_casing.map(|casing| match casing {
+            FieldCasing::Snake => Case::Snake,
+            FieldCasing::Camel => Case::Camel,
+        });
+        crate::utilities::check_target("", &field_casing, self.untagged, true)?;
+
+        Ok(ZetroArgs {
+            schema_file: String::new(),
+            out_file: String::new(),
+            language: match self.language {
+                Language::Rust => EmitLang::Rust,
+                Language::TypeScript => EmitLang::Typescript,
+            },
+            field_casing,
+            plugins: self
+                .plugins
+                .iter()
+                .map(|plugin| PluginCall {
+                    name: plugin.name.clone(),
+                    args: plugin.args.clone().into_iter().collect(),
+                })
+                .collect(),
+            mangle: self.mangle,
+            untagged: self.untagged,
+            keep_unused_types: self.keep_unused_types,
+            header: self.header.clone(),
+            split: false,
+            template: self
+                .template
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned()),
+            inner_attributes: self.inner_attributes,
+        })
+    }
+}
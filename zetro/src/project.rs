@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, HashMap};
+
+use indexmap::IndexMap;
+
+use crate::utilities::{self, PluginArg, PluginCall, ZetroArgs};
+
+/// Name of the project config file `zetro generate` looks for by default
+pub(crate) const CONFIG_FILE_NAME: &str = "zetro.toml";
+
+/// A project config (`zetro.toml`): one schema and the files generated from
+/// it. For example:
+///
+/// ```toml
+/// schema = "schema.json"
+///
+/// [[targets]]
+/// out-file = "server/src/generated/code_generated.rs"
+/// untagged = true
+/// plugins = { warp = { fnv = true } }
+///
+/// [[targets]]
+/// out-file = "client/src/generated/code_generated.ts"
+/// untagged = true
+/// header = "/* eslint-disable */"
+/// plugins = { class-client = {} }
+/// ```
+///
+/// Relative paths are resolved against the directory of the config file.
+pub(crate) struct ProjectConfig {
+    pub schema_file: String,
+    pub targets: Vec<ZetroArgs>,
+}
+
+#[derive(serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    schema: String,
+    targets: Vec<RawTarget>,
+}
+
+#[derive(serde_derive::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawTarget {
+    out_file: Option<String>,
+    /// Split output, see `ZetroArgs::split`
+    out_dir: Option<String>,
+    lang: Option<String>,
+    field_casing: Option<String>,
+    mangle: Option<bool>,
+    #[serde(default)]
+    untagged: bool,
+    #[serde(default)]
+    keep_unused_types: bool,
+    #[serde(default)]
+    ignore_out_naming: bool,
+    header: Option<String>,
+    /// Template to render instead of generating the types
+    template: Option<String>,
+    /// Plugin name -> arguments. Plugins run in the order they are declared.
+    #[serde(default)]
+    plugins: IndexMap<String, BTreeMap<String, toml::Value>>,
+}
+
+impl ProjectConfig {
+    pub fn read(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: error reading file: {}", path, e))?;
+        let base_dir = std::path::Path::new(path)
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Self::from_toml(&contents, &base_dir).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parses a config file. Relative paths are joined to `base_dir`.
+    pub fn from_toml(contents: &str, base_dir: &str) -> Result<Self, String> {
+        let raw = toml::from_str::<RawConfig>(contents).map_err(|e| e.to_string())?;
+        let resolve = |path: &str| {
+            std::path::Path::new(base_dir)
+                .join(path)
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        if raw.targets.is_empty() {
+            return Err(String::from("Expected at least one entry in `targets`"));
+        }
+
+        let schema_file = resolve(&raw.schema);
+        let mut targets: Vec<ZetroArgs> = Vec::new();
+
+        for (i, target) in raw.targets.into_iter().enumerate() {
+            let error = |e: String| format!("targets[{}]: {}", i, e);
+
+            let language = match &target.lang {
+                Some(v) => Some(utilities::EmitLang::from_ext(v).ok_or_else(|| {
+                    error(format!(
+                        "Expected language identifier to be one of: {}\nGot: '{}'",
+                        "'ts', 'tsx', 'rs'", v
+                    ))
+                })?),
+                None => None,
+            };
+            let out_file = match (&target.out_file, &target.out_dir) {
+                (Some(file), None) => file.clone(),
+                (None, Some(dir)) => utilities::split_index_file(dir, language).map_err(error)?,
+                _ => {
+                    return Err(error(String::from(
+                        "Expected one of `out-file` or `out-dir`",
+                    )))
+                }
+            };
+            let language = match language {
+                Some(v) => v,
+                None => utilities::guess_language(&out_file).map_err(error)?,
+            };
+            let field_casing = match &target.field_casing {
+                Some(v) => Some(utilities::parse_casing(v).map_err(error)?),
+                None => None,
+            };
+            utilities::check_target(
+                &out_file,
+                &field_casing,
+                target.untagged,
+                target.ignore_out_naming,
+            )
+            .map_err(error)?;
+
+            let mut plugins: Vec<PluginCall> = Vec::new();
+            for (name, args) in target.plugins {
+                let mut plugin = PluginCall {
+                    name: name.to_lowercase(),
+                    args: HashMap::new(),
+                };
+                for (key, value) in args {
+                    let value = match value {
+                        toml::Value::Boolean(v) => PluginArg::Bool(v),
+                        toml::Value::Integer(v) => PluginArg::Int(v),
+                        toml::Value::Float(v) => PluginArg::Float(v),
+                        toml::Value::String(v) => PluginArg::Str(v),
+                        other => {
+                            return Err(error(format!(
+                                "Invalid value for argument `{}` of plugin '{}'. \
+                                Expected a boolean, number or string. Got: {}",
+                                key, name, other
+                            )))
+                        }
+                    };
+                    plugin.args.insert(key.to_lowercase(), value);
+                }
+                plugins.push(plugin);
+            }
+
+            targets.push(ZetroArgs {
+                schema_file: schema_file.clone(),
+                out_file: resolve(&out_file),
+                language,
+                field_casing,
+                plugins,
+                mangle: target.mangle,
+                untagged: target.untagged,
+                keep_unused_types: target.keep_unused_types,
+                header: target.header,
+                template: target.template.as_deref().map(resolve),
+                split: target.out_dir.is_some(),
+                inner_attributes: true,
+            });
+        }
+
+        Ok(Self {
+            schema_file,
+            targets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProjectConfig;
+    use crate::utilities::{EmitLang, PluginArg};
+
+    #[test]
+    fn parses_targets() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            schema = "api/schema.zetro"
+
+            [[targets]]
+            out-file = "server/code_generated.rs"
+            untagged = true
+            plugins = { warp = { fnv = true, workers = 4 }, proptest = {} }
+
+            [[targets]]
+            lang = "ts"
+            out-file = "client/api.ts"
+            ignore-out-naming = true
+            field-casing = "snake"
+            header = "/* eslint-disable */"
+            template = "templates/client.ts.j2"
+
+            [[targets]]
+            lang = "ts"
+            out-dir = "web/api_generated"
+            "#,
+            "project",
+        )
+        .unwrap();
+
+        assert_eq!(config.schema_file, "project/api/schema.zetro");
+        assert_eq!(config.targets.len(), 3);
+
+        let server = &config.targets[0];
+        assert!(matches!(server.language, EmitLang::Rust));
+        assert_eq!(server.out_file, "project/server/code_generated.rs");
+        assert!(server.untagged);
+        assert_eq!(server.plugins[0].name, "warp");
+        assert_eq!(server.plugins[0].args["fnv"], PluginArg::Bool(true));
+        assert_eq!(server.plugins[0].args["workers"], PluginArg::Int(4));
+        // In the order they are declared
+        assert_eq!(server.plugins[1].name, "proptest");
+
+        let client = &config.targets[1];
+        assert!(matches!(client.language, EmitLang::Typescript));
+        assert!(client.field_casing.is_some());
+        assert_eq!(client.header.as_deref(), Some("/* eslint-disable */"));
+        assert_eq!(
+            client.template.as_deref(),
+            Some("project/templates/client.ts.j2")
+        );
+        assert_eq!(server.template, None);
+
+        let split = &config.targets[2];
+        assert!(split.split && !client.split);
+        assert_eq!(split.out_file, "project/web/api_generated/index.ts");
+    }
+
+    /// Targets are validated like command line arguments
+    #[test]
+    fn rejects_invalid_targets() {
+        for config in [
+            "schema = \"s.json\"\ntargets = []",
+            "schema = \"s.json\"\n[[targets]]\nout-file = \"code.rs\"",
+            "schema = \"s.json\"\n[[targets]]\nout-file = \"code_generated.py\"",
+            "schema = \"s.json\"\n[[targets]]\nout-file = \"a_generated.rs\"\nuntagged = true\nfield-casing = \"camel\"",
+            "schema = \"s.json\"\n[[targets]]\nout-file = \"a_generated.rs\"\nplugins = { warp = { fnv = [1] } }",
+            "schema = \"s.json\"\n[[targets]]\nout-file = \"a_generated.rs\"\nunknown = 1",
+            "schema = \"s.json\"\n[[targets]]\nout-dir = \"api_generated\"",
+            "schema = \"s.json\"\n[[targets]]\nlang = \"rs\"\nout-dir = \"api_generated\"\nout-file = \"a_generated.rs\"",
+        ] {
+            assert!(
+                ProjectConfig::from_toml(config, "").is_err(),
+                "expected an error for: {}",
+                config
+            );
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use convert_case::Case;
 
+use crate::common::fake::FakeOptions;
+
 pub(super) struct ZetroArgs {
     pub schema_file: String, // Path to schema file
     pub out_file: String,    // Path to output file
@@ -8,13 +10,147 @@ pub(super) struct ZetroArgs {
     pub plugins: Vec<PluginCall>,
     pub mangle: Option<bool>,
     pub untagged: bool,
+    pub keep_unused_types: bool,  // Generate types that no route uses
+    pub header: Option<String>,   // Text to put at the top of the output file
+    pub template: Option<String>, // Template to render instead of generating the types
+    // Whether to write one module per route next to `out_file`, which is then
+    // the index (`mod.rs` or `index.ts`)
+    pub split: bool,
+    // Whether generated Rust may start with `#![allow(dead_code)]`. Code that
+    // is `include!`d can't contain inner attributes.
+    pub inner_attributes: bool,
+}
+
+/// Arguments for `zetro convert`
+pub(super) struct ConvertArgs {
+    pub schema_file: String, // Path to the schema to convert
+    pub out_file: String,    // Path to write to. Its extension picks the format
+}
+
+/// Arguments for `zetro fmt`
+pub(super) struct FmtArgs {
+    pub schema_file: String,  // Path to the schema to format in place
+    pub check: bool,          // Only report whether the file is formatted
+    pub strip_comments: bool, // Format even if comments would be lost
+}
+
+/// Where `zetro generate`, `zetro check` and `zetro diff` get their targets
+/// from
+pub(super) enum TargetSource {
+    Config(String),    // Path to `zetro.toml`
+    Target(ZetroArgs), // A single target passed as options
+}
+
+/// Where `zetro inspect` gets the schema from
+pub(super) enum SchemaSource {
+    Schema(String), // Path to a schema file
+    Config(String), // Path to `zetro.toml`, which names the schema
+}
+
+/// Arguments for `zetro lint`
+pub(super) struct InspectArgs {
+    pub source: SchemaSource,
+    pub direction: Option<PayloadDirection>, // Decode a payload from stdin instead
+}
+
+pub(super) struct EncodeArgs {
+    pub source: SchemaSource,
+    pub untagged: bool, // Encode structs as arrays
+}
+
+pub(super) struct CallArgs {
+    pub source: SchemaSource,
+    pub url: String,
+    pub headers: Vec<(String, String)>, // Extra HTTP headers, eg. for authentication
+    pub untagged: bool,
+    pub calls: Vec<(String, String)>, // Route names with their JSON request bodies
+}
+
+pub(super) struct MockArgs {
+    pub source: SchemaSource,
+    pub host: String,
+    pub port: u16,
+    pub fixtures_file: Option<String>, // Responses to serve instead of fake data
+    pub untagged: bool,
+    pub seed: u64, // Seed of the fake data
+}
+
+pub(super) struct FixturesArgs {
+    pub source: SchemaSource,
+    pub struct_name: String,
+    pub count: usize,
+    pub untagged: bool,
+    pub options: FakeOptions,
+}
+
+pub(super) enum PayloadDirection {
+    Request,
+    Response,
+}
+
+pub(super) struct LintArgs {
+    pub schema_file: String,         // Path to the schema to lint
+    pub config_file: Option<String>, // Path to the lint config, if given
+    pub format: LintFormat,
+}
+
+pub(super) enum LintFormat {
+    Human,
+    Json,
 }
 
 pub(super) struct PluginCall {
     pub name: String,
-    pub args: std::collections::HashMap<String, String>,
+    pub args: std::collections::HashMap<String, PluginArg>,
 }
 
+/// A plugin argument. Arguments passed on the command line are always
+/// strings, while `zetro.toml` keeps the type written in the file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginArg {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl From<bool> for PluginArg {
+    fn from(v: bool) -> Self {
+        PluginArg::Bool(v)
+    }
+}
+
+impl From<i64> for PluginArg {
+    fn from(v: i64) -> Self {
+        PluginArg::Int(v)
+    }
+}
+
+impl From<f64> for PluginArg {
+    fn from(v: f64) -> Self {
+        PluginArg::Float(v)
+    }
+}
+
+impl From<&str> for PluginArg {
+    fn from(v: &str) -> Self {
+        PluginArg::Str(v.to_owned())
+    }
+}
+
+impl PluginArg {
+    /// Reads a boolean argument. Strings are accepted in any form
+    /// `parse_bool` understands.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PluginArg::Bool(v) => Some(*v),
+            PluginArg::Str(v) => parse_bool(&v.to_lowercase()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(super) enum EmitLang {
     Rust,
     Typescript,
@@ -41,7 +177,86 @@ pub(crate) fn parse_bool(val: &str) -> Option<bool> {
     None
 }
 
-pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
+/// An option passed on the command line
+pub(super) struct Flag {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// Splits command line arguments into options. Options listed in
+/// `value_flags` take a value, written either as `--name=value` or as
+/// `--name value`. Options listed in `switches` take no value.
+pub(super) fn parse_flags(
+    args: &[String],
+    value_flags: &[&str],
+    switches: &[&str],
+) -> Result<Vec<Flag>, String> {
+    let (flags, positional) = parse_flags_and_args(args, value_flags, switches)?;
+    match positional.first() {
+        Some(arg) => Err(format!("Unexpected argument: '{}'", arg)),
+        None => Ok(flags),
+    }
+}
+
+/// Like `parse_flags`, but also returns the arguments that are not options,
+/// in order
+pub(super) fn parse_flags_and_args(
+    args: &[String],
+    value_flags: &[&str],
+    switches: &[&str],
+) -> Result<(Vec<Flag>, Vec<String>), String> {
+    let mut flags: Vec<Flag> = Vec::new();
+    let mut positional: Vec<String> = Vec::new();
+    let mut args = args.iter().peekable();
+
+    while let Some(arg) = args.next() {
+        // Checked before splitting, as JSON arguments may contain `=`
+        if !arg.starts_with('-') {
+            positional.push(arg.clone());
+            continue;
+        }
+        let (name, mut value) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_owned())),
+            None => (arg.as_str(), None),
+        };
+
+        if value_flags.contains(&name) {
+            if value.is_none() {
+                value = args.next_if(|v| !v.starts_with("--")).cloned();
+            }
+        } else if switches.contains(&name) {
+            if value.is_some() {
+                return Err(format!("Option '{}' does not take a value", name));
+            }
+        } else {
+            return Err(format!("Unrecognized option: '{}'", name));
+        }
+
+        flags.push(Flag {
+            name: name.to_owned(),
+            value,
+        });
+    }
+
+    Ok((flags, positional))
+}
+
+/// Options of a single generation target, see `parse_args`
+const TARGET_FLAGS: [&str; 9] = [
+    "--schema",
+    "--out-file",
+    "--out-dir",
+    "--field-casing",
+    "--mangle",
+    "--untagged",
+    "--add-plugin",
+    "--lang",
+    "--template",
+];
+const TARGET_SWITCHES: [&str; 2] = ["--ignore-out-naming", "--keep-unused-types"];
+
+/// Parses the options of a single generation target
+pub(super) fn parse_args(args: &[String]) -> Result<ZetroArgs, String> {
     let plugin_call_args =
         regex::Regex::new(r"(?i)([a-z0-9\-]+)(\(([a-z0-9\-]+:[a-z0-9\-]+ ?)+\))?").unwrap();
 
@@ -57,16 +272,19 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
     // {file}-generated.{ext} to avoid overwriting actual code.
     // This variable allows `out_file` to have any name
     let mut ignore_naming_convention = false;
+    // Types that no route uses are dropped unless this is set
+    let mut keep_unused_types = false;
+    let mut template: Option<String> = None; // Path to a template
+    let mut out_dir: Option<String> = None; // Directory for split output
 
-    for arg in &args[1..] {
-        let keyval = arg.split("=").collect::<Vec<_>>();
-        let key = keyval[0];
+    for flag in parse_flags(args, &TARGET_FLAGS, &TARGET_SWITCHES)? {
+        let value = flag.value.as_deref();
 
-        match key {
+        match flag.name.as_str() {
             "--schema" => {
                 schema_file = Some(
-                    match keyval.get(1) {
-                        Some(&v) => v,
+                    match value {
+                        Some(v) => v,
                         None => return Err(String::from("Expected path to schema file")),
                     }
                     .to_owned(),
@@ -74,31 +292,40 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
             }
             "--out-file" => {
                 out_file = Some(
-                    match keyval.get(1) {
-                        Some(&v) => v,
+                    match value {
+                        Some(v) => v,
                         None => return Err(String::from("Expected path to output file")),
                     }
                     .to_owned(),
                 );
             }
+            "--out-dir" => {
+                out_dir = Some(
+                    match value {
+                        Some(v) => v,
+                        None => return Err(String::from("Expected path to output directory")),
+                    }
+                    .to_owned(),
+                );
+            }
+            "--template" => {
+                template = Some(
+                    match value {
+                        Some(v) => v,
+                        None => return Err(String::from("Expected path to template file")),
+                    }
+                    .to_owned(),
+                );
+            }
             "--field-casing" => {
-                field_casing = match keyval.get(1) {
-                    Some(&v) => match v.to_lowercase().as_str() {
-                        "snake" => Some(Case::Snake),
-                        "camel" => Some(Case::Camel),
-                        _ => {
-                            return Err(format!(
-                                "Expected field casing to be one of: {}\nGot: '{}'",
-                                "'snake', 'camel'", v
-                            ))
-                        }
-                    },
+                field_casing = match value {
+                    Some(v) => Some(parse_casing(v)?),
                     None => return Err(String::from("Expected arguments for field casing.")),
                 };
             }
             "--mangle" => {
-                should_mangle = match keyval.get(1) {
-                    Some(&v) => {
+                should_mangle = match value {
+                    Some(v) => {
                         let v = parse_bool(&v.to_lowercase());
                         if v.is_some() {
                             v
@@ -110,8 +337,8 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
                 };
             }
             "--untagged" => {
-                untagged_repr = match keyval.get(1) {
-                    Some(&v) => {
+                untagged_repr = match value {
+                    Some(v) => {
                         let v = parse_bool(&v.to_lowercase());
                         if let Some(v) = v {
                             v
@@ -129,8 +356,8 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
                 // keys and values.
                 // Plugins can also be empty, like this:
                 // --add-plugin=empty_plugin() or --add-plugin=empty_plugin
-                let plugin_call = match keyval.get(1) {
-                    Some(&v) => v,
+                let plugin_call = match value {
+                    Some(v) => v,
                     None => {
                         return Err(String::from("--plugin must be followed by a plugin call."))
                     }
@@ -173,7 +400,7 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
                     // Split by space to get individual arguments
                     for arg in plugin_args.split(" ") {
                         let arg = arg.split(":").collect::<Vec<_>>();
-                        let key = match arg.get(0) {
+                        let key = match arg.first() {
                             Some(&v) => v,
                             None => {
                                 return Err(String::from(
@@ -202,14 +429,14 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
                         }
                         .to_string();
 
-                        plugin_entry.args.insert(key, value);
+                        plugin_entry.args.insert(key, PluginArg::Str(value));
                     }
                 }
 
                 plugins.push(plugin_entry);
             }
             "--lang" => {
-                language = match keyval.get(1) {
+                language = match value {
                     Some(v) => match EmitLang::from_ext(v) {
                         Some(v) => Some(v),
                         None => {
@@ -228,37 +455,93 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
                 };
             }
             "--ignore-out-naming" => ignore_naming_convention = true,
-            _ => return Err(format!("Unrecognized option: '{}'", key)),
+            "--keep-unused-types" => keep_unused_types = true,
+            _ => unreachable!(),
         }
     }
 
     if schema_file.is_none() {
         return Err(String::from("Missing option --schema"));
     }
+    let split = out_dir.is_some();
+    if let Some(dir) = out_dir {
+        if out_file.is_some() {
+            return Err(String::from("Use either --out-file or --out-dir, not both"));
+        }
+        out_file = Some(split_index_file(&dir, language)?);
+    }
     if out_file.is_none() {
-        return Err(String::from("Missing option --out-file"));
+        return Err(String::from("Missing option --out-file or --out-dir"));
     }
     if language.is_none() {
-        // Try to detect language from the file extension
-        let out_file_path = out_file.as_ref().unwrap();
-        let ext = out_file_path.split(".").last();
-        let _lang = match ext {
-            Some(v) => match EmitLang::from_ext(v) {
-                Some(v) => Some(v),
-                None => None,
-            },
-            None => None,
-        };
-        if _lang.is_none() {
-            return Err(String::from(
-                "Could not guess language from file extension.\
+        language = Some(guess_language(out_file.as_ref().unwrap())?);
+    }
+
+    check_target(
+        out_file.as_ref().unwrap(),
+        &field_casing,
+        untagged_repr,
+        ignore_naming_convention,
+    )?;
+
+    Ok(ZetroArgs {
+        language: language.unwrap(),
+        out_file: out_file.unwrap(),
+        schema_file: schema_file.unwrap(),
+        mangle: should_mangle,
+        untagged: untagged_repr,
+        field_casing,
+        plugins,
+        keep_unused_types,
+        header: None,
+        template,
+        split,
+        inner_attributes: true,
+    })
+}
+
+/// The index file of split output in `dir`. The language can't be guessed
+/// from a directory, so it must be given.
+pub(super) fn split_index_file(dir: &str, language: Option<EmitLang>) -> Result<String, String> {
+    let language = language.ok_or_else(|| {
+        String::from("Split output needs a language. Provide one with --lang (or `lang`).")
+    })?;
+    Ok(std::path::Path::new(dir)
+        .join(crate::generators::generator(language).index_file())
+        .to_string_lossy()
+        .into_owned())
+}
+
+pub(super) fn parse_casing(val: &str) -> Result<Case, String> {
+    match val.to_lowercase().as_str() {
+        "snake" => Ok(Case::Snake),
+        "camel" => Ok(Case::Camel),
+        _ => Err(format!(
+            "Expected field casing to be one of: {}\nGot: '{}'",
+            "'snake', 'camel'", val
+        )),
+    }
+}
+
+/// Detects the language to generate from the extension of the output file
+pub(super) fn guess_language(out_file: &str) -> Result<EmitLang, String> {
+    match out_file.rsplit('.').next().and_then(EmitLang::from_ext) {
+        Some(v) => Ok(v),
+        None => Err(String::from(
+            "Could not guess language from file extension.\
             Manually provide a language with --lang.",
-            ));
-        } else {
-            language = _lang;
-        }
+        )),
     }
+}
 
+/// Checks options that can't be combined, and the naming convention of the
+/// output file
+pub(super) fn check_target(
+    out_file: &str,
+    field_casing: &Option<Case>,
+    untagged_repr: bool,
+    ignore_naming_convention: bool,
+) -> Result<(), String> {
     if field_casing.is_some() && untagged_repr {
         return Err(String::from(
             "Can not use --field-casing and --untagged together. Responses will always be camelCase.",
@@ -267,8 +550,8 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
 
     // Check naming convention for out_file
     if !ignore_naming_convention
-        && !out_file.clone().unwrap().contains("-generated")
-        && !out_file.clone().unwrap().contains("_generated")
+        && !out_file.contains("-generated")
+        && !out_file.contains("_generated")
     {
         return Err(String::from(
             "The output filename does not contain the string '_generated' or \
@@ -277,13 +560,533 @@ pub(super) fn parse_args(args: Vec<String>) -> Result<ZetroArgs, String> {
         ));
     }
 
-    Ok(ZetroArgs {
-        language: language.unwrap(),
-        out_file: out_file.unwrap(),
-        schema_file: schema_file.unwrap(),
-        mangle: should_mangle,
-        untagged: untagged_repr,
-        field_casing,
-        plugins,
+    Ok(())
+}
+
+/// Parses the arguments of `zetro convert`
+pub(super) fn parse_convert_args(args: &[String]) -> Result<ConvertArgs, String> {
+    let mut schema_file: Option<String> = None;
+    let mut out_file: Option<String> = None;
+
+    for flag in parse_flags(args, &["--schema", "--out-file"], &[])? {
+        match flag.name.as_str() {
+            "--schema" => match flag.value {
+                Some(v) => schema_file = Some(v),
+                None => return Err(String::from("Expected path to schema file")),
+            },
+            "--out-file" => match flag.value {
+                Some(v) => out_file = Some(v),
+                None => return Err(String::from("Expected path to output file")),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    let schema_file = match schema_file {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --schema")),
+    };
+    let out_file = match out_file {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --out-file")),
+    };
+    Ok(ConvertArgs {
+        schema_file,
+        out_file,
+    })
+}
+
+pub(super) fn parse_fmt_args(args: &[String]) -> Result<FmtArgs, String> {
+    let mut schema_file: Option<String> = None;
+    let mut check = false;
+    let mut strip_comments = false;
+
+    for flag in parse_flags(args, &["--schema"], &["--check", "--strip-comments"])? {
+        match flag.name.as_str() {
+            "--schema" => match flag.value {
+                Some(v) => schema_file = Some(v),
+                None => return Err(String::from("Expected path to schema file")),
+            },
+            "--check" => check = true,
+            "--strip-comments" => strip_comments = true,
+            _ => unreachable!(),
+        }
+    }
+
+    let schema_file = match schema_file {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --schema")),
+    };
+
+    Ok(FmtArgs {
+        schema_file,
+        check,
+        strip_comments,
     })
 }
+
+pub(super) fn parse_lint_args(args: &[String]) -> Result<LintArgs, String> {
+    let mut schema_file: Option<String> = None;
+    let mut config_file: Option<String> = None;
+    let mut format = LintFormat::Human;
+
+    for flag in parse_flags(args, &["--schema", "--config", "--format"], &[])? {
+        match flag.name.as_str() {
+            "--schema" => match flag.value {
+                Some(v) => schema_file = Some(v),
+                None => return Err(String::from("Expected path to schema file")),
+            },
+            "--config" => match flag.value {
+                Some(v) => config_file = Some(v),
+                None => return Err(String::from("Expected path to lint config file")),
+            },
+            "--format" => {
+                format = match flag.value.as_deref() {
+                    Some("human") => LintFormat::Human,
+                    Some("json") => LintFormat::Json,
+                    Some(v) => {
+                        return Err(format!(
+                            "Expected format to be one of: 'human', 'json'\nGot: '{}'",
+                            v
+                        ))
+                    }
+                    None => return Err(String::from("Expected output format for --format")),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let schema_file = match schema_file {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --schema")),
+    };
+
+    Ok(LintArgs {
+        schema_file,
+        config_file,
+        format,
+    })
+}
+
+/// Parses the arguments of `zetro generate`, `check` and `diff`: either
+/// `--config`, or the options of a single target. Without any option,
+/// `zetro.toml` in the working directory is used.
+pub(super) fn parse_target_args(args: &[String]) -> Result<TargetSource, String> {
+    let mut value_flags = TARGET_FLAGS.to_vec();
+    value_flags.push("--config");
+    let flags = parse_flags(args, &value_flags, &TARGET_SWITCHES)?;
+
+    if !flags.iter().any(|f| f.name == "--config") {
+        if flags.is_empty() {
+            return Ok(TargetSource::Config(String::from(
+                crate::project::CONFIG_FILE_NAME,
+            )));
+        }
+        return Ok(TargetSource::Target(parse_args(args)?));
+    }
+    if flags.len() > 1 {
+        return Err(String::from(
+            "--config can not be combined with other options. \
+            Targets are configured in the config file.",
+        ));
+    }
+
+    match &flags[0].value {
+        Some(v) => Ok(TargetSource::Config(v.clone())),
+        None => Err(String::from("Expected path to config file")),
+    }
+}
+
+/// Parses `--schema` or `--config`. Without either, `zetro.toml` in the
+/// working directory is used.
+pub(super) fn parse_schema_args(args: &[String]) -> Result<SchemaSource, String> {
+    schema_source(parse_flags(args, &["--schema", "--config"], &[])?)
+}
+
+/// Parses the arguments of `zetro inspect`: the schema, and `--direction`
+pub(super) fn parse_inspect_args(args: &[String]) -> Result<InspectArgs, String> {
+    let mut direction: Option<PayloadDirection> = None;
+    let mut schema_flags: Vec<Flag> = Vec::new();
+
+    for flag in parse_flags(args, &["--schema", "--config", "--direction"], &[])? {
+        if flag.name != "--direction" {
+            schema_flags.push(flag);
+            continue;
+        }
+        direction = Some(match flag.value.as_deref() {
+            Some("request") => PayloadDirection::Request,
+            Some("response") => PayloadDirection::Response,
+            Some(v) => {
+                return Err(format!(
+                    "Expected direction to be one of: 'request', 'response'\nGot: '{}'",
+                    v
+                ))
+            }
+            None => return Err(String::from("Expected a direction for --direction")),
+        });
+    }
+
+    Ok(InspectArgs {
+        source: schema_source(schema_flags)?,
+        direction,
+    })
+}
+
+/// Parses the arguments of `zetro encode`: the schema, and `--untagged`
+pub(super) fn parse_encode_args(args: &[String]) -> Result<EncodeArgs, String> {
+    let mut untagged = true;
+    let mut schema_flags: Vec<Flag> = Vec::new();
+
+    for flag in parse_flags(args, &["--schema", "--config", "--untagged"], &[])? {
+        if flag.name != "--untagged" {
+            schema_flags.push(flag);
+            continue;
+        }
+        untagged = match flag.value.as_deref() {
+            Some(v) => parse_bool(&v.to_lowercase()).ok_or_else(|| {
+                String::from(
+                    "Invalid value for --untagged. Expected one of 'true', '1', 'false', '0'",
+                )
+            })?,
+            None => return Err(String::from("Expected boolean value for --untagged")),
+        };
+    }
+
+    Ok(EncodeArgs {
+        source: schema_source(schema_flags)?,
+        untagged,
+    })
+}
+
+/// Parses the arguments of `zetro call`: options, then pairs of route name
+/// and request body
+pub(super) fn parse_call_args(args: &[String]) -> Result<CallArgs, String> {
+    let (flags, positional) = parse_flags_and_args(
+        args,
+        &["--schema", "--config", "--url", "--header", "--untagged"],
+        &[],
+    )?;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut untagged = true;
+    let mut schema_flags: Vec<Flag> = Vec::new();
+
+    for flag in flags {
+        match (flag.name.as_str(), flag.value) {
+            ("--url", Some(v)) => url = Some(v),
+            ("--url", None) => return Err(String::from("Expected the URL of the API for --url")),
+            ("--header", Some(v)) => match v.split_once(':') {
+                Some((name, value)) => {
+                    headers.push((name.trim().to_owned(), value.trim().to_owned()))
+                }
+                None => return Err(format!("Expected a header as 'Name: value'\nGot: '{}'", v)),
+            },
+            ("--header", None) => return Err(String::from("Expected a header for --header")),
+            ("--untagged", Some(v)) => {
+                untagged = parse_bool(&v.to_lowercase()).ok_or_else(|| {
+                    String::from(
+                        "Invalid value for --untagged. Expected one of 'true', '1', 'false', '0'",
+                    )
+                })?
+            }
+            ("--untagged", None) => {
+                return Err(String::from("Expected boolean value for --untagged"))
+            }
+            (name, value) => schema_flags.push(Flag {
+                name: name.to_owned(),
+                value,
+            }),
+        }
+    }
+
+    let url = match url {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --url")),
+    };
+    if positional.is_empty() {
+        return Err(String::from("Expected a route name and a request body"));
+    }
+    if positional.len() % 2 != 0 {
+        return Err(format!(
+            "Expected a request body after the route name '{}'",
+            positional.last().unwrap()
+        ));
+    }
+    let calls = positional
+        .chunks(2)
+        .map(|call| (call[0].clone(), call[1].clone()))
+        .collect();
+
+    Ok(CallArgs {
+        source: schema_source(schema_flags)?,
+        url,
+        headers,
+        untagged,
+        calls,
+    })
+}
+
+/// Parses the arguments of `zetro mock`
+pub(super) fn parse_mock_args(args: &[String]) -> Result<MockArgs, String> {
+    let mut host = String::from("127.0.0.1");
+    let mut port: u16 = 8090;
+    let mut fixtures_file: Option<String> = None;
+    let mut untagged = true;
+    let mut seed: u64 = 0;
+    let mut schema_flags: Vec<Flag> = Vec::new();
+
+    let value_flags = [
+        "--schema",
+        "--config",
+        "--host",
+        "--port",
+        "--fixtures",
+        "--untagged",
+        "--seed",
+    ];
+    for flag in parse_flags(args, &value_flags, &[])? {
+        match (flag.name.as_str(), flag.value) {
+            ("--host", Some(v)) => host = v,
+            ("--port", Some(v)) => {
+                port = v
+                    .parse()
+                    .map_err(|_| format!("Expected a port number for --port\nGot: '{}'", v))?
+            }
+            ("--fixtures", Some(v)) => fixtures_file = Some(v),
+            ("--untagged", Some(v)) => {
+                untagged = parse_bool(&v.to_lowercase()).ok_or_else(|| {
+                    String::from(
+                        "Invalid value for --untagged. Expected one of 'true', '1', 'false', '0'",
+                    )
+                })?
+            }
+            ("--seed", Some(v)) => {
+                seed = v
+                    .parse()
+                    .map_err(|_| format!("Expected a number for --seed\nGot: '{}'", v))?
+            }
+            (name @ ("--schema" | "--config"), value) => schema_flags.push(Flag {
+                name: name.to_owned(),
+                value,
+            }),
+            (name, None) => return Err(format!("Expected a value for {}", name)),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(MockArgs {
+        source: schema_source(schema_flags)?,
+        host,
+        port,
+        fixtures_file,
+        untagged,
+        seed,
+    })
+}
+
+/// Parses the arguments of `zetro fixtures`
+pub(super) fn parse_fixtures_args(args: &[String]) -> Result<FixturesArgs, String> {
+    let mut struct_name: Option<String> = None;
+    let mut count: usize = 1;
+    let mut untagged = true;
+    let mut options = FakeOptions::default();
+    let mut schema_flags: Vec<Flag> = Vec::new();
+
+    let value_flags = [
+        "--schema",
+        "--config",
+        "--struct",
+        "--count",
+        "--seed",
+        "--untagged",
+        "--null-probability",
+        "--list-len",
+        "--max-depth",
+    ];
+    let number = |name: &str, v: &str| {
+        v.parse::<u64>()
+            .map_err(|_| format!("Expected a number for {}\nGot: '{}'", name, v))
+    };
+    for flag in parse_flags(args, &value_flags, &[])? {
+        match (flag.name.as_str(), flag.value) {
+            ("--struct", Some(v)) => struct_name = Some(v),
+            ("--count", Some(v)) => count = number("--count", &v)? as usize,
+            ("--seed", Some(v)) => options.seed = number("--seed", &v)?,
+            ("--max-depth", Some(v)) => options.max_depth = number("--max-depth", &v)? as usize,
+            ("--untagged", Some(v)) => {
+                untagged = parse_bool(&v.to_lowercase()).ok_or_else(|| {
+                    String::from(
+                        "Invalid value for --untagged. Expected one of 'true', '1', 'false', '0'",
+                    )
+                })?
+            }
+            ("--null-probability", Some(v)) => {
+                options.null_probability = v
+                    .parse()
+                    .ok()
+                    .filter(|p| (0.0..=1.0).contains(p))
+                    .ok_or_else(|| {
+                        format!(
+                            "Expected a number from 0 to 1 for --null-probability\nGot: '{}'",
+                            v
+                        )
+                    })?
+            }
+            ("--list-len", Some(v)) => {
+                let (min, max) = v.split_once("..").unwrap_or((&v, &v));
+                let (min, max) = (number("--list-len", min)?, number("--list-len", max)?);
+                if min > max {
+                    return Err(format!(
+                        "Expected --list-len as 'min..max' or 'len'\nGot: '{}'",
+                        v
+                    ));
+                }
+                options.list_len = min as usize..=max as usize;
+            }
+            (name @ ("--schema" | "--config"), value) => schema_flags.push(Flag {
+                name: name.to_owned(),
+                value,
+            }),
+            (name, None) => return Err(format!("Expected a value for {}", name)),
+            _ => unreachable!(),
+        }
+    }
+
+    let struct_name = match struct_name {
+        Some(v) => v,
+        None => return Err(String::from("Missing option --struct")),
+    };
+    Ok(FixturesArgs {
+        source: schema_source(schema_flags)?,
+        struct_name,
+        count,
+        untagged,
+        options,
+    })
+}
+
+fn schema_source(flags: Vec<Flag>) -> Result<SchemaSource, String> {
+    let mut source: Option<SchemaSource> = None;
+
+    for flag in flags {
+        if source.is_some() {
+            return Err(String::from(
+                "Expected only one of the options --schema and --config",
+            ));
+        }
+        source = Some(match (flag.name.as_str(), flag.value) {
+            ("--schema", Some(v)) => SchemaSource::Schema(v),
+            ("--config", Some(v)) => SchemaSource::Config(v),
+            ("--schema", None) => return Err(String::from("Expected path to schema file")),
+            _ => return Err(String::from("Expected path to config file")),
+        });
+    }
+
+    Ok(source
+        .unwrap_or_else(|| SchemaSource::Config(String::from(crate::project::CONFIG_FILE_NAME))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_flags, parse_flags_and_args, parse_target_args, TargetSource};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    /// Values can follow an option after `=` or as the next argument
+    #[test]
+    fn splits_flags() {
+        let flags = parse_flags(
+            &args(&[
+                "--schema",
+                "a.json",
+                "--check",
+                "--out-file=b=c.rs",
+                "--lang",
+            ]),
+            &["--schema", "--out-file", "--lang"],
+            &["--check"],
+        )
+        .unwrap();
+        let flags = flags
+            .iter()
+            .map(|f| (f.name.as_str(), f.value.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            flags,
+            vec![
+                ("--schema", Some("a.json")),
+                ("--check", None),
+                ("--out-file", Some("b=c.rs")),
+                ("--lang", None),
+            ]
+        );
+
+        // A missing value is not taken from the next option
+        let flags =
+            parse_flags(&args(&["--schema", "--check"]), &["--schema"], &["--check"]).unwrap();
+        assert!(flags[0].value.is_none());
+
+        for bad in [&["--check=true"][..], &["--nope"], &["schema.json"]] {
+            assert!(parse_flags(&args(bad), &["--schema"], &["--check"]).is_err());
+        }
+        // Arguments that are not options are kept whole
+        let (flags, positional) = parse_flags_and_args(
+            &args(&["--url", "http://x", "GetRooms", "{\"a\":\"b=c\"}"]),
+            &["--url"],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(flags[0].value.as_deref(), Some("http://x"));
+        assert_eq!(positional, vec!["GetRooms", "{\"a\":\"b=c\"}"]);
+    }
+
+    #[test]
+    fn parses_targets() {
+        assert!(matches!(
+            parse_target_args(&[]),
+            Ok(TargetSource::Config(path)) if path == "zetro.toml"
+        ));
+        assert!(matches!(
+            parse_target_args(&args(&["--config", "api/zetro.toml"])),
+            Ok(TargetSource::Config(path)) if path == "api/zetro.toml"
+        ));
+
+        match parse_target_args(&args(&[
+            "--schema",
+            "schema.json",
+            "--out-file",
+            "code_generated.rs",
+            "--untagged",
+            "true",
+            "--add-plugin",
+            "warp(fnv:true)",
+        ])) {
+            Ok(TargetSource::Target(target)) => {
+                assert_eq!(target.schema_file, "schema.json");
+                assert!(target.untagged);
+                assert_eq!(target.plugins[0].name, "warp");
+            }
+            _ => panic!("expected a single target"),
+        }
+
+        assert!(parse_target_args(&args(&["--config=zetro.toml", "--untagged=true"])).is_err());
+        assert!(parse_target_args(&args(&["--out-file=code_generated.rs"])).is_err());
+
+        match parse_target_args(&args(&[
+            "--schema=schema.json",
+            "--out-dir=src/api_generated",
+            "--lang=rs",
+        ])) {
+            Ok(TargetSource::Target(target)) => {
+                assert!(target.split);
+                assert_eq!(target.out_file, "src/api_generated/mod.rs");
+            }
+            _ => panic!("expected a single target"),
+        }
+        assert!(parse_target_args(&args(&["--schema=s.json", "--out-dir=api_generated"])).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! Conformance of the generated Rust server and TypeScript client. Every
+//! schema in `tests/conformance` takes and returns the same type in each
+//! route. It is generated for both sides, tagged and untagged, and served by
+//! a warp server whose routes echo their request. A Node script sends random
+//! batches through the generated clients and checks that they come back
+//! unchanged, and that both sides number enum variants the same.
+//!
+//! This builds warp and needs Node with TypeScript support, so it only runs
+//! with `cargo test --test conformance -- --ignored`. The TypeScript runner
+//! is `node --experimental-strip-types` (Node 22.6 or later), or the command
+//! in `ZETRO_TS_RUNNER`, eg. `npx tsx`. Set `ZETRO_SEED` to replay a run.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+};
+
+use convert_case::{Case, Casing};
+use serde_json::{json, Value};
+use zetro::{
+    schema::{ZetroRoute, ZetroSchema, ZetroStruct},
+    FakeOptions, Language, Options, Plugin, SchemaFormat,
+};
+
+/// Batches sent for every schema and encoding
+const BATCHES: usize = 40;
+/// How deep random values nest structs through nullable fields and lists
+const MAX_DEPTH: usize = 3;
+
+/// A schema of the corpus, generated with one encoding
+struct Target {
+    schema_name: String,
+    schema: ZetroSchema,
+    untagged: bool,
+}
+
+impl Target {
+    fn encoding(&self) -> &'static str {
+        if self.untagged {
+            "untagged"
+        } else {
+            "tagged"
+        }
+    }
+
+    /// Name of the Rust and TypeScript modules
+    fn module(&self) -> String {
+        format!("{}_{}", self.schema_name, self.encoding())
+    }
+
+    /// The URL path the server answers on
+    fn path(&self) -> String {
+        format!("{}/{}", self.schema_name, self.encoding())
+    }
+
+    fn generate(&self, language: Language, plugin: &str) -> String {
+        let options = Options {
+            untagged: self.untagged,
+            plugins: vec![Plugin::new(plugin)],
+            ..Options::new(language)
+        };
+        zetro::generate(&self.schema, &options).unwrap()
+    }
+}
+
+/// Every schema of the corpus, in both encodings
+fn targets() -> Vec<Target> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+    let mut paths = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut targets = Vec::new();
+    for path in paths {
+        let contents = fs::read_to_string(&path).unwrap();
+        let format = SchemaFormat::from_path(&path.to_string_lossy());
+        let schema = zetro::parse_schema(&contents, format)
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        for route in schema.queries.iter().chain(schema.mutations.iter()) {
+            assert!(
+                route.request_body.to_value() == route.response_body.to_value(),
+                "{}: {} must return its request type to be echoed",
+                path.display(),
+                route.name
+            );
+        }
+
+        for untagged in [false, true] {
+            targets.push(Target {
+                schema_name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                schema: schema.clone(),
+                untagged,
+            });
+        }
+    }
+    targets
+}
+
+/// Handlers that echo their request, with the signatures of the methods of
+/// `trait_name` in the generated `code`
+fn echo_handlers(code: &str, trait_name: &str) -> String {
+    let start = format!("pub trait {} {{\n", trait_name);
+    let body = &code[code.find(&start).unwrap() + start.len()..];
+    body[..body.find("\n}").unwrap()]
+        .lines()
+        .filter(|line| line.trim_start().starts_with("async fn"))
+        .map(|signature| {
+            format!(
+                "{} {{\n\t\tOk(request)\n\t}}",
+                signature
+                    .trim_end_matches(';')
+                    .replacen("(ctx:", "(_ctx:", 1)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Code added to the generated server `code`: routes that echo their
+/// request, `routes` to serve them and `enums`, the value of every enum
+/// variant on the wire
+fn echo_server(target: &Target, code: &str) -> String {
+    let enums = target
+        .schema
+        .enums
+        .iter()
+        .map(|e| {
+            let variants = e
+                .variants
+                .iter()
+                .map(|v| {
+                    format!(
+                        "\t\t\t\"{}\": {}::{},",
+                        v.to_case(Case::UpperSnake),
+                        e.name.to_case(Case::Pascal),
+                        v.to_case(Case::Pascal)
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!(
+                "\t\t\"{}\": {{\n{}\n\t\t}},",
+                e.name.to_case(Case::Pascal),
+                variants.join("\n")
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "pub struct Queries;
+
+pub struct Mutations;
+
+#[async_trait::async_trait]
+impl ZetroQueries for Queries {{
+{}
+}}
+
+#[async_trait::async_trait]
+impl ZetroMutations for Mutations {{
+{}
+}}
+
+pub fn routes() -> warp::filters::BoxedFilter<(warp::reply::Response,)> {{
+\tgenerate_routes(ZetroContext::new(), Queries, Mutations)
+\t\t.map(warp::Reply::into_response)
+\t\t.boxed()
+}}
+
+pub fn enums() -> serde_json::Value {{
+\tserde_json::json!({{
+{}
+\t}})
+}}
+",
+        echo_handlers(code, "ZetroQueries"),
+        echo_handlers(code, "ZetroMutations"),
+        enums.join("\n")
+    )
+}
+
+/// The server crate. It serves every target and prints its port once it
+/// listens.
+fn write_server(dir: &Path, targets: &[Target]) {
+    let src = dir.join("src");
+    let _ = fs::remove_dir_all(&src);
+    fs::create_dir_all(&src).unwrap();
+
+    // A workspace of its own, so that it isn't taken for a member of zetro's
+    fs::write(
+        dir.join("Cargo.toml"),
+        r#"[package]
+name = "zetro-conformance"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+async-trait = "0.1"
+bytes = "1"
+serde = "1"
+serde_derive = "1"
+serde_json = "1"
+serde_repr = "0.1"
+tokio = { version = "1", features = ["macros", "rt-multi-thread"] }
+warp = "0.3"
+
+[workspace]
+"#,
+    )
+    .unwrap();
+
+    let mut modules = Vec::new();
+    let mut routes = Vec::new();
+    for target in targets {
+        let code = target.generate(Language::Rust, "warp");
+        fs::write(
+            src.join(format!("{}.rs", target.module())),
+            format!("{}\n\n{}", code, echo_server(target, &code)),
+        )
+        .unwrap();
+
+        modules.push(format!("mod {};", target.module()));
+        routes.push(format!(
+            "warp::path!(\"{0}\" / \"{1}\")
+\t\t\t.and(warp::post())
+\t\t\t.and({2}::routes())
+\t\t\t.or(warp::path!(\"{0}\" / \"{1}\" / \"enums\").map(|| warp::reply::json(&{2}::enums()).into_response()))
+\t\t\t.unify()",
+            target.schema_name,
+            target.encoding(),
+            target.module()
+        ));
+    }
+
+    fs::write(
+        src.join("main.rs"),
+        format!(
+            "{}
+
+use warp::{{Filter, Reply}};
+
+#[tokio::main]
+async fn main() {{
+\tlet routes = {};
+\tlet (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+\tprintln!(\"{{}}\", address.port());
+\tserver.await;
+}}
+",
+            modules.join("\n"),
+            routes.join("\n\t\t.or(") + &")\n\t\t.unify()".repeat(routes.len() - 1)
+        ),
+    )
+    .unwrap();
+}
+
+/// The client package: the generated clients and a script that sends the
+/// batches in `cases.json` through them
+fn write_client(dir: &Path, targets: &[Target], cases: &Value) {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("package.json"), "{ \"type\": \"module\" }\n").unwrap();
+    fs::write(dir.join("cases.json"), cases.to_string()).unwrap();
+
+    let mut imports = Vec::new();
+    let mut clients = Vec::new();
+    for target in targets {
+        let code = target.generate(Language::TypeScript, "class-client");
+        fs::write(dir.join(format!("{}.ts", target.module())), code).unwrap();
+        imports.push(format!(
+            "import * as {0} from \"./{0}.ts\";",
+            target.module()
+        ));
+        clients.push(format!("\t\"{}\": {},", target.path(), target.module()));
+    }
+
+    fs::write(
+        dir.join("driver.ts"),
+        format!(
+            "{}
+import {{ readFileSync }} from \"node:fs\";
+
+const clients: Record<string, any> = {{
+{}
+}};
+
+const [url, casesPath] = process.argv.slice(2);
+const cases = JSON.parse(readFileSync(casesPath, \"utf8\"));
+
+// JSON with sorted keys. Absent values are null, like on the wire.
+function canonical(value: any): string {{
+\treturn JSON.stringify(value, function (_key: string, v: any) {{
+\t\tif (v === undefined) {{ return null; }}
+\t\tif (v !== null && typeof v === \"object\" && !Array.isArray(v)) {{
+\t\t\treturn Object.fromEntries(Object.keys(v).sort().map(function (k) {{ return [k, v[k]]; }}));
+\t\t}}
+\t\treturn v;
+\t}});
+}}
+
+let failures = 0;
+function fail(message: string) {{
+\tfailures += 1;
+\tconsole.error(message);
+}}
+
+// Enum variants have the same value on both sides
+for (const [path, api] of Object.entries(clients)) {{
+\tconst enums = await (await fetch(url + \"/\" + path + \"/enums\")).json();
+\tfor (const [name, variants] of Object.entries(enums)) {{
+\t\tif (canonical(api[name]) !== canonical(variants)) {{
+\t\t\tfail(path + \": enum \" + name + \" is \" + canonical(api[name]) + \" in TypeScript and \" + canonical(variants) + \" in Rust\");
+\t\t}}
+\t}}
+}}
+
+for (const testCase of cases) {{
+\tconst api = clients[testCase.path];
+\tconst client = {{
+\t\tmakeRequest: async function (body: any) {{
+\t\t\tconst response = await fetch(url + \"/\" + testCase.path, {{ method: \"POST\", body: JSON.stringify(body) }});
+\t\t\tif (response.status !== 200) {{
+\t\t\t\tthrow {{ code: response.status, message: await response.text() }};
+\t\t\t}}
+\t\t\treturn response.json();
+\t\t}},
+\t}};
+
+\tconst expected: Record<string, any> = {{}};
+\tlet actual: any;
+\ttry {{
+\t\tconst batch = testCase.kind === \"query\" ? new api.ZetroQuery(client) : new api.ZetroMutation(client);
+\t\tfor (const [method, value] of testCase.calls) {{
+\t\t\texpected[method] = value;
+\t\t\tbatch[method](value);
+\t\t}}
+\t\tactual = await batch.fetch();
+\t}} catch (e) {{
+\t\tactual = {{ error: String(e?.message ?? e) }};
+\t}}
+\tif (canonical(actual) !== canonical(expected)) {{
+\t\tfail(testCase.path + \": \" + testCase.kind + \" \" + testCase.calls.map(function (c: any) {{ return c[0]; }}).join(\", \") +
+\t\t\t\"\\n  sent:     \" + canonical(expected) + \"\\n  received: \" + canonical(actual));
+\t}}
+}}
+
+console.log(cases.length + \" batches sent, \" + failures + \" failure(s)\");
+process.exit(failures === 0 ? 0 : 1);
+",
+            imports.join("\n"),
+            clients.join("\n")
+        ),
+    )
+    .unwrap();
+}
+
+/// Random batches for every target, with values as the TypeScript client
+/// takes them: tagged structs and enums as numbers. A batch calls up to four
+/// routes of one kind.
+fn cases(targets: &[Target], seed: u64) -> Value {
+    let options = FakeOptions {
+        seed,
+        // The calls of a route are structs around its request
+        max_depth: MAX_DEPTH + 1,
+        ..Default::default()
+    };
+    let mut cases = Vec::new();
+    for target in targets {
+        // The values of a route: its request, and whether it's in a batch
+        let mut schema = target.schema.clone();
+        let routes = schema
+            .queries
+            .iter()
+            .chain(schema.mutations.iter())
+            .map(|route| {
+                let call = json!({
+                    "description": "",
+                    "fields": { "request": route.request_body.to_value(), "sent": "bool" }
+                });
+                (route.name.clone(), call)
+            })
+            .collect::<Vec<_>>();
+        for (name, call) in routes {
+            schema
+                .structs
+                .push(ZetroStruct::from_value(format!("{}Call", name), &call).unwrap());
+        }
+        let calls = |routes: &[ZetroRoute]| {
+            routes
+                .iter()
+                .map(|route| {
+                    let name = format!("{}Call", route.name);
+                    let calls = zetro::fake_structs(&schema, &name, BATCHES, false, &options)
+                        .unwrap_or_else(|e| panic!("{}: {}", target.path(), e));
+                    (route.name.to_case(Case::Camel), calls)
+                })
+                .collect::<Vec<_>>()
+        };
+        let kinds = [
+            ("query", calls(&schema.queries)),
+            ("mutation", calls(&schema.mutations)),
+        ];
+
+        for i in 0..BATCHES {
+            let (kind, routes) = &kinds[i % 2];
+            // A route only once per batch, as the client returns results by route
+            let mut batch = Vec::new();
+            for (method, calls) in routes {
+                if batch.is_empty() || calls[i]["sent"] == json!(true) {
+                    batch.push(json!([method, calls[i]["request"]]));
+                }
+            }
+            if !batch.is_empty() {
+                cases.push(json!({ "path": target.path(), "kind": kind, "calls": batch }));
+            }
+        }
+    }
+    Value::Array(cases)
+}
+
+/// Kills the server when the test ends, even if it fails
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// The generated server and client agree on every payload
+#[test]
+#[ignore]
+fn rust_server_and_typescript_client_agree() {
+    let seed = match std::env::var("ZETRO_SEED") {
+        Ok(seed) => seed.parse().expect("ZETRO_SEED must be a number"),
+        Err(_) => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64,
+    };
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("conformance");
+    let targets = targets();
+
+    write_server(&dir, &targets);
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .args(["build", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", dir.join("target"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "the generated server doesn't compile");
+
+    let mut server = Server(
+        Command::new(dir.join("target/debug/zetro-conformance"))
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap(),
+    );
+    let mut port = String::new();
+    BufReader::new(server.0.stdout.take().unwrap())
+        .read_line(&mut port)
+        .unwrap();
+    let url = format!("http://127.0.0.1:{}", port.trim());
+
+    let client = dir.join("client");
+    write_client(&client, &targets, &cases(&targets, seed));
+    let runner = std::env::var("ZETRO_TS_RUNNER")
+        .unwrap_or_else(|_| String::from("node --experimental-strip-types"));
+    let mut runner = runner.split_whitespace();
+    let status = Command::new(runner.next().expect("ZETRO_TS_RUNNER is empty"))
+        .args(runner)
+        .arg(client.join("driver.ts"))
+        .arg(&url)
+        .arg(client.join("cases.json"))
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "the server and client disagree. Replay with ZETRO_SEED={}",
+        seed
+    );
+}
@@ -0,0 +1,306 @@
+//! Snapshot tests of the generated code. Every schema in `tests/corpus` is
+//! generated for every target in `targets` and compared with its file in
+//! `tests/snapshots/<schema>/`. Split targets, which the library can't
+//! generate, are generated by the CLI into directories next to the files.
+//!
+//! After an intended change, update the files with
+//! `ZETRO_BLESS=1 cargo test --test snapshots` and review the diff.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use zetro::{FieldCasing, Language, Options, Plugin, SchemaFormat};
+
+/// The targets every schema is generated for, as (file name, options)
+fn targets() -> Vec<(&'static str, Options)> {
+    let rust = |untagged: bool, plugins: Vec<Plugin>| Options {
+        untagged,
+        plugins,
+        ..Options::new(Language::Rust)
+    };
+    let typescript = |untagged: bool, plugins: Vec<Plugin>| Options {
+        untagged,
+        plugins,
+        ..Options::new(Language::TypeScript)
+    };
+    let client = || vec![Plugin::new("class-client")];
+
+    vec![
+        ("server.rs", rust(false, vec![Plugin::new("warp")])),
+        (
+            "server_untagged.rs",
+            rust(
+                true,
+                vec![
+                    Plugin::new("warp").arg("fnv", true),
+                    Plugin::new("proptest"),
+                ],
+            ),
+        ),
+        (
+            "all_types.rs",
+            Options {
+                keep_unused_types: true,
+                ..rust(true, Vec::new())
+            },
+        ),
+        ("client.ts", typescript(false, client())),
+        ("client_untagged.ts", typescript(true, client())),
+        (
+            "client_mangled.ts",
+            Options {
+                mangle: Some(true),
+                ..typescript(true, client())
+            },
+        ),
+        (
+            "all_types.ts",
+            Options {
+                field_casing: Some(FieldCasing::Snake),
+                keep_unused_types: true,
+                ..typescript(false, Vec::new())
+            },
+        ),
+    ]
+}
+
+/// The split targets every schema is generated for, as (directory name,
+/// options of `zetro generate`)
+fn split_targets() -> Vec<(&'static str, Vec<&'static str>)> {
+    vec![
+        ("split_server", vec!["--lang", "rs", "--add-plugin=warp"]),
+        (
+            "split_client",
+            vec![
+                "--lang",
+                "ts",
+                "--untagged",
+                "true",
+                "--add-plugin=class-client",
+            ],
+        ),
+    ]
+}
+
+fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+/// Every schema of the corpus as (name, path), sorted by name
+fn corpus() -> Vec<(String, PathBuf)> {
+    let mut schemas = fs::read_dir(tests_dir().join("corpus"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (name, path)
+        })
+        .collect::<Vec<_>>();
+    schemas.sort();
+    schemas
+}
+
+/// The generated code of every snapshot, as (path, code)
+fn generate_all() -> Vec<(PathBuf, String)> {
+    let mut generated = Vec::new();
+    for (name, path) in corpus() {
+        let contents = fs::read_to_string(&path).unwrap();
+        let format = SchemaFormat::from_path(&path.to_string_lossy());
+        let schema = zetro::parse_schema(&contents, format)
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+        for (file, options) in targets() {
+            let code = zetro::generate(&schema, &options)
+                .unwrap_or_else(|e| panic!("{}, {}: {}", name, file, e));
+            generated.push((tests_dir().join("snapshots").join(&name).join(file), code));
+        }
+
+        for (dir, args) in split_targets() {
+            let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+                .join("split")
+                .join(&name)
+                .join(format!("{}_generated", dir));
+            let _ = fs::remove_dir_all(&out_dir);
+            let output = Command::new(env!("CARGO_BIN_EXE_zetro"))
+                .arg("generate")
+                .arg("--schema")
+                .arg(&path)
+                .arg("--out-dir")
+                .arg(&out_dir)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "{}, {}: {}",
+                name,
+                dir,
+                String::from_utf8_lossy(&output.stderr)
+            );
+            for file in fs::read_dir(&out_dir).unwrap() {
+                let file = file.unwrap();
+                generated.push((
+                    tests_dir()
+                        .join("snapshots")
+                        .join(&name)
+                        .join(dir)
+                        .join(file.file_name()),
+                    fs::read_to_string(file.path()).unwrap(),
+                ));
+            }
+        }
+    }
+    generated
+}
+
+/// Files in the snapshot directories and in the directories of split
+/// targets, to find snapshots of removed targets
+fn existing_snapshots() -> BTreeSet<PathBuf> {
+    let mut paths = BTreeSet::new();
+    let Ok(dirs) = fs::read_dir(tests_dir().join("snapshots")) else {
+        return paths;
+    };
+    let mut dirs = dirs.map(|dir| dir.unwrap().path()).collect::<Vec<_>>();
+    while let Some(dir) = dirs.pop() {
+        for file in fs::read_dir(dir).unwrap() {
+            let path = file.unwrap().path();
+            match path.is_dir() {
+                true => dirs.push(path),
+                false => {
+                    paths.insert(path);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Generated code is the same as the snapshots
+#[test]
+fn matches_snapshots() {
+    let bless = std::env::var("ZETRO_BLESS").is_ok_and(|v| v == "1");
+    let mut stale = existing_snapshots();
+    let mut failures = Vec::new();
+
+    for (path, code) in generate_all() {
+        stale.remove(&path);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if existing == code {
+            continue;
+        }
+        if bless {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &code).unwrap();
+            continue;
+        }
+
+        let relative = path.strip_prefix(env!("CARGO_MANIFEST_DIR")).unwrap();
+        failures.push(
+            similar::TextDiff::from_lines(&existing, &code)
+                .unified_diff()
+                .header(
+                    &format!("a/{}", relative.display()),
+                    &format!("b/{}", relative.display()),
+                )
+                .to_string(),
+        );
+    }
+    for path in stale {
+        if bless {
+            fs::remove_file(&path).unwrap();
+        } else {
+            failures.push(format!("{} has no target\n", path.display()));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\nThe generated code changed. If that is intended, run \
+        `ZETRO_BLESS=1 cargo test --test snapshots` and commit the snapshots",
+        failures.concat()
+    );
+}
+
+/// The Rust snapshots compile and their generated round-trip tests pass. This
+/// builds a crate with warp and proptest, so it only runs with `--ignored`.
+#[test]
+#[ignore]
+fn rust_snapshots_compile() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    let src = dir.join("src");
+    let _ = fs::remove_dir_all(&src);
+    fs::create_dir_all(&src).unwrap();
+
+    // A workspace of its own, so that it isn't taken for a member of zetro's
+    fs::write(
+        dir.join("Cargo.toml"),
+        r#"[package]
+name = "zetro-snapshots"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+async-trait = "0.1"
+bytes = "1"
+fnv = "1"
+serde = "1"
+serde_derive = "1"
+serde_json = "1"
+serde_repr = "0.1"
+warp = "0.3"
+
+[dev-dependencies]
+proptest = "1"
+
+[workspace]
+"#,
+    )
+    .unwrap();
+
+    let mut modules = BTreeSet::new();
+    for path in existing_snapshots() {
+        if path.extension().is_some_and(|e| e == "rs") {
+            let parent = path.parent().unwrap();
+            let name = |path: &Path| path.file_name().unwrap().to_string_lossy().into_owned();
+            // Split targets are a directory with a `mod.rs`
+            let split = parent.parent().unwrap() != tests_dir().join("snapshots");
+            let module = match split {
+                true => format!("{}_{}", name(parent.parent().unwrap()), name(parent)),
+                false => format!(
+                    "{}_{}",
+                    name(parent),
+                    path.file_stem().unwrap().to_string_lossy()
+                ),
+            };
+            match split {
+                true => {
+                    fs::create_dir_all(src.join(&module)).unwrap();
+                    fs::copy(&path, src.join(&module).join(name(&path))).unwrap();
+                }
+                false => {
+                    fs::copy(&path, src.join(format!("{}.rs", module))).unwrap();
+                }
+            }
+            modules.insert(format!("mod {};", module));
+        }
+    }
+    assert!(!modules.is_empty(), "there are no Rust snapshots");
+    let modules = modules.into_iter().collect::<Vec<_>>();
+    fs::write(src.join("lib.rs"), modules.join("\n") + "\n").unwrap();
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .args(["test", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "the Rust snapshots don't compile or their tests fail"
+    );
+}
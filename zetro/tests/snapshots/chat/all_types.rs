@@ -0,0 +1,371 @@
+// This is an autogenerated file. DO NOT EDIT
+
+#![allow(dead_code)]
+use serde::ser::SerializeTuple;
+
+/// AUTOGENERATED. The struct that serializes error states
+#[derive(Debug, Clone)]
+pub struct ZetroServerError {
+	/// Arbitrary, human-readable message for the end user
+	pub message: String,
+	/// Arbitrary, response code for frontend logic
+	pub code: i16,
+}
+
+/// Contains basic information about a single message author
+#[derive(Debug, Clone)]
+pub struct AuthorRef {
+	/// Username of author
+	pub username: String,
+}
+
+impl serde::ser::Serialize for AuthorRef {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.username)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for AuthorRef {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = AuthorRef;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let username = seq.next_element::<String>()?;
+				if username.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let username = username.unwrap();
+
+				Ok(AuthorRef {
+					username: username,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+/// Resembles a single chat room
+#[derive(Debug, Clone)]
+pub struct Chatroom {
+	/// Unique id for this room
+	pub id: u64,
+	/// Messages in this room
+	pub messages: Vec<Message>,
+	/// Name of room
+	pub name: String,
+	pub status: RoomStatus,
+}
+
+impl serde::ser::Serialize for Chatroom {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(4)?;
+		state.serialize_element(&self.id)?;
+		state.serialize_element(&self.messages)?;
+		state.serialize_element(&self.name)?;
+		state.serialize_element(&self.status)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Chatroom {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Chatroom;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let id = seq.next_element::<u64>()?;
+				if id.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let id = id.unwrap();
+
+				let messages = seq.next_element::<Vec<Message>>()?;
+				if messages.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let messages = messages.unwrap();
+
+				let name = seq.next_element::<String>()?;
+				if name.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let name = name.unwrap();
+
+				let status = seq.next_element::<RoomStatus>()?;
+				if status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let status = status.unwrap();
+
+				Ok(Chatroom {
+					id: id,
+					messages: messages,
+					name: name,
+					status: status,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(4, Visitor)
+	}
+}
+
+/// Parameters for a GetRooms request
+#[derive(Debug, Clone)]
+pub struct GetRoomsRequest {
+	/// Fetch only rooms with the given status
+	pub with_status: Option<RoomStatus>,
+}
+
+impl serde::ser::Serialize for GetRoomsRequest {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.with_status)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for GetRoomsRequest {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = GetRoomsRequest;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let with_status = seq.next_element::<Option<RoomStatus>>()?;
+				if with_status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let with_status = with_status.unwrap();
+
+				Ok(GetRoomsRequest {
+					with_status: with_status,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+/// Returns the chat rooms
+#[derive(Debug, Clone)]
+pub struct GetRoomsResponse {
+	/// A list of rooms
+	pub rooms: Vec<Chatroom>,
+}
+
+impl serde::ser::Serialize for GetRoomsResponse {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.rooms)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for GetRoomsResponse {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = GetRoomsResponse;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let rooms = seq.next_element::<Vec<Chatroom>>()?;
+				if rooms.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let rooms = rooms.unwrap();
+
+				Ok(GetRoomsResponse {
+					rooms: rooms,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+/// Resembles a single chat message in a room
+#[derive(Debug, Clone)]
+pub struct Message {
+	/// Author who sent this message
+	pub author: AuthorRef,
+	/// UNIX timestamp (seconds) of when this message was sent
+	pub date: u32,
+	/// Unique id for this message
+	pub id: u64,
+	/// Message content
+	pub text: String,
+}
+
+impl serde::ser::Serialize for Message {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(4)?;
+		state.serialize_element(&self.author)?;
+		state.serialize_element(&self.date)?;
+		state.serialize_element(&self.id)?;
+		state.serialize_element(&self.text)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Message {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Message;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let author = seq.next_element::<AuthorRef>()?;
+				if author.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let author = author.unwrap();
+
+				let date = seq.next_element::<u32>()?;
+				if date.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let date = date.unwrap();
+
+				let id = seq.next_element::<u64>()?;
+				if id.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let id = id.unwrap();
+
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				Ok(Message {
+					author: author,
+					date: date,
+					id: id,
+					text: text,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(4, Visitor)
+	}
+}
+
+/// Parameters for a SendMessage request
+#[derive(Debug, Clone)]
+pub struct SendMessageRequest {
+	/// The message to send. ID field is ignored.
+	pub msg: Message,
+	/// Room to send the message in
+	pub room_id: u64,
+}
+
+impl serde::ser::Serialize for SendMessageRequest {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(2)?;
+		state.serialize_element(&self.msg)?;
+		state.serialize_element(&self.room_id)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for SendMessageRequest {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = SendMessageRequest;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let msg = seq.next_element::<Message>()?;
+				if msg.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let msg = msg.unwrap();
+
+				let room_id = seq.next_element::<u64>()?;
+				if room_id.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let room_id = room_id.unwrap();
+
+				Ok(SendMessageRequest {
+					msg: msg,
+					room_id: room_id,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(2, Visitor)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum RoomStatus {
+	Active = 0,
+	Disabled = 1,
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message: string,
+	/** Arbitrary, response code for frontend logic */
+	code: number,
+}
+
+/* ============ Structs ============ */
+
+/** Contains basic information about a single message author */
+export interface AuthorRef {
+	/** Username of author */
+	username: string,
+}
+
+/** Resembles a single chat room */
+export interface Chatroom {
+	/** Unique id for this room */
+	id: number,
+	/** Messages in this room */
+	messages: Message[],
+	/** Name of room */
+	name: string,
+	status: number,
+}
+
+/** Parameters for a GetRooms request */
+export interface GetRoomsRequest {
+	/** Fetch only rooms with the given status */
+	with_status?: number,
+}
+
+/** Returns the chat rooms */
+export interface GetRoomsResponse {
+	/** A list of rooms */
+	rooms: Chatroom[],
+}
+
+/** Resembles a single chat message in a room */
+export interface Message {
+	/** Author who sent this message */
+	author: AuthorRef,
+	/** UNIX timestamp (seconds) of when this message was sent */
+	date: number,
+	/** Unique id for this message */
+	id: number,
+	/** Message content */
+	text: string,
+}
+
+/** Parameters for a SendMessage request */
+export interface SendMessageRequest {
+	/** The message to send. ID field is ignored. */
+	msg: Message,
+	/** Room to send the message in */
+	room_id: number,
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const RoomStatus = {
+	ACTIVE: 0,
+	DISABLED: 1
+} as const;
+
+/* ============ End Enums ============ */
\ No newline at end of file
@@ -0,0 +1,238 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message_: string,
+	/** Arbitrary, response code for frontend logic */
+	code_: number,
+}
+
+/* ============ Structs ============ */
+
+/** Contains basic information about a single message author */
+export interface AuthorRef {
+	/** Username of author */
+	username_: string,
+}
+
+export function serializeAuthorRef(obj: AuthorRef): any[] | null {
+	return [
+		obj.username_
+	];
+}
+
+export function deserializeAuthorRef(obj: any): AuthorRef | null {
+	if (obj == null) { return null; }
+	return {
+		username_: obj[0]
+	};
+}
+
+/** Resembles a single chat room */
+export interface Chatroom {
+	/** Unique id for this room */
+	id_: number,
+	/** Messages in this room */
+	messages_: Message[],
+	/** Name of room */
+	name_: string,
+	status_: number,
+}
+
+export function deserializeChatroom(obj: any): Chatroom | null {
+	if (obj == null) { return null; }
+	return {
+		id_: obj[0],
+		messages_: obj[1].map(function (elem: any) { return deserializeMessage(elem); }),
+		name_: obj[2],
+		status_: obj[3]
+	};
+}
+
+/** Parameters for a GetRooms request */
+export interface GetRoomsRequest {
+	/** Fetch only rooms with the given status */
+	withStatus_?: number,
+}
+
+export function serializeGetRoomsRequest(obj: GetRoomsRequest): any[] | null {
+	return [
+		obj.withStatus_
+	];
+}
+
+/** Returns the chat rooms */
+export interface GetRoomsResponse {
+	/** A list of rooms */
+	rooms_: Chatroom[],
+}
+
+export function deserializeGetRoomsResponse(obj: any): GetRoomsResponse | null {
+	if (obj == null) { return null; }
+	return {
+		rooms_: obj[0].map(function (elem: any) { return deserializeChatroom(elem); })
+	};
+}
+
+/** Resembles a single chat message in a room */
+export interface Message {
+	/** Author who sent this message */
+	author_: AuthorRef,
+	/** UNIX timestamp (seconds) of when this message was sent */
+	date_: number,
+	/** Unique id for this message */
+	id_: number,
+	/** Message content */
+	text_: string,
+}
+
+export function serializeMessage(obj: Message): any[] | null {
+	return [
+		serializeAuthorRef(obj.author_),
+		obj.date_,
+		obj.id_,
+		obj.text_
+	];
+}
+
+export function deserializeMessage(obj: any): Message | null {
+	if (obj == null) { return null; }
+	return {
+		author_: deserializeAuthorRef(obj[0]),
+		date_: obj[1],
+		id_: obj[2],
+		text_: obj[3]
+	};
+}
+
+/** Parameters for a SendMessage request */
+export interface SendMessageRequest {
+	/** The message to send. ID field is ignored. */
+	msg_: Message,
+	/** Room to send the message in */
+	roomId_: number,
+}
+
+export function serializeSendMessageRequest(obj: SendMessageRequest): any[] | null {
+	return [
+		serializeMessage(obj.msg_),
+		obj.roomId_
+	];
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const RoomStatus = {
+	ACTIVE_: 0,
+	DISABLED_: 1
+} as const;
+
+/* ============ End Enums ============ */
+
+/** Users must implement this interface to use `ZetroQuery` and `ZetroMutation` */
+export interface IZetroClient {
+	/**
+	 * Body is array-encoded data for the request. The return value MUST be the
+	 * response JSON body of the server. Implementors can add authentication
+	 * information to the request. This interface can be easily mocked for tests.
+	 * Note that a non-200 response status (even in the case of a malformed request)
+	 * MUST be considered an unexpected error and be handled accordingly.
+	 * In other words, only forward the parsed JSON body to this method if the
+	 * status is 200 OK.
+	 */
+	makeRequest_: (body: any) => Promise<any>;
+}
+
+/* ============ Queries ============ */
+
+export class ZetroQuery<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	getRooms_(requestBody: GetRoomsRequest): ZetroQuery<T & {getRooms_: GetRoomsResponse}> {
+		this.state_.push(["YiGepyIChwIjKAW1XFFbSD-DH-4", serializeGetRoomsRequest(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getRooms_ = deserializeGetRoomsResponse(item[1]);
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch_(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest_([1, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code_: result[1][0], message_: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code_: e.code_ || -1, message_: e.message_ || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Queries ============ */
+
+/* ============ Mutations ============ */
+
+export class ZetroMutation<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	sendMessage_(requestBody: SendMessageRequest): ZetroMutation<T & {sendMessage_: number}> {
+		this.state_.push(["89dG-t_0BkXmKSZq7mRDCYHXbBo", serializeSendMessageRequest(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.sendMessage_ = item[1];
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch_(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest_([2, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code_: result[1][0], message_: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code_: e.code_ || -1, message_: e.message_ || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Mutations ============ */
\ No newline at end of file
@@ -0,0 +1,236 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/// AUTOGENERATED. The struct that serializes error states
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZetroServerError {
+	/// Arbitrary, human-readable message for the end user
+	pub message: String,
+	/// Arbitrary, response code for frontend logic
+	pub code: i16,
+}
+
+/// Contains basic information about a single message author
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorRef {
+	/// Username of author
+	pub username: String,
+}
+
+/// Resembles a single chat room
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chatroom {
+	/// Unique id for this room
+	pub id: u64,
+	/// Messages in this room
+	pub messages: Vec<Message>,
+	/// Name of room
+	pub name: String,
+	pub status: RoomStatus,
+}
+
+/// Parameters for a GetRooms request
+#[derive(Debug, Clone, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRoomsRequest {
+	/// Fetch only rooms with the given status
+	pub with_status: Option<RoomStatus>,
+}
+
+/// Returns the chat rooms
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRoomsResponse {
+	/// A list of rooms
+	pub rooms: Vec<Chatroom>,
+}
+
+/// Resembles a single chat message in a room
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+	/// Author who sent this message
+	pub author: AuthorRef,
+	/// UNIX timestamp (seconds) of when this message was sent
+	pub date: u32,
+	/// Unique id for this message
+	pub id: u64,
+	/// Message content
+	pub text: String,
+}
+
+/// Parameters for a SendMessage request
+#[derive(Debug, Clone, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendMessageRequest {
+	/// The message to send. ID field is ignored.
+	pub msg: Message,
+	/// Room to send the message in
+	pub room_id: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum RoomStatus {
+	Active = 0,
+	Disabled = 1,
+}
+
+use warp::Filter;
+
+impl warp::reject::Reject for ZetroServerError {}
+
+pub struct ZetroContext {
+	data: std::collections::HashMap<std::any::TypeId, Box<dyn std::any::Any + Sync + Send>>,
+}
+
+impl ZetroContext {
+	pub fn new() -> Self {
+		ZetroContext {
+			data: std::collections::HashMap::new(),
+		}
+	}
+
+	pub fn insert<T>(&mut self, item: T)
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data.insert(std::any::TypeId::of::<T>(), Box::new(item));
+	}
+    
+	pub fn get<T>(&self) -> &T
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data
+			.get(&std::any::TypeId::of::<T>())
+			.unwrap()
+			.downcast_ref::<T>()
+			.unwrap()
+		}
+}
+
+fn _generate_data_reply(data: Vec<serde_json::Value>) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&data,
+		&serde_json::Value::Null,
+	))
+	.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+fn _generate_error_reply(code: i16, message: &str) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&serde_json::Value::Null,
+		&(&code, message),
+		))
+		.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+#[async_trait::async_trait]
+pub trait ZetroQueries {
+	async fn get_rooms<'a>(ctx: &'a ZetroContext, request: GetRoomsRequest) -> Result<GetRoomsResponse, ZetroServerError>;
+}
+
+#[async_trait::async_trait]
+pub trait ZetroMutations {
+	async fn send_message<'a>(ctx: &'a ZetroContext, request: SendMessageRequest) -> Result<u64, ZetroServerError>;
+}
+
+pub fn generate_routes<Q, M>(ctx: ZetroContext, _queries: Q, _mutations: M) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone
+where Q: ZetroQueries, M: ZetroMutations,
+{
+        use std::sync::Arc;
+
+        let ctx = Arc::new(ctx);
+
+        warp::any()
+            .and(warp::any().map(move || Arc::clone(&ctx)))
+            .and(warp::body::bytes())
+            .then(|ctx: Arc<ZetroContext>, body: bytes::Bytes| async move {
+                let mut retval: Vec<serde_json::Value> = Vec::new();
+                let request_payload = serde_json::from_slice::<(u8, Vec<serde_json::Value>)>(&body);
+                if request_payload.is_err() {
+                    return _generate_error_reply(400, "Bad request");
+                }
+                // Determines whether the request is a query or mutation
+                let (method_code, operations) = request_payload.unwrap();
+                for op in operations {
+                    if !op.is_array() {
+                        return _generate_error_reply(400, "Operations must be an array");
+                    }
+                    let arr = op.as_array().unwrap();
+                    let route_name = arr.get(0);
+                    let route_body = arr.get(1);
+
+                    if route_name.is_none() || route_body.is_none() {
+                        return _generate_error_reply(400, "Route name and route body are mandatory");
+                    }
+
+                    let route_name = route_name.unwrap();
+                    let route_body = route_body.unwrap().to_owned();
+
+                    if !route_name.is_string() {
+                        return _generate_error_reply(400, "Route name must be string");
+                    }
+                    let route_name = route_name.as_str().unwrap();
+                    if method_code == 1 {
+                        // Handle query
+                        retval.push(match route_name {
+                            
+                        // 'GetRooms' route:
+                        "YiGepyIChwIjKAW1XFFbSD-DH-4" => {
+                            let route_body = serde_json::from_value::<GetRoomsRequest>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_rooms(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else if method_code == 2 {
+                        // Handle mutation
+                        retval.push(match route_name {
+                            
+                        // 'SendMessage' route:
+                        "89dG-t_0BkXmKSZq7mRDCYHXbBo" => {
+                            let route_body = serde_json::from_value::<SendMessageRequest>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = M::send_message(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else {
+                        return _generate_error_reply(400, "Bad request");
+                    }
+                }
+                _generate_data_reply(retval)
+            })
+        }
\ No newline at end of file
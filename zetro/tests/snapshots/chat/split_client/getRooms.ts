@@ -0,0 +1,62 @@
+// This is an autogenerated file. DO NOT EDIT
+
+import { Message, deserializeMessage } from "./shared";
+
+/* ============ Structs ============ */
+
+/** Resembles a single chat room */
+export interface Chatroom {
+	/** Unique id for this room */
+	id: number,
+	/** Messages in this room */
+	messages: Message[],
+	/** Name of room */
+	name: string,
+	status: number,
+}
+
+export function deserializeChatroom(obj: any): Chatroom | null {
+	if (obj == null) { return null; }
+	return {
+		id: obj[0],
+		messages: obj[1].map(function (elem: any) { return deserializeMessage(elem); }),
+		name: obj[2],
+		status: obj[3]
+	};
+}
+
+/** Parameters for a GetRooms request */
+export interface GetRoomsRequest {
+	/** Fetch only rooms with the given status */
+	withStatus?: number,
+}
+
+export function serializeGetRoomsRequest(obj: GetRoomsRequest): any[] | null {
+	return [
+		obj.withStatus
+	];
+}
+
+/** Returns the chat rooms */
+export interface GetRoomsResponse {
+	/** A list of rooms */
+	rooms: Chatroom[],
+}
+
+export function deserializeGetRoomsResponse(obj: any): GetRoomsResponse | null {
+	if (obj == null) { return null; }
+	return {
+		rooms: obj[0].map(function (elem: any) { return deserializeChatroom(elem); })
+	};
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const RoomStatus = {
+	ACTIVE: 0,
+	DISABLED: 1
+} as const;
+
+/* ============ End Enums ============ */
\ No newline at end of file
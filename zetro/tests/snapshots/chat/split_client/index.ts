@@ -0,0 +1,124 @@
+// This is an autogenerated file. DO NOT EDIT
+
+import { GetRoomsRequest, GetRoomsResponse, deserializeGetRoomsResponse, serializeGetRoomsRequest } from "./getRooms";
+
+import { SendMessageRequest, serializeSendMessageRequest } from "./sendMessage";
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message: string,
+	/** Arbitrary, response code for frontend logic */
+	code: number,
+}
+
+export * from "./shared";
+
+export * from "./getRooms";
+
+export * from "./sendMessage";
+
+/** Users must implement this interface to use `ZetroQuery` and `ZetroMutation` */
+export interface IZetroClient {
+	/**
+	 * Body is array-encoded data for the request. The return value MUST be the
+	 * response JSON body of the server. Implementors can add authentication
+	 * information to the request. This interface can be easily mocked for tests.
+	 * Note that a non-200 response status (even in the case of a malformed request)
+	 * MUST be considered an unexpected error and be handled accordingly.
+	 * In other words, only forward the parsed JSON body to this method if the
+	 * status is 200 OK.
+	 */
+	makeRequest: (body: any) => Promise<any>;
+}
+
+/* ============ Queries ============ */
+
+export class ZetroQuery<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	getRooms(requestBody: GetRoomsRequest): ZetroQuery<T & {getRooms: GetRoomsResponse}> {
+		this.state_.push(["YiGepyIChwIjKAW1XFFbSD-DH-4", serializeGetRoomsRequest(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getRooms = deserializeGetRoomsResponse(item[1]);
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([1, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Queries ============ */
+
+/* ============ Mutations ============ */
+
+export class ZetroMutation<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	sendMessage(requestBody: SendMessageRequest): ZetroMutation<T & {sendMessage: number}> {
+		this.state_.push(["89dG-t_0BkXmKSZq7mRDCYHXbBo", serializeSendMessageRequest(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.sendMessage = item[1];
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([2, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Mutations ============ */
\ No newline at end of file
@@ -0,0 +1,22 @@
+// This is an autogenerated file. DO NOT EDIT
+
+import { Message, serializeMessage } from "./shared";
+
+/* ============ Structs ============ */
+
+/** Parameters for a SendMessage request */
+export interface SendMessageRequest {
+	/** The message to send. ID field is ignored. */
+	msg: Message,
+	/** Room to send the message in */
+	roomId: number,
+}
+
+export function serializeSendMessageRequest(obj: SendMessageRequest): any[] | null {
+	return [
+		serializeMessage(obj.msg),
+		obj.roomId
+	];
+}
+
+/* ============ End Structs ============ */
\ No newline at end of file
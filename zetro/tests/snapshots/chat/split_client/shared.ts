@@ -0,0 +1,55 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/* ============ Structs ============ */
+
+/** Contains basic information about a single message author */
+export interface AuthorRef {
+	/** Username of author */
+	username: string,
+}
+
+export function serializeAuthorRef(obj: AuthorRef): any[] | null {
+	return [
+		obj.username
+	];
+}
+
+export function deserializeAuthorRef(obj: any): AuthorRef | null {
+	if (obj == null) { return null; }
+	return {
+		username: obj[0]
+	};
+}
+
+/** Resembles a single chat message in a room */
+export interface Message {
+	/** Author who sent this message */
+	author: AuthorRef,
+	/** UNIX timestamp (seconds) of when this message was sent */
+	date: number,
+	/** Unique id for this message */
+	id: number,
+	/** Message content */
+	text: string,
+}
+
+export function serializeMessage(obj: Message): any[] | null {
+	return [
+		serializeAuthorRef(obj.author),
+		obj.date,
+		obj.id,
+		obj.text
+	];
+}
+
+export function deserializeMessage(obj: any): Message | null {
+	if (obj == null) { return null; }
+	return {
+		author: deserializeAuthorRef(obj[0]),
+		date: obj[1],
+		id: obj[2],
+		text: obj[3]
+	};
+}
+
+/* ============ End Structs ============ */
\ No newline at end of file
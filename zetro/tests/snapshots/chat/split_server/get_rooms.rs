@@ -0,0 +1,39 @@
+// This is an autogenerated file. DO NOT EDIT
+
+use super::shared::{Message};
+
+/// Resembles a single chat room
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chatroom {
+	/// Unique id for this room
+	pub id: u64,
+	/// Messages in this room
+	pub messages: Vec<Message>,
+	/// Name of room
+	pub name: String,
+	pub status: RoomStatus,
+}
+
+/// Parameters for a GetRooms request
+#[derive(Debug, Clone, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRoomsRequest {
+	/// Fetch only rooms with the given status
+	pub with_status: Option<RoomStatus>,
+}
+
+/// Returns the chat rooms
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRoomsResponse {
+	/// A list of rooms
+	pub rooms: Vec<Chatroom>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum RoomStatus {
+	Active = 0,
+	Disabled = 1,
+}
\ No newline at end of file
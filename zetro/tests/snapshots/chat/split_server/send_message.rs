@@ -0,0 +1,13 @@
+// This is an autogenerated file. DO NOT EDIT
+
+use super::shared::{Message};
+
+/// Parameters for a SendMessage request
+#[derive(Debug, Clone, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendMessageRequest {
+	/// The message to send. ID field is ignored.
+	pub msg: Message,
+	/// Room to send the message in
+	pub room_id: u64,
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/// Contains basic information about a single message author
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorRef {
+	/// Username of author
+	pub username: String,
+}
+
+/// Resembles a single chat message in a room
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+	/// Author who sent this message
+	pub author: AuthorRef,
+	/// UNIX timestamp (seconds) of when this message was sent
+	pub date: u32,
+	/// Unique id for this message
+	pub id: u64,
+	/// Message content
+	pub text: String,
+}
\ No newline at end of file
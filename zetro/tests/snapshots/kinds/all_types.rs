@@ -0,0 +1,541 @@
+// This is an autogenerated file. DO NOT EDIT
+
+#![allow(dead_code)]
+use serde::ser::SerializeTuple;
+
+/// AUTOGENERATED. The struct that serializes error states
+#[derive(Debug, Clone)]
+pub struct ZetroServerError {
+	/// Arbitrary, human-readable message for the end user
+	pub message: String,
+	/// Arbitrary, response code for frontend logic
+	pub code: i16,
+}
+
+/// Every combination of nullable and multiple
+/// with a multi-line description
+#[derive(Debug, Clone)]
+pub struct Modifiers {
+	pub many_primitives: Vec<Primitives>,
+	pub multiple: Vec<String>,
+	pub nullable: Option<String>,
+	pub nullable_many_primitives: Option<Vec<Primitives>>,
+	pub nullable_multiple: Option<Vec<String>>,
+	pub nullable_primitives: Option<Primitives>,
+	pub nullable_status: Option<Status>,
+	pub nullable_statuses: Option<Vec<Status>>,
+	pub plain: String,
+	pub primitives: Primitives,
+	pub status: Status,
+	pub statuses: Vec<Status>,
+}
+
+impl serde::ser::Serialize for Modifiers {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(12)?;
+		state.serialize_element(&self.many_primitives)?;
+		state.serialize_element(&self.multiple)?;
+		state.serialize_element(&self.nullable)?;
+		state.serialize_element(&self.nullable_many_primitives)?;
+		state.serialize_element(&self.nullable_multiple)?;
+		state.serialize_element(&self.nullable_primitives)?;
+		state.serialize_element(&self.nullable_status)?;
+		state.serialize_element(&self.nullable_statuses)?;
+		state.serialize_element(&self.plain)?;
+		state.serialize_element(&self.primitives)?;
+		state.serialize_element(&self.status)?;
+		state.serialize_element(&self.statuses)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Modifiers {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Modifiers;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let many_primitives = seq.next_element::<Vec<Primitives>>()?;
+				if many_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let many_primitives = many_primitives.unwrap();
+
+				let multiple = seq.next_element::<Vec<String>>()?;
+				if multiple.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let multiple = multiple.unwrap();
+
+				let nullable = seq.next_element::<Option<String>>()?;
+				if nullable.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable = nullable.unwrap();
+
+				let nullable_many_primitives = seq.next_element::<Option<Vec<Primitives>>>()?;
+				if nullable_many_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_many_primitives = nullable_many_primitives.unwrap();
+
+				let nullable_multiple = seq.next_element::<Option<Vec<String>>>()?;
+				if nullable_multiple.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_multiple = nullable_multiple.unwrap();
+
+				let nullable_primitives = seq.next_element::<Option<Primitives>>()?;
+				if nullable_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_primitives = nullable_primitives.unwrap();
+
+				let nullable_status = seq.next_element::<Option<Status>>()?;
+				if nullable_status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_status = nullable_status.unwrap();
+
+				let nullable_statuses = seq.next_element::<Option<Vec<Status>>>()?;
+				if nullable_statuses.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_statuses = nullable_statuses.unwrap();
+
+				let plain = seq.next_element::<String>()?;
+				if plain.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let plain = plain.unwrap();
+
+				let primitives = seq.next_element::<Primitives>()?;
+				if primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let primitives = primitives.unwrap();
+
+				let status = seq.next_element::<Status>()?;
+				if status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let status = status.unwrap();
+
+				let statuses = seq.next_element::<Vec<Status>>()?;
+				if statuses.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let statuses = statuses.unwrap();
+
+				Ok(Modifiers {
+					many_primitives: many_primitives,
+					multiple: multiple,
+					nullable: nullable,
+					nullable_many_primitives: nullable_many_primitives,
+					nullable_multiple: nullable_multiple,
+					nullable_primitives: nullable_primitives,
+					nullable_status: nullable_status,
+					nullable_statuses: nullable_statuses,
+					plain: plain,
+					primitives: primitives,
+					status: status,
+					statuses: statuses,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(12, Visitor)
+	}
+}
+
+/// Every primitive type
+#[derive(Debug, Clone)]
+pub struct Primitives {
+	pub boolean: bool,
+	pub float_32: f32,
+	pub float_64: f64,
+	pub int_16: i16,
+	pub int_32: i32,
+	pub int_64: i64,
+	pub int_8: i8,
+	/// A documented field
+	pub text: String,
+	pub uint_16: u16,
+	pub uint_32: u32,
+	pub uint_64: u64,
+	pub uint_8: u8,
+}
+
+impl serde::ser::Serialize for Primitives {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(12)?;
+		state.serialize_element(&self.boolean)?;
+		state.serialize_element(&self.float_32)?;
+		state.serialize_element(&self.float_64)?;
+		state.serialize_element(&self.int_16)?;
+		state.serialize_element(&self.int_32)?;
+		state.serialize_element(&self.int_64)?;
+		state.serialize_element(&self.int_8)?;
+		state.serialize_element(&self.text)?;
+		state.serialize_element(&self.uint_16)?;
+		state.serialize_element(&self.uint_32)?;
+		state.serialize_element(&self.uint_64)?;
+		state.serialize_element(&self.uint_8)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Primitives {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Primitives;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let boolean = seq.next_element::<bool>()?;
+				if boolean.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let boolean = boolean.unwrap();
+
+				let float_32 = seq.next_element::<f32>()?;
+				if float_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let float_32 = float_32.unwrap();
+
+				let float_64 = seq.next_element::<f64>()?;
+				if float_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let float_64 = float_64.unwrap();
+
+				let int_16 = seq.next_element::<i16>()?;
+				if int_16.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_16 = int_16.unwrap();
+
+				let int_32 = seq.next_element::<i32>()?;
+				if int_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_32 = int_32.unwrap();
+
+				let int_64 = seq.next_element::<i64>()?;
+				if int_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_64 = int_64.unwrap();
+
+				let int_8 = seq.next_element::<i8>()?;
+				if int_8.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_8 = int_8.unwrap();
+
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				let uint_16 = seq.next_element::<u16>()?;
+				if uint_16.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_16 = uint_16.unwrap();
+
+				let uint_32 = seq.next_element::<u32>()?;
+				if uint_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_32 = uint_32.unwrap();
+
+				let uint_64 = seq.next_element::<u64>()?;
+				if uint_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_64 = uint_64.unwrap();
+
+				let uint_8 = seq.next_element::<u8>()?;
+				if uint_8.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_8 = uint_8.unwrap();
+
+				Ok(Primitives {
+					boolean: boolean,
+					float_32: float_32,
+					float_64: float_64,
+					int_16: int_16,
+					int_32: int_32,
+					int_64: int_64,
+					int_8: int_8,
+					text: text,
+					uint_16: uint_16,
+					uint_32: uint_32,
+					uint_64: uint_64,
+					uint_8: uint_8,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(12, Visitor)
+	}
+}
+
+#[allow(non_camel_case_types)]
+/// A nested object
+#[derive(Debug, Clone)]
+pub struct Tree_label {
+	pub color: Option<Status>,
+	pub text: String,
+}
+
+#[allow(non_camel_case_types)]
+/// A nullable list of nested objects
+#[derive(Debug, Clone)]
+pub struct Tree_notes {
+	pub text: String,
+}
+
+/// A recursive struct with nested objects
+#[derive(Debug, Clone)]
+pub struct Tree {
+	pub children: Vec<Box<Tree>>,
+	pub label: Tree_label,
+	pub notes: Option<Vec<Tree_notes>>,
+	pub parent: Option<Box<Tree>>,
+}
+
+impl serde::ser::Serialize for Tree_label {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(2)?;
+		state.serialize_element(&self.color)?;
+		state.serialize_element(&self.text)?;
+		state.end()    
+	}
+}
+
+impl serde::ser::Serialize for Tree_notes {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.text)?;
+		state.end()    
+	}
+}
+
+impl serde::ser::Serialize for Tree {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(4)?;
+		state.serialize_element(&self.children)?;
+		state.serialize_element(&self.label)?;
+		state.serialize_element(&self.notes)?;
+		state.serialize_element(&self.parent)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree_label {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree_label;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let color = seq.next_element::<Option<Status>>()?;
+				if color.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let color = color.unwrap();
+
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				Ok(Tree_label {
+					color: color,
+					text: text,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(2, Visitor)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree_notes {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree_notes;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				Ok(Tree_notes {
+					text: text,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let children = seq.next_element::<Vec<Box<Tree>>>()?;
+				if children.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let children = children.unwrap();
+
+				let label = seq.next_element::<Tree_label>()?;
+				if label.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let label = label.unwrap();
+
+				let notes = seq.next_element::<Option<Vec<Tree_notes>>>()?;
+				if notes.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let notes = notes.unwrap();
+
+				let parent = seq.next_element::<Option<Box<Tree>>>()?;
+				if parent.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let parent = parent.unwrap();
+
+				Ok(Tree {
+					children: children,
+					label: label,
+					notes: notes,
+					parent: parent,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(4, Visitor)
+	}
+}
+
+/// No route uses this struct
+#[derive(Debug, Clone)]
+pub struct Unused {
+	pub id: u64,
+}
+
+impl serde::ser::Serialize for Unused {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.id)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Unused {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Unused;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let id = seq.next_element::<u64>()?;
+				if id.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let id = id.unwrap();
+
+				Ok(Unused {
+					id: id,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum Status {
+	Active = 0,
+	Disabled = 1,
+	Deleted = 2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum UnusedEnum {
+	Only = 0,
+}
\ No newline at end of file
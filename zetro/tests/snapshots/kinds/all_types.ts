@@ -0,0 +1,86 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message: string,
+	/** Arbitrary, response code for frontend logic */
+	code: number,
+}
+
+/* ============ Structs ============ */
+
+/** Every combination of nullable and multiple
+with a multi-line description */
+export interface Modifiers {
+	many_primitives: Primitives[],
+	multiple: string[],
+	nullable?: string,
+	nullable_many_primitives?: Primitives[],
+	nullable_multiple?: string[],
+	nullable_primitives?: Primitives,
+	nullable_status?: number,
+	nullable_statuses?: number[],
+	plain: string,
+	primitives: Primitives,
+	status: number,
+	statuses: number[],
+}
+
+/** Every primitive type */
+export interface Primitives {
+	boolean: boolean,
+	float_32: number,
+	float_64: number,
+	int_16: number,
+	int_32: number,
+	int_64: number,
+	int_8: number,
+	/** A documented field */
+	text: string,
+	uint_16: number,
+	uint_32: number,
+	uint_64: number,
+	uint_8: number,
+}
+
+/** A nested object */
+interface Tree_label {
+	color?: number,
+	text: string,
+}
+
+/** A nullable list of nested objects */
+interface Tree_notes {
+	text: string,
+}
+
+/** A recursive struct with nested objects */
+export interface Tree {
+	children: Tree[],
+	label: Tree_label,
+	notes?: Tree_notes[],
+	parent?: Tree,
+}
+
+/** No route uses this struct */
+export interface Unused {
+	id: number,
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const Status = {
+	ACTIVE: 0,
+	DISABLED: 1,
+	DELETED: 2
+} as const;
+
+export const UnusedEnum = {
+	ONLY: 0
+} as const;
+
+/* ============ End Enums ============ */
\ No newline at end of file
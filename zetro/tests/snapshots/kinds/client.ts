@@ -0,0 +1,209 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message: string,
+	/** Arbitrary, response code for frontend logic */
+	code: number,
+}
+
+/* ============ Structs ============ */
+
+/** Every combination of nullable and multiple
+with a multi-line description */
+export interface Modifiers {
+	manyPrimitives: Primitives[],
+	multiple: string[],
+	nullable?: string,
+	nullableManyPrimitives?: Primitives[],
+	nullableMultiple?: string[],
+	nullablePrimitives?: Primitives,
+	nullableStatus?: number,
+	nullableStatuses?: number[],
+	plain: string,
+	primitives: Primitives,
+	status: number,
+	statuses: number[],
+}
+
+/** Every primitive type */
+export interface Primitives {
+	boolean: boolean,
+	float32: number,
+	float64: number,
+	int16: number,
+	int32: number,
+	int64: number,
+	int8: number,
+	/** A documented field */
+	text: string,
+	uint16: number,
+	uint32: number,
+	uint64: number,
+	uint8: number,
+}
+
+/** A nested object */
+interface Tree_label {
+	color?: number,
+	text: string,
+}
+
+/** A nullable list of nested objects */
+interface Tree_notes {
+	text: string,
+}
+
+/** A recursive struct with nested objects */
+export interface Tree {
+	children: Tree[],
+	label: Tree_label,
+	notes?: Tree_notes[],
+	parent?: Tree,
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const Status = {
+	ACTIVE: 0,
+	DISABLED: 1,
+	DELETED: 2
+} as const;
+
+/* ============ End Enums ============ */
+
+/** Users must implement this interface to use `ZetroQuery` and `ZetroMutation` */
+export interface IZetroClient {
+	/**
+	 * Body is array-encoded data for the request. The return value MUST be the
+	 * response JSON body of the server. Implementors can add authentication
+	 * information to the request. This interface can be easily mocked for tests.
+	 * Note that a non-200 response status (even in the case of a malformed request)
+	 * MUST be considered an unexpected error and be handled accordingly.
+	 * In other words, only forward the parsed JSON body to this method if the
+	 * status is 200 OK.
+	 */
+	makeRequest: (body: any) => Promise<any>;
+}
+
+/* ============ Queries ============ */
+
+export class ZetroQuery<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	findTree(requestBody: Tree[]): ZetroQuery<T & {findTree: Tree}> {
+		this.state_.push(["ThjA6s-HKHz_sXk69anaU1hNRn4", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.findTree = item[1];
+		})
+		return this as any;
+	}
+	getPrimitives(requestBody: number): ZetroQuery<T & {getPrimitives: Primitives}> {
+		this.state_.push(["mIQuSzSjfIc0SMiD5GOKVubn5yU", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getPrimitives = item[1];
+		})
+		return this as any;
+	}
+	getStatus(requestBody?: string): ZetroQuery<T & {getStatus: number}> {
+		this.state_.push(["MfZjc6qr3q2voF7lAnM0q9i-xF0", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getStatus = item[1];
+		})
+		return this as any;
+	}
+	getTrees(requestBody: Primitives): ZetroQuery<T & {getTrees: Tree[]}> {
+		this.state_.push(["P2xqdlJPNe-SDjR33ar2uo3AefI", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getTrees = item[1];
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([1, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Queries ============ */
+
+/* ============ Mutations ============ */
+
+export class ZetroMutation<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	delete(requestBody: number[]): ZetroMutation<T & {delete: boolean}> {
+		this.state_.push(["YkwVdcYEoSGmiXeqTO3aHPhG4FE", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.delete = item[1];
+		})
+		return this as any;
+	}
+	saveModifiers(requestBody?: Modifiers[]): ZetroMutation<T & {saveModifiers: Modifiers[]}> {
+		this.state_.push(["43xA5uwSorvlcK0CWr2xmBRIT6A", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.saveModifiers = item[1];
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([2, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Mutations ============ */
\ No newline at end of file
@@ -0,0 +1,327 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message_: string,
+	/** Arbitrary, response code for frontend logic */
+	code_: number,
+}
+
+/* ============ Structs ============ */
+
+/** Every combination of nullable and multiple
+with a multi-line description */
+export interface Modifiers {
+	manyPrimitives_: Primitives[],
+	multiple_: string[],
+	nullable_?: string,
+	nullableManyPrimitives_?: Primitives[],
+	nullableMultiple_?: string[],
+	nullablePrimitives_?: Primitives,
+	nullableStatus_?: number,
+	nullableStatuses_?: number[],
+	plain_: string,
+	primitives_: Primitives,
+	status_: number,
+	statuses_: number[],
+}
+
+export function serializeModifiers(obj: Modifiers): any[] | null {
+	return [
+		obj.manyPrimitives_.map(function (nested) { return serializePrimitives(nested); }),
+		obj.multiple_,
+		obj.nullable_,
+		obj.nullableManyPrimitives_?.map(function (nested) { return serializePrimitives(nested); }),
+		obj.nullableMultiple_,
+		obj.nullablePrimitives_ == null ? null : serializePrimitives(obj.nullablePrimitives_),
+		obj.nullableStatus_,
+		obj.nullableStatuses_,
+		obj.plain_,
+		serializePrimitives(obj.primitives_),
+		obj.status_,
+		obj.statuses_
+	];
+}
+
+export function deserializeModifiers(obj: any): Modifiers | null {
+	if (obj == null) { return null; }
+	return {
+		manyPrimitives_: obj[0].map(function (elem: any) { return deserializePrimitives(elem); }),
+		multiple_: obj[1],
+		nullable_: obj[2],
+		nullableManyPrimitives_: obj[3]?.map(function (elem: any) { return deserializePrimitives(elem); }),
+		nullableMultiple_: obj[4],
+		nullablePrimitives_: deserializePrimitives(obj[5]),
+		nullableStatus_: obj[6],
+		nullableStatuses_: obj[7],
+		plain_: obj[8],
+		primitives_: deserializePrimitives(obj[9]),
+		status_: obj[10],
+		statuses_: obj[11]
+	};
+}
+
+/** Every primitive type */
+export interface Primitives {
+	boolean_: boolean,
+	float32_: number,
+	float64_: number,
+	int16_: number,
+	int32_: number,
+	int64_: number,
+	int8_: number,
+	/** A documented field */
+	text_: string,
+	uint16_: number,
+	uint32_: number,
+	uint64_: number,
+	uint8_: number,
+}
+
+export function serializePrimitives(obj: Primitives): any[] | null {
+	return [
+		obj.boolean_,
+		obj.float32_,
+		obj.float64_,
+		obj.int16_,
+		obj.int32_,
+		obj.int64_,
+		obj.int8_,
+		obj.text_,
+		obj.uint16_,
+		obj.uint32_,
+		obj.uint64_,
+		obj.uint8_
+	];
+}
+
+export function deserializePrimitives(obj: any): Primitives | null {
+	if (obj == null) { return null; }
+	return {
+		boolean_: obj[0],
+		float32_: obj[1],
+		float64_: obj[2],
+		int16_: obj[3],
+		int32_: obj[4],
+		int64_: obj[5],
+		int8_: obj[6],
+		text_: obj[7],
+		uint16_: obj[8],
+		uint32_: obj[9],
+		uint64_: obj[10],
+		uint8_: obj[11]
+	};
+}
+
+/** A nested object */
+interface Tree_label {
+	color_?: number,
+	text_: string,
+}
+
+/** A nullable list of nested objects */
+interface Tree_notes {
+	text_: string,
+}
+
+/** A recursive struct with nested objects */
+export interface Tree {
+	children_: Tree[],
+	label_: Tree_label,
+	notes_?: Tree_notes[],
+	parent_?: Tree,
+}
+
+function serializeTree_label(obj: Tree_label): any[] | null {
+	return [
+		obj.color_,
+		obj.text_
+	];
+}
+
+function serializeTree_notes(obj?: Tree_notes): any[] | null {
+if (obj == null) { return null; }
+	return [
+		obj.text_
+	];
+}
+
+export function serializeTree(obj: Tree): any[] | null {
+	return [
+		obj.children_.map(function (nested) { return serializeTree(nested); }),
+		serializeTree_label(obj.label_),
+		obj.notes_?.map(function (elem: any) { return serializeTree_notes(elem); }),
+		obj.parent_ == null ? null : serializeTree(obj.parent_)
+	];
+}
+
+function deserializeTree_label(obj: any): Tree_label | null {
+	if (obj == null) { return null; }
+	return {
+		color_: obj[0],
+		text_: obj[1]
+	};
+}
+
+function deserializeTree_notes(obj: any): Tree_notes | null {
+	if (obj == null) { return null; }
+	return {
+		text_: obj[0]
+	};
+}
+
+export function deserializeTree(obj: any): Tree | null {
+	if (obj == null) { return null; }
+	return {
+		children_: obj[0].map(function (elem: any) { return deserializeTree(elem); }),
+		label_: deserializeTree_label(obj[1]),
+		notes_: obj[2]?.map(function (elem: any) { return deserializeTree_notes(elem); }),
+		parent_: deserializeTree(obj[3])
+	};
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const Status = {
+	ACTIVE_: 0,
+	DISABLED_: 1,
+	DELETED_: 2
+} as const;
+
+/* ============ End Enums ============ */
+
+/** Users must implement this interface to use `ZetroQuery` and `ZetroMutation` */
+export interface IZetroClient {
+	/**
+	 * Body is array-encoded data for the request. The return value MUST be the
+	 * response JSON body of the server. Implementors can add authentication
+	 * information to the request. This interface can be easily mocked for tests.
+	 * Note that a non-200 response status (even in the case of a malformed request)
+	 * MUST be considered an unexpected error and be handled accordingly.
+	 * In other words, only forward the parsed JSON body to this method if the
+	 * status is 200 OK.
+	 */
+	makeRequest_: (body: any) => Promise<any>;
+}
+
+/* ============ Queries ============ */
+
+export class ZetroQuery<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	findTree_(requestBody: Tree[]): ZetroQuery<T & {findTree_: Tree}> {
+		this.state_.push(["ThjA6s-HKHz_sXk69anaU1hNRn4", requestBody.map(function (elem: any) { return serializeTree(elem); })]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.findTree_ = deserializeTree(item[1]);
+		})
+		return this as any;
+	}
+	getPrimitives_(requestBody: number): ZetroQuery<T & {getPrimitives_: Primitives}> {
+		this.state_.push(["mIQuSzSjfIc0SMiD5GOKVubn5yU", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getPrimitives_ = deserializePrimitives(item[1]);
+		})
+		return this as any;
+	}
+	getStatus_(requestBody?: string): ZetroQuery<T & {getStatus_: number}> {
+		this.state_.push(["MfZjc6qr3q2voF7lAnM0q9i-xF0", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getStatus_ = item[1];
+		})
+		return this as any;
+	}
+	getTrees_(requestBody: Primitives): ZetroQuery<T & {getTrees_: Tree[]}> {
+		this.state_.push(["P2xqdlJPNe-SDjR33ar2uo3AefI", serializePrimitives(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getTrees_ = item[1].map(function (elem: any) { return deserializeTree(elem); });
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch_(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest_([1, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code_: result[1][0], message_: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code_: e.code_ || -1, message_: e.message_ || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Queries ============ */
+
+/* ============ Mutations ============ */
+
+export class ZetroMutation<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	delete_(requestBody: number[]): ZetroMutation<T & {delete_: boolean}> {
+		this.state_.push(["YkwVdcYEoSGmiXeqTO3aHPhG4FE", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.delete_ = item[1];
+		})
+		return this as any;
+	}
+	saveModifiers_(requestBody?: Modifiers[]): ZetroMutation<T & {saveModifiers_: Modifiers[]}> {
+		this.state_.push(["43xA5uwSorvlcK0CWr2xmBRIT6A", requestBody?.map(function (elem: any) { return serializeModifiers(elem); })]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.saveModifiers_ = item[1]?.map(function (elem: any) { return deserializeModifiers(elem); });
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch_(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest_([2, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code_: result[1][0], message_: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code_: e.code_ || -1, message_: e.message_ || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Mutations ============ */
\ No newline at end of file
@@ -0,0 +1,327 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/** AUTOGENERATED
+The struct that serializes error states */
+export interface ZetroServerError {
+	/** Arbitrary, human-readable message for the end user */
+	message: string,
+	/** Arbitrary, response code for frontend logic */
+	code: number,
+}
+
+/* ============ Structs ============ */
+
+/** Every combination of nullable and multiple
+with a multi-line description */
+export interface Modifiers {
+	manyPrimitives: Primitives[],
+	multiple: string[],
+	nullable?: string,
+	nullableManyPrimitives?: Primitives[],
+	nullableMultiple?: string[],
+	nullablePrimitives?: Primitives,
+	nullableStatus?: number,
+	nullableStatuses?: number[],
+	plain: string,
+	primitives: Primitives,
+	status: number,
+	statuses: number[],
+}
+
+export function serializeModifiers(obj: Modifiers): any[] | null {
+	return [
+		obj.manyPrimitives.map(function (nested) { return serializePrimitives(nested); }),
+		obj.multiple,
+		obj.nullable,
+		obj.nullableManyPrimitives?.map(function (nested) { return serializePrimitives(nested); }),
+		obj.nullableMultiple,
+		obj.nullablePrimitives == null ? null : serializePrimitives(obj.nullablePrimitives),
+		obj.nullableStatus,
+		obj.nullableStatuses,
+		obj.plain,
+		serializePrimitives(obj.primitives),
+		obj.status,
+		obj.statuses
+	];
+}
+
+export function deserializeModifiers(obj: any): Modifiers | null {
+	if (obj == null) { return null; }
+	return {
+		manyPrimitives: obj[0].map(function (elem: any) { return deserializePrimitives(elem); }),
+		multiple: obj[1],
+		nullable: obj[2],
+		nullableManyPrimitives: obj[3]?.map(function (elem: any) { return deserializePrimitives(elem); }),
+		nullableMultiple: obj[4],
+		nullablePrimitives: deserializePrimitives(obj[5]),
+		nullableStatus: obj[6],
+		nullableStatuses: obj[7],
+		plain: obj[8],
+		primitives: deserializePrimitives(obj[9]),
+		status: obj[10],
+		statuses: obj[11]
+	};
+}
+
+/** Every primitive type */
+export interface Primitives {
+	boolean: boolean,
+	float32: number,
+	float64: number,
+	int16: number,
+	int32: number,
+	int64: number,
+	int8: number,
+	/** A documented field */
+	text: string,
+	uint16: number,
+	uint32: number,
+	uint64: number,
+	uint8: number,
+}
+
+export function serializePrimitives(obj: Primitives): any[] | null {
+	return [
+		obj.boolean,
+		obj.float32,
+		obj.float64,
+		obj.int16,
+		obj.int32,
+		obj.int64,
+		obj.int8,
+		obj.text,
+		obj.uint16,
+		obj.uint32,
+		obj.uint64,
+		obj.uint8
+	];
+}
+
+export function deserializePrimitives(obj: any): Primitives | null {
+	if (obj == null) { return null; }
+	return {
+		boolean: obj[0],
+		float32: obj[1],
+		float64: obj[2],
+		int16: obj[3],
+		int32: obj[4],
+		int64: obj[5],
+		int8: obj[6],
+		text: obj[7],
+		uint16: obj[8],
+		uint32: obj[9],
+		uint64: obj[10],
+		uint8: obj[11]
+	};
+}
+
+/** A nested object */
+interface Tree_label {
+	color?: number,
+	text: string,
+}
+
+/** A nullable list of nested objects */
+interface Tree_notes {
+	text: string,
+}
+
+/** A recursive struct with nested objects */
+export interface Tree {
+	children: Tree[],
+	label: Tree_label,
+	notes?: Tree_notes[],
+	parent?: Tree,
+}
+
+function serializeTree_label(obj: Tree_label): any[] | null {
+	return [
+		obj.color,
+		obj.text
+	];
+}
+
+function serializeTree_notes(obj?: Tree_notes): any[] | null {
+if (obj == null) { return null; }
+	return [
+		obj.text
+	];
+}
+
+export function serializeTree(obj: Tree): any[] | null {
+	return [
+		obj.children.map(function (nested) { return serializeTree(nested); }),
+		serializeTree_label(obj.label),
+		obj.notes?.map(function (elem: any) { return serializeTree_notes(elem); }),
+		obj.parent == null ? null : serializeTree(obj.parent)
+	];
+}
+
+function deserializeTree_label(obj: any): Tree_label | null {
+	if (obj == null) { return null; }
+	return {
+		color: obj[0],
+		text: obj[1]
+	};
+}
+
+function deserializeTree_notes(obj: any): Tree_notes | null {
+	if (obj == null) { return null; }
+	return {
+		text: obj[0]
+	};
+}
+
+export function deserializeTree(obj: any): Tree | null {
+	if (obj == null) { return null; }
+	return {
+		children: obj[0].map(function (elem: any) { return deserializeTree(elem); }),
+		label: deserializeTree_label(obj[1]),
+		notes: obj[2]?.map(function (elem: any) { return deserializeTree_notes(elem); }),
+		parent: deserializeTree(obj[3])
+	};
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const Status = {
+	ACTIVE: 0,
+	DISABLED: 1,
+	DELETED: 2
+} as const;
+
+/* ============ End Enums ============ */
+
+/** Users must implement this interface to use `ZetroQuery` and `ZetroMutation` */
+export interface IZetroClient {
+	/**
+	 * Body is array-encoded data for the request. The return value MUST be the
+	 * response JSON body of the server. Implementors can add authentication
+	 * information to the request. This interface can be easily mocked for tests.
+	 * Note that a non-200 response status (even in the case of a malformed request)
+	 * MUST be considered an unexpected error and be handled accordingly.
+	 * In other words, only forward the parsed JSON body to this method if the
+	 * status is 200 OK.
+	 */
+	makeRequest: (body: any) => Promise<any>;
+}
+
+/* ============ Queries ============ */
+
+export class ZetroQuery<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	findTree(requestBody: Tree[]): ZetroQuery<T & {findTree: Tree}> {
+		this.state_.push(["ThjA6s-HKHz_sXk69anaU1hNRn4", requestBody.map(function (elem: any) { return serializeTree(elem); })]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.findTree = deserializeTree(item[1]);
+		})
+		return this as any;
+	}
+	getPrimitives(requestBody: number): ZetroQuery<T & {getPrimitives: Primitives}> {
+		this.state_.push(["mIQuSzSjfIc0SMiD5GOKVubn5yU", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getPrimitives = deserializePrimitives(item[1]);
+		})
+		return this as any;
+	}
+	getStatus(requestBody?: string): ZetroQuery<T & {getStatus: number}> {
+		this.state_.push(["MfZjc6qr3q2voF7lAnM0q9i-xF0", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getStatus = item[1];
+		})
+		return this as any;
+	}
+	getTrees(requestBody: Primitives): ZetroQuery<T & {getTrees: Tree[]}> {
+		this.state_.push(["P2xqdlJPNe-SDjR33ar2uo3AefI", serializePrimitives(requestBody)]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.getTrees = item[1].map(function (elem: any) { return deserializeTree(elem); });
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([1, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Queries ============ */
+
+/* ============ Mutations ============ */
+
+export class ZetroMutation<T = unknown> {
+	private state_: any[] = [];
+	private parsers_: ((returnObject: any, item: any) => void)[] = [];
+	private readonly client_: IZetroClient;
+
+	constructor(client: IZetroClient) {
+		this.client_ = client;
+	}
+
+	delete(requestBody: number[]): ZetroMutation<T & {delete: boolean}> {
+		this.state_.push(["YkwVdcYEoSGmiXeqTO3aHPhG4FE", requestBody]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.delete = item[1];
+		})
+		return this as any;
+	}
+	saveModifiers(requestBody?: Modifiers[]): ZetroMutation<T & {saveModifiers: Modifiers[]}> {
+		this.state_.push(["43xA5uwSorvlcK0CWr2xmBRIT6A", requestBody?.map(function (elem: any) { return serializeModifiers(elem); })]);
+		this.parsers_.push(function (resultObj: any, item: any) {
+			resultObj.saveModifiers = item[1]?.map(function (elem: any) { return deserializeModifiers(elem); });
+		})
+		return this as any;
+	}
+
+/*
+ * Excecutes the request and returns the response.
+ * If the call was unsuccessful, an error of type ZetroServerError
+ * will be thrown.
+ */
+	async fetch(): Promise<T> {
+		try {
+			const result = await this.client_.makeRequest([2, this.state_]);
+			if (result[1] != null) {
+				// Error
+				throw {code: result[1][0], message: result[1][1]}
+			}
+			const data = result[0];
+			const returnObject = {};
+			for (let i = 0; i < data.length; i++) {
+				this.parsers_[i](returnObject, data[i]);
+			}
+			return returnObject as any;
+		} catch (e) {
+			throw {code: e.code || -1, message: e.message || "An unexpected error occurred."};
+		}
+}
+}
+
+/* ============ End Mutations ============ */
\ No newline at end of file
@@ -0,0 +1,305 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/// AUTOGENERATED. The struct that serializes error states
+#[derive(Debug, Clone, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZetroServerError {
+	/// Arbitrary, human-readable message for the end user
+	pub message: String,
+	/// Arbitrary, response code for frontend logic
+	pub code: i16,
+}
+
+/// Every combination of nullable and multiple
+/// with a multi-line description
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Modifiers {
+	pub many_primitives: Vec<Primitives>,
+	pub multiple: Vec<String>,
+	pub nullable: Option<String>,
+	pub nullable_many_primitives: Option<Vec<Primitives>>,
+	pub nullable_multiple: Option<Vec<String>>,
+	pub nullable_primitives: Option<Primitives>,
+	pub nullable_status: Option<Status>,
+	pub nullable_statuses: Option<Vec<Status>>,
+	pub plain: String,
+	pub primitives: Primitives,
+	pub status: Status,
+	pub statuses: Vec<Status>,
+}
+
+/// Every primitive type
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Primitives {
+	pub boolean: bool,
+	pub float_32: f32,
+	pub float_64: f64,
+	pub int_16: i16,
+	pub int_32: i32,
+	pub int_64: i64,
+	pub int_8: i8,
+	/// A documented field
+	pub text: String,
+	pub uint_16: u16,
+	pub uint_32: u32,
+	pub uint_64: u64,
+	pub uint_8: u8,
+}
+
+#[allow(non_camel_case_types)]
+/// A nested object
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree_label {
+	pub color: Option<Status>,
+	pub text: String,
+}
+
+#[allow(non_camel_case_types)]
+/// A nullable list of nested objects
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree_notes {
+	pub text: String,
+}
+
+/// A recursive struct with nested objects
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree {
+	pub children: Vec<Box<Tree>>,
+	pub label: Tree_label,
+	pub notes: Option<Vec<Tree_notes>>,
+	pub parent: Option<Box<Tree>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum Status {
+	Active = 0,
+	Disabled = 1,
+	Deleted = 2,
+}
+
+use warp::Filter;
+
+impl warp::reject::Reject for ZetroServerError {}
+
+pub struct ZetroContext {
+	data: std::collections::HashMap<std::any::TypeId, Box<dyn std::any::Any + Sync + Send>>,
+}
+
+impl ZetroContext {
+	pub fn new() -> Self {
+		ZetroContext {
+			data: std::collections::HashMap::new(),
+		}
+	}
+
+	pub fn insert<T>(&mut self, item: T)
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data.insert(std::any::TypeId::of::<T>(), Box::new(item));
+	}
+    
+	pub fn get<T>(&self) -> &T
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data
+			.get(&std::any::TypeId::of::<T>())
+			.unwrap()
+			.downcast_ref::<T>()
+			.unwrap()
+		}
+}
+
+fn _generate_data_reply(data: Vec<serde_json::Value>) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&data,
+		&serde_json::Value::Null,
+	))
+	.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+fn _generate_error_reply(code: i16, message: &str) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&serde_json::Value::Null,
+		&(&code, message),
+		))
+		.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+#[async_trait::async_trait]
+pub trait ZetroQueries {
+	async fn find_tree<'a>(ctx: &'a ZetroContext, request: Vec<Tree>) -> Result<Option<Tree>, ZetroServerError>;
+
+	async fn get_primitives<'a>(ctx: &'a ZetroContext, request: u64) -> Result<Primitives, ZetroServerError>;
+
+	async fn get_status<'a>(ctx: &'a ZetroContext, request: Option<String>) -> Result<Status, ZetroServerError>;
+
+	async fn get_trees<'a>(ctx: &'a ZetroContext, request: Primitives) -> Result<Vec<Tree>, ZetroServerError>;
+}
+
+#[async_trait::async_trait]
+pub trait ZetroMutations {
+	async fn delete<'a>(ctx: &'a ZetroContext, request: Vec<u64>) -> Result<bool, ZetroServerError>;
+
+	async fn save_modifiers<'a>(ctx: &'a ZetroContext, request: Option<Vec<Modifiers>>) -> Result<Option<Vec<Modifiers>>, ZetroServerError>;
+}
+
+pub fn generate_routes<Q, M>(ctx: ZetroContext, _queries: Q, _mutations: M) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone
+where Q: ZetroQueries, M: ZetroMutations,
+{
+        use std::sync::Arc;
+
+        let ctx = Arc::new(ctx);
+
+        warp::any()
+            .and(warp::any().map(move || Arc::clone(&ctx)))
+            .and(warp::body::bytes())
+            .then(|ctx: Arc<ZetroContext>, body: bytes::Bytes| async move {
+                let mut retval: Vec<serde_json::Value> = Vec::new();
+                let request_payload = serde_json::from_slice::<(u8, Vec<serde_json::Value>)>(&body);
+                if request_payload.is_err() {
+                    return _generate_error_reply(400, "Bad request");
+                }
+                // Determines whether the request is a query or mutation
+                let (method_code, operations) = request_payload.unwrap();
+                for op in operations {
+                    if !op.is_array() {
+                        return _generate_error_reply(400, "Operations must be an array");
+                    }
+                    let arr = op.as_array().unwrap();
+                    let route_name = arr.get(0);
+                    let route_body = arr.get(1);
+
+                    if route_name.is_none() || route_body.is_none() {
+                        return _generate_error_reply(400, "Route name and route body are mandatory");
+                    }
+
+                    let route_name = route_name.unwrap();
+                    let route_body = route_body.unwrap().to_owned();
+
+                    if !route_name.is_string() {
+                        return _generate_error_reply(400, "Route name must be string");
+                    }
+                    let route_name = route_name.as_str().unwrap();
+                    if method_code == 1 {
+                        // Handle query
+                        retval.push(match route_name {
+                            
+                        // 'FindTree' route:
+                        "ThjA6s-HKHz_sXk69anaU1hNRn4" => {
+                            let route_body = serde_json::from_value::<Vec<Tree>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::find_tree(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetPrimitives' route:
+                        "mIQuSzSjfIc0SMiD5GOKVubn5yU" => {
+                            let route_body = serde_json::from_value::<u64>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_primitives(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetStatus' route:
+                        "MfZjc6qr3q2voF7lAnM0q9i-xF0" => {
+                            let route_body = serde_json::from_value::<Option<String>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_status(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetTrees' route:
+                        "P2xqdlJPNe-SDjR33ar2uo3AefI" => {
+                            let route_body = serde_json::from_value::<Primitives>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_trees(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else if method_code == 2 {
+                        // Handle mutation
+                        retval.push(match route_name {
+                            
+                        // 'Delete' route:
+                        "YkwVdcYEoSGmiXeqTO3aHPhG4FE" => {
+                            let route_body = serde_json::from_value::<Vec<u64>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = M::delete(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'SaveModifiers' route:
+                        "43xA5uwSorvlcK0CWr2xmBRIT6A" => {
+                            let route_body = serde_json::from_value::<Option<Vec<Modifiers>>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = M::save_modifiers(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else {
+                        return _generate_error_reply(400, "Bad request");
+                    }
+                }
+                _generate_data_reply(retval)
+            })
+        }
\ No newline at end of file
@@ -0,0 +1,934 @@
+// This is an autogenerated file. DO NOT EDIT
+
+use serde::ser::SerializeTuple;
+
+/// AUTOGENERATED. The struct that serializes error states
+#[derive(Debug, Clone)]
+pub struct ZetroServerError {
+	/// Arbitrary, human-readable message for the end user
+	pub message: String,
+	/// Arbitrary, response code for frontend logic
+	pub code: i16,
+}
+
+/// Every combination of nullable and multiple
+/// with a multi-line description
+#[derive(Debug, Clone)]
+pub struct Modifiers {
+	pub many_primitives: Vec<Primitives>,
+	pub multiple: Vec<String>,
+	pub nullable: Option<String>,
+	pub nullable_many_primitives: Option<Vec<Primitives>>,
+	pub nullable_multiple: Option<Vec<String>>,
+	pub nullable_primitives: Option<Primitives>,
+	pub nullable_status: Option<Status>,
+	pub nullable_statuses: Option<Vec<Status>>,
+	pub plain: String,
+	pub primitives: Primitives,
+	pub status: Status,
+	pub statuses: Vec<Status>,
+}
+
+impl serde::ser::Serialize for Modifiers {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(12)?;
+		state.serialize_element(&self.many_primitives)?;
+		state.serialize_element(&self.multiple)?;
+		state.serialize_element(&self.nullable)?;
+		state.serialize_element(&self.nullable_many_primitives)?;
+		state.serialize_element(&self.nullable_multiple)?;
+		state.serialize_element(&self.nullable_primitives)?;
+		state.serialize_element(&self.nullable_status)?;
+		state.serialize_element(&self.nullable_statuses)?;
+		state.serialize_element(&self.plain)?;
+		state.serialize_element(&self.primitives)?;
+		state.serialize_element(&self.status)?;
+		state.serialize_element(&self.statuses)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Modifiers {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Modifiers;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let many_primitives = seq.next_element::<Vec<Primitives>>()?;
+				if many_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let many_primitives = many_primitives.unwrap();
+
+				let multiple = seq.next_element::<Vec<String>>()?;
+				if multiple.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let multiple = multiple.unwrap();
+
+				let nullable = seq.next_element::<Option<String>>()?;
+				if nullable.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable = nullable.unwrap();
+
+				let nullable_many_primitives = seq.next_element::<Option<Vec<Primitives>>>()?;
+				if nullable_many_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_many_primitives = nullable_many_primitives.unwrap();
+
+				let nullable_multiple = seq.next_element::<Option<Vec<String>>>()?;
+				if nullable_multiple.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_multiple = nullable_multiple.unwrap();
+
+				let nullable_primitives = seq.next_element::<Option<Primitives>>()?;
+				if nullable_primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_primitives = nullable_primitives.unwrap();
+
+				let nullable_status = seq.next_element::<Option<Status>>()?;
+				if nullable_status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_status = nullable_status.unwrap();
+
+				let nullable_statuses = seq.next_element::<Option<Vec<Status>>>()?;
+				if nullable_statuses.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let nullable_statuses = nullable_statuses.unwrap();
+
+				let plain = seq.next_element::<String>()?;
+				if plain.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let plain = plain.unwrap();
+
+				let primitives = seq.next_element::<Primitives>()?;
+				if primitives.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let primitives = primitives.unwrap();
+
+				let status = seq.next_element::<Status>()?;
+				if status.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let status = status.unwrap();
+
+				let statuses = seq.next_element::<Vec<Status>>()?;
+				if statuses.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let statuses = statuses.unwrap();
+
+				Ok(Modifiers {
+					many_primitives: many_primitives,
+					multiple: multiple,
+					nullable: nullable,
+					nullable_many_primitives: nullable_many_primitives,
+					nullable_multiple: nullable_multiple,
+					nullable_primitives: nullable_primitives,
+					nullable_status: nullable_status,
+					nullable_statuses: nullable_statuses,
+					plain: plain,
+					primitives: primitives,
+					status: status,
+					statuses: statuses,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(12, Visitor)
+	}
+}
+
+/// Every primitive type
+#[derive(Debug, Clone)]
+pub struct Primitives {
+	pub boolean: bool,
+	pub float_32: f32,
+	pub float_64: f64,
+	pub int_16: i16,
+	pub int_32: i32,
+	pub int_64: i64,
+	pub int_8: i8,
+	/// A documented field
+	pub text: String,
+	pub uint_16: u16,
+	pub uint_32: u32,
+	pub uint_64: u64,
+	pub uint_8: u8,
+}
+
+impl serde::ser::Serialize for Primitives {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(12)?;
+		state.serialize_element(&self.boolean)?;
+		state.serialize_element(&self.float_32)?;
+		state.serialize_element(&self.float_64)?;
+		state.serialize_element(&self.int_16)?;
+		state.serialize_element(&self.int_32)?;
+		state.serialize_element(&self.int_64)?;
+		state.serialize_element(&self.int_8)?;
+		state.serialize_element(&self.text)?;
+		state.serialize_element(&self.uint_16)?;
+		state.serialize_element(&self.uint_32)?;
+		state.serialize_element(&self.uint_64)?;
+		state.serialize_element(&self.uint_8)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Primitives {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Primitives;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let boolean = seq.next_element::<bool>()?;
+				if boolean.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let boolean = boolean.unwrap();
+
+				let float_32 = seq.next_element::<f32>()?;
+				if float_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let float_32 = float_32.unwrap();
+
+				let float_64 = seq.next_element::<f64>()?;
+				if float_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let float_64 = float_64.unwrap();
+
+				let int_16 = seq.next_element::<i16>()?;
+				if int_16.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_16 = int_16.unwrap();
+
+				let int_32 = seq.next_element::<i32>()?;
+				if int_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_32 = int_32.unwrap();
+
+				let int_64 = seq.next_element::<i64>()?;
+				if int_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_64 = int_64.unwrap();
+
+				let int_8 = seq.next_element::<i8>()?;
+				if int_8.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let int_8 = int_8.unwrap();
+
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				let uint_16 = seq.next_element::<u16>()?;
+				if uint_16.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_16 = uint_16.unwrap();
+
+				let uint_32 = seq.next_element::<u32>()?;
+				if uint_32.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_32 = uint_32.unwrap();
+
+				let uint_64 = seq.next_element::<u64>()?;
+				if uint_64.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_64 = uint_64.unwrap();
+
+				let uint_8 = seq.next_element::<u8>()?;
+				if uint_8.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let uint_8 = uint_8.unwrap();
+
+				Ok(Primitives {
+					boolean: boolean,
+					float_32: float_32,
+					float_64: float_64,
+					int_16: int_16,
+					int_32: int_32,
+					int_64: int_64,
+					int_8: int_8,
+					text: text,
+					uint_16: uint_16,
+					uint_32: uint_32,
+					uint_64: uint_64,
+					uint_8: uint_8,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(12, Visitor)
+	}
+}
+
+#[allow(non_camel_case_types)]
+/// A nested object
+#[derive(Debug, Clone)]
+pub struct Tree_label {
+	pub color: Option<Status>,
+	pub text: String,
+}
+
+#[allow(non_camel_case_types)]
+/// A nullable list of nested objects
+#[derive(Debug, Clone)]
+pub struct Tree_notes {
+	pub text: String,
+}
+
+/// A recursive struct with nested objects
+#[derive(Debug, Clone)]
+pub struct Tree {
+	pub children: Vec<Box<Tree>>,
+	pub label: Tree_label,
+	pub notes: Option<Vec<Tree_notes>>,
+	pub parent: Option<Box<Tree>>,
+}
+
+impl serde::ser::Serialize for Tree_label {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(2)?;
+		state.serialize_element(&self.color)?;
+		state.serialize_element(&self.text)?;
+		state.end()    
+	}
+}
+
+impl serde::ser::Serialize for Tree_notes {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(1)?;
+		state.serialize_element(&self.text)?;
+		state.end()    
+	}
+}
+
+impl serde::ser::Serialize for Tree {
+fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where S: serde::ser::Serializer,
+	{
+		let mut state = serializer.serialize_tuple(4)?;
+		state.serialize_element(&self.children)?;
+		state.serialize_element(&self.label)?;
+		state.serialize_element(&self.notes)?;
+		state.serialize_element(&self.parent)?;
+		state.end()    
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree_label {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree_label;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let color = seq.next_element::<Option<Status>>()?;
+				if color.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let color = color.unwrap();
+
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				Ok(Tree_label {
+					color: color,
+					text: text,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(2, Visitor)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree_notes {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree_notes;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let text = seq.next_element::<String>()?;
+				if text.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let text = text.unwrap();
+
+				Ok(Tree_notes {
+					text: text,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(1, Visitor)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for Tree {
+fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Tree;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				write!(formatter, "")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+			where A: serde::de::SeqAccess<'de>,
+			{
+				let children = seq.next_element::<Vec<Box<Tree>>>()?;
+				if children.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let children = children.unwrap();
+
+				let label = seq.next_element::<Tree_label>()?;
+				if label.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let label = label.unwrap();
+
+				let notes = seq.next_element::<Option<Vec<Tree_notes>>>()?;
+				if notes.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let notes = notes.unwrap();
+
+				let parent = seq.next_element::<Option<Box<Tree>>>()?;
+				if parent.is_none() {
+					return Err(serde::de::Error::custom("invalid field"));
+				}
+				let parent = parent.unwrap();
+
+				Ok(Tree {
+					children: children,
+					label: label,
+					notes: notes,
+					parent: parent,
+				})
+			}
+		}
+		deserializer.deserialize_tuple(4, Visitor)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum Status {
+	Active = 0,
+	Disabled = 1,
+	Deleted = 2,
+}
+
+use warp::Filter;
+
+impl warp::reject::Reject for ZetroServerError {}
+
+pub struct ZetroContext {
+	data: fnv::FnvHashMap<std::any::TypeId, Box<dyn std::any::Any + Sync + Send>>,
+}
+
+impl ZetroContext {
+	pub fn new() -> Self {
+		ZetroContext {
+			data: fnv::FnvHashMap::default(),
+		}
+	}
+
+	pub fn insert<T>(&mut self, item: T)
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data.insert(std::any::TypeId::of::<T>(), Box::new(item));
+	}
+    
+	pub fn get<T>(&self) -> &T
+	where T: std::any::Any + Sync + Send,
+	{
+		self.data
+			.get(&std::any::TypeId::of::<T>())
+			.unwrap()
+			.downcast_ref::<T>()
+			.unwrap()
+		}
+}
+
+fn _generate_data_reply(data: Vec<serde_json::Value>) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&data,
+		&serde_json::Value::Null,
+	))
+	.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+fn _generate_error_reply(code: i16, message: &str) -> warp::reply::Response {
+	let serialized = serde_json::to_string(&(
+		&serde_json::Value::Null,
+		&(&code, message),
+		))
+		.unwrap();
+
+	warp::http::Response::builder()
+		.status(200)
+		.body(warp::hyper::body::Body::from(serialized))
+		.unwrap()
+}
+
+#[async_trait::async_trait]
+pub trait ZetroQueries {
+	async fn find_tree<'a>(ctx: &'a ZetroContext, request: Vec<Tree>) -> Result<Option<Tree>, ZetroServerError>;
+
+	async fn get_primitives<'a>(ctx: &'a ZetroContext, request: u64) -> Result<Primitives, ZetroServerError>;
+
+	async fn get_status<'a>(ctx: &'a ZetroContext, request: Option<String>) -> Result<Status, ZetroServerError>;
+
+	async fn get_trees<'a>(ctx: &'a ZetroContext, request: Primitives) -> Result<Vec<Tree>, ZetroServerError>;
+}
+
+#[async_trait::async_trait]
+pub trait ZetroMutations {
+	async fn delete<'a>(ctx: &'a ZetroContext, request: Vec<u64>) -> Result<bool, ZetroServerError>;
+
+	async fn save_modifiers<'a>(ctx: &'a ZetroContext, request: Option<Vec<Modifiers>>) -> Result<Option<Vec<Modifiers>>, ZetroServerError>;
+}
+
+pub fn generate_routes<Q, M>(ctx: ZetroContext, _queries: Q, _mutations: M) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone
+where Q: ZetroQueries, M: ZetroMutations,
+{
+        use std::sync::Arc;
+
+        let ctx = Arc::new(ctx);
+
+        warp::any()
+            .and(warp::any().map(move || Arc::clone(&ctx)))
+            .and(warp::body::bytes())
+            .then(|ctx: Arc<ZetroContext>, body: bytes::Bytes| async move {
+                let mut retval: Vec<serde_json::Value> = Vec::new();
+                let request_payload = serde_json::from_slice::<(u8, Vec<serde_json::Value>)>(&body);
+                if request_payload.is_err() {
+                    return _generate_error_reply(400, "Bad request");
+                }
+                // Determines whether the request is a query or mutation
+                let (method_code, operations) = request_payload.unwrap();
+                for op in operations {
+                    if !op.is_array() {
+                        return _generate_error_reply(400, "Operations must be an array");
+                    }
+                    let arr = op.as_array().unwrap();
+                    let route_name = arr.get(0);
+                    let route_body = arr.get(1);
+
+                    if route_name.is_none() || route_body.is_none() {
+                        return _generate_error_reply(400, "Route name and route body are mandatory");
+                    }
+
+                    let route_name = route_name.unwrap();
+                    let route_body = route_body.unwrap().to_owned();
+
+                    if !route_name.is_string() {
+                        return _generate_error_reply(400, "Route name must be string");
+                    }
+                    let route_name = route_name.as_str().unwrap();
+                    if method_code == 1 {
+                        // Handle query
+                        retval.push(match route_name {
+                            
+                        // 'FindTree' route:
+                        "ThjA6s-HKHz_sXk69anaU1hNRn4" => {
+                            let route_body = serde_json::from_value::<Vec<Tree>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::find_tree(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetPrimitives' route:
+                        "mIQuSzSjfIc0SMiD5GOKVubn5yU" => {
+                            let route_body = serde_json::from_value::<u64>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_primitives(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetStatus' route:
+                        "MfZjc6qr3q2voF7lAnM0q9i-xF0" => {
+                            let route_body = serde_json::from_value::<Option<String>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_status(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'GetTrees' route:
+                        "P2xqdlJPNe-SDjR33ar2uo3AefI" => {
+                            let route_body = serde_json::from_value::<Primitives>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = Q::get_trees(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else if method_code == 2 {
+                        // Handle mutation
+                        retval.push(match route_name {
+                            
+                        // 'Delete' route:
+                        "YkwVdcYEoSGmiXeqTO3aHPhG4FE" => {
+                            let route_body = serde_json::from_value::<Vec<u64>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = M::delete(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+
+                        // 'SaveModifiers' route:
+                        "43xA5uwSorvlcK0CWr2xmBRIT6A" => {
+                            let route_body = serde_json::from_value::<Option<Vec<Modifiers>>>(route_body);
+                            if route_body.is_err() {
+                                return _generate_error_reply(400, "Bad request");
+                            }
+                            let route_body = route_body.unwrap();
+                            let result = M::save_modifiers(&ctx, route_body).await;
+                            match result {
+                                Err(e) => return _generate_error_reply(e.code, &e.message),
+                                Ok(d) => serde_json::to_value((route_name, &d)).unwrap()
+                            }
+                        }
+    
+                            _ => {
+                                return _generate_error_reply(400, "Unrecognized route name");
+                            }
+                        });
+                    } else {
+                        return _generate_error_reply(400, "Bad request");
+                    }
+                }
+                _generate_data_reply(retval)
+            })
+        }
+
+/// How deep `Arbitrary` values nest structs through nullable fields and lists
+#[cfg(test)]
+const ZETRO_MAX_DEPTH: u32 = 3;
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Modifiers {
+	type Parameters = u32;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	#[allow(unused_variables)]
+	fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		(
+			(
+			if depth < ZETRO_MAX_DEPTH { prop::collection::vec(any_with::<Primitives>(depth + 1), 0..4).boxed() } else { Just(Vec::new()).boxed() },
+			prop::collection::vec(any::<String>(), 0..4),
+			prop::option::of(any::<String>()),
+			if depth < ZETRO_MAX_DEPTH { prop::option::of(prop::collection::vec(any_with::<Primitives>(depth + 1), 0..4)).boxed() } else { Just(None).boxed() },
+			prop::option::of(prop::collection::vec(any::<String>(), 0..4)),
+			if depth < ZETRO_MAX_DEPTH { prop::option::of(any_with::<Primitives>(depth + 1)).boxed() } else { Just(None).boxed() },
+			prop::option::of(any::<Status>()),
+			prop::option::of(prop::collection::vec(any::<Status>(), 0..4)),
+			any::<String>(),
+			any_with::<Primitives>(depth + 1),
+		),
+			(
+			any::<Status>(),
+			prop::collection::vec(any::<Status>(), 0..4),
+		),
+		)
+			.prop_map(|((many_primitives, multiple, nullable, nullable_many_primitives, nullable_multiple, nullable_primitives, nullable_status, nullable_statuses, plain, primitives,), (status, statuses,),)| Modifiers { many_primitives, multiple, nullable, nullable_many_primitives, nullable_multiple, nullable_primitives, nullable_status, nullable_statuses, plain, primitives, status, statuses })
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+impl PartialEq for Modifiers {
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {
+		self.many_primitives == other.many_primitives
+			&& self.multiple == other.multiple
+			&& self.nullable == other.nullable
+			&& self.nullable_many_primitives == other.nullable_many_primitives
+			&& self.nullable_multiple == other.nullable_multiple
+			&& self.nullable_primitives == other.nullable_primitives
+			&& self.nullable_status == other.nullable_status
+			&& self.nullable_statuses == other.nullable_statuses
+			&& self.plain == other.plain
+			&& self.primitives == other.primitives
+			&& self.status == other.status
+			&& self.statuses == other.statuses
+	}
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Primitives {
+	type Parameters = u32;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	#[allow(unused_variables)]
+	fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		(
+			(
+			any::<bool>(),
+			(-1_000_000i32..1_000_000).prop_map(|n| n as f32 / 4.0),
+			(-1_000_000i32..1_000_000).prop_map(|n| f64::from(n) / 4.0),
+			any::<i16>(),
+			any::<i32>(),
+			any::<i64>(),
+			any::<i8>(),
+			any::<String>(),
+			any::<u16>(),
+			any::<u32>(),
+		),
+			(
+			any::<u64>(),
+			any::<u8>(),
+		),
+		)
+			.prop_map(|((boolean, float_32, float_64, int_16, int_32, int_64, int_8, text, uint_16, uint_32,), (uint_64, uint_8,),)| Primitives { boolean, float_32, float_64, int_16, int_32, int_64, int_8, text, uint_16, uint_32, uint_64, uint_8 })
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+impl PartialEq for Primitives {
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {
+		self.boolean == other.boolean
+			&& self.float_32 == other.float_32
+			&& self.float_64 == other.float_64
+			&& self.int_16 == other.int_16
+			&& self.int_32 == other.int_32
+			&& self.int_64 == other.int_64
+			&& self.int_8 == other.int_8
+			&& self.text == other.text
+			&& self.uint_16 == other.uint_16
+			&& self.uint_32 == other.uint_32
+			&& self.uint_64 == other.uint_64
+			&& self.uint_8 == other.uint_8
+	}
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Tree_label {
+	type Parameters = u32;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	#[allow(unused_variables)]
+	fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		(
+			prop::option::of(any::<Status>()),
+			any::<String>(),
+		)
+			.prop_map(|(color, text,)| Tree_label { color, text })
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Tree_notes {
+	type Parameters = u32;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	#[allow(unused_variables)]
+	fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		(
+			any::<String>(),
+		)
+			.prop_map(|(text,)| Tree_notes { text })
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Tree {
+	type Parameters = u32;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	#[allow(unused_variables)]
+	fn arbitrary_with(depth: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		(
+			if depth < ZETRO_MAX_DEPTH { prop::collection::vec(any_with::<Tree>(depth + 1).prop_map(Box::new), 0..4).boxed() } else { Just(Vec::new()).boxed() },
+			any_with::<Tree_label>(depth + 1),
+			if depth < ZETRO_MAX_DEPTH { prop::option::of(prop::collection::vec(any_with::<Tree_notes>(depth + 1), 0..4)).boxed() } else { Just(None).boxed() },
+			if depth < ZETRO_MAX_DEPTH { prop::option::of(any_with::<Tree>(depth + 1).prop_map(Box::new)).boxed() } else { Just(None).boxed() },
+		)
+			.prop_map(|(children, label, notes, parent,)| Tree { children, label, notes, parent })
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+impl PartialEq for Tree_label {
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {
+		self.color == other.color
+			&& self.text == other.text
+	}
+}
+
+#[cfg(test)]
+impl PartialEq for Tree_notes {
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {
+		self.text == other.text
+	}
+}
+
+#[cfg(test)]
+impl PartialEq for Tree {
+	#[allow(unused_variables)]
+	fn eq(&self, other: &Self) -> bool {
+		self.children == other.children
+			&& self.label == other.label
+			&& self.notes == other.notes
+			&& self.parent == other.parent
+	}
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Status {
+	type Parameters = ();
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+		use proptest::prelude::*;
+		prop::sample::select(vec![Status::Active, Status::Disabled, Status::Deleted]).boxed()
+	}
+}
+
+#[cfg(test)]
+mod zetro_round_trip {
+	#[allow(unused_imports)]
+	use super::*;
+	use proptest::prelude::*;
+
+	proptest! {
+		#[test]
+		fn round_trips_modifiers(value in any::<Modifiers>()) {
+			let json = serde_json::to_string(&value).unwrap();
+			let decoded: Modifiers = serde_json::from_str(&json).unwrap();
+			prop_assert_eq!(decoded, value);
+		}
+
+		#[test]
+		fn round_trips_primitives(value in any::<Primitives>()) {
+			let json = serde_json::to_string(&value).unwrap();
+			let decoded: Primitives = serde_json::from_str(&json).unwrap();
+			prop_assert_eq!(decoded, value);
+		}
+
+		#[test]
+		fn round_trips_tree(value in any::<Tree>()) {
+			let json = serde_json::to_string(&value).unwrap();
+			let decoded: Tree = serde_json::from_str(&json).unwrap();
+			prop_assert_eq!(decoded, value);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+// This is an autogenerated file. DO NOT EDIT
+
+import { Primitives, deserializePrimitives, serializePrimitives } from "./shared";
+
+/* ============ Structs ============ */
+
+/** Every combination of nullable and multiple
+with a multi-line description */
+export interface Modifiers {
+	manyPrimitives: Primitives[],
+	multiple: string[],
+	nullable?: string,
+	nullableManyPrimitives?: Primitives[],
+	nullableMultiple?: string[],
+	nullablePrimitives?: Primitives,
+	nullableStatus?: number,
+	nullableStatuses?: number[],
+	plain: string,
+	primitives: Primitives,
+	status: number,
+	statuses: number[],
+}
+
+export function serializeModifiers(obj: Modifiers): any[] | null {
+	return [
+		obj.manyPrimitives.map(function (nested) { return serializePrimitives(nested); }),
+		obj.multiple,
+		obj.nullable,
+		obj.nullableManyPrimitives?.map(function (nested) { return serializePrimitives(nested); }),
+		obj.nullableMultiple,
+		obj.nullablePrimitives == null ? null : serializePrimitives(obj.nullablePrimitives),
+		obj.nullableStatus,
+		obj.nullableStatuses,
+		obj.plain,
+		serializePrimitives(obj.primitives),
+		obj.status,
+		obj.statuses
+	];
+}
+
+export function deserializeModifiers(obj: any): Modifiers | null {
+	if (obj == null) { return null; }
+	return {
+		manyPrimitives: obj[0].map(function (elem: any) { return deserializePrimitives(elem); }),
+		multiple: obj[1],
+		nullable: obj[2],
+		nullableManyPrimitives: obj[3]?.map(function (elem: any) { return deserializePrimitives(elem); }),
+		nullableMultiple: obj[4],
+		nullablePrimitives: deserializePrimitives(obj[5]),
+		nullableStatus: obj[6],
+		nullableStatuses: obj[7],
+		plain: obj[8],
+		primitives: deserializePrimitives(obj[9]),
+		status: obj[10],
+		statuses: obj[11]
+	};
+}
+
+/* ============ End Structs ============ */
\ No newline at end of file
@@ -0,0 +1,134 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/* ============ Structs ============ */
+
+/** Every primitive type */
+export interface Primitives {
+	boolean: boolean,
+	float32: number,
+	float64: number,
+	int16: number,
+	int32: number,
+	int64: number,
+	int8: number,
+	/** A documented field */
+	text: string,
+	uint16: number,
+	uint32: number,
+	uint64: number,
+	uint8: number,
+}
+
+export function serializePrimitives(obj: Primitives): any[] | null {
+	return [
+		obj.boolean,
+		obj.float32,
+		obj.float64,
+		obj.int16,
+		obj.int32,
+		obj.int64,
+		obj.int8,
+		obj.text,
+		obj.uint16,
+		obj.uint32,
+		obj.uint64,
+		obj.uint8
+	];
+}
+
+export function deserializePrimitives(obj: any): Primitives | null {
+	if (obj == null) { return null; }
+	return {
+		boolean: obj[0],
+		float32: obj[1],
+		float64: obj[2],
+		int16: obj[3],
+		int32: obj[4],
+		int64: obj[5],
+		int8: obj[6],
+		text: obj[7],
+		uint16: obj[8],
+		uint32: obj[9],
+		uint64: obj[10],
+		uint8: obj[11]
+	};
+}
+
+/** A nested object */
+interface Tree_label {
+	color?: number,
+	text: string,
+}
+
+/** A nullable list of nested objects */
+interface Tree_notes {
+	text: string,
+}
+
+/** A recursive struct with nested objects */
+export interface Tree {
+	children: Tree[],
+	label: Tree_label,
+	notes?: Tree_notes[],
+	parent?: Tree,
+}
+
+function serializeTree_label(obj: Tree_label): any[] | null {
+	return [
+		obj.color,
+		obj.text
+	];
+}
+
+function serializeTree_notes(obj?: Tree_notes): any[] | null {
+if (obj == null) { return null; }
+	return [
+		obj.text
+	];
+}
+
+export function serializeTree(obj: Tree): any[] | null {
+	return [
+		obj.children.map(function (nested) { return serializeTree(nested); }),
+		serializeTree_label(obj.label),
+		obj.notes?.map(function (elem: any) { return serializeTree_notes(elem); }),
+		obj.parent == null ? null : serializeTree(obj.parent)
+	];
+}
+
+function deserializeTree_label(obj: any): Tree_label | null {
+	if (obj == null) { return null; }
+	return {
+		color: obj[0],
+		text: obj[1]
+	};
+}
+
+function deserializeTree_notes(obj: any): Tree_notes | null {
+	if (obj == null) { return null; }
+	return {
+		text: obj[0]
+	};
+}
+
+export function deserializeTree(obj: any): Tree | null {
+	if (obj == null) { return null; }
+	return {
+		children: obj[0].map(function (elem: any) { return deserializeTree(elem); }),
+		label: deserializeTree_label(obj[1]),
+		notes: obj[2]?.map(function (elem: any) { return deserializeTree_notes(elem); }),
+		parent: deserializeTree(obj[3])
+	};
+}
+
+/* ============ End Structs ============ */
+
+/* ============ Enums ============ */
+
+export const Status = {
+	ACTIVE: 0,
+	DISABLED: 1,
+	DELETED: 2
+} as const;
+
+/* ============ End Enums ============ */
\ No newline at end of file
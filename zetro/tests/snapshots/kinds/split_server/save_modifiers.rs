@@ -0,0 +1,22 @@
+// This is an autogenerated file. DO NOT EDIT
+
+use super::shared::{Primitives, Status};
+
+/// Every combination of nullable and multiple
+/// with a multi-line description
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Modifiers {
+	pub many_primitives: Vec<Primitives>,
+	pub multiple: Vec<String>,
+	pub nullable: Option<String>,
+	pub nullable_many_primitives: Option<Vec<Primitives>>,
+	pub nullable_multiple: Option<Vec<String>>,
+	pub nullable_primitives: Option<Primitives>,
+	pub nullable_status: Option<Status>,
+	pub nullable_statuses: Option<Vec<Status>>,
+	pub plain: String,
+	pub primitives: Primitives,
+	pub status: Status,
+	pub statuses: Vec<Status>,
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+// This is an autogenerated file. DO NOT EDIT
+
+/// Every primitive type
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Primitives {
+	pub boolean: bool,
+	pub float_32: f32,
+	pub float_64: f64,
+	pub int_16: i16,
+	pub int_32: i32,
+	pub int_64: i64,
+	pub int_8: i8,
+	/// A documented field
+	pub text: String,
+	pub uint_16: u16,
+	pub uint_32: u32,
+	pub uint_64: u64,
+	pub uint_8: u8,
+}
+
+#[allow(non_camel_case_types)]
+/// A nested object
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree_label {
+	pub color: Option<Status>,
+	pub text: String,
+}
+
+#[allow(non_camel_case_types)]
+/// A nullable list of nested objects
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree_notes {
+	pub text: String,
+}
+
+/// A recursive struct with nested objects
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tree {
+	pub children: Vec<Box<Tree>>,
+	pub label: Tree_label,
+	pub notes: Option<Vec<Tree_notes>>,
+	pub parent: Option<Box<Tree>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum Status {
+	Active = 0,
+	Disabled = 1,
+	Deleted = 2,
+}
\ No newline at end of file
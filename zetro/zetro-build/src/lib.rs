@@ -0,0 +1,117 @@
+//! Generates Zetro code from a `build.rs`, so that Rust servers don't have to
+//! commit generated files:
+//!
+//! ```no_run
+//! // build.rs
+//! let mut options = zetro_build::Options::new(zetro_build::Language::Rust);
+//! options.untagged = true;
+//! options.plugins.push(zetro_build::Plugin::new("warp").arg("fnv", true));
+//!
+//! zetro_build::generate("../schema.json", &options, "api_generated.rs").unwrap();
+//! ```
+//!
+//! The generated file is then included from the crate:
+//!
+//! ```ignore
+//! #[allow(dead_code)]
+//! mod api {
+//!     include!(concat!(env!("OUT_DIR"), "/api_generated.rs"));
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+pub use zetro::{FieldCasing, Language, Options, Plugin};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The schema is invalid, or the options are
+    Zetro(zetro::Error),
+    /// `OUT_DIR` is not set, ie. the caller is not a build script
+    OutDir(std::env::VarError),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Zetro(e) => write!(f, "{}", e),
+            Error::OutDir(e) => write!(f, "OUT_DIR: {}", e),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generates code for the schema at `schema` into `$OUT_DIR/<out_file>`, and
+/// makes cargo rerun the build script when the schema (or template) changes. Returns the path
+/// of the generated file.
+///
+/// Rust code is generated without inner attributes, since it can't be
+/// `include!`d otherwise.
+pub fn generate(
+    schema: impl AsRef<Path>,
+    options: &Options,
+    out_file: &str,
+) -> Result<PathBuf, Error> {
+    let out_dir = std::env::var("OUT_DIR").map_err(Error::OutDir)?;
+    println!("cargo:rerun-if-changed={}", schema.as_ref().display());
+    if let Some(template) = &options.template {
+        println!("cargo:rerun-if-changed={}", template.display());
+    }
+
+    generate_into(
+        schema.as_ref(),
+        options,
+        &Path::new(&out_dir).join(out_file),
+    )
+}
+
+fn generate_into(schema: &Path, options: &Options, out_file: &Path) -> Result<PathBuf, Error> {
+    let schema = zetro::load_schema(schema).map_err(Error::Zetro)?;
+
+    let mut options = options.clone();
+    options.inner_attributes = false;
+    let code = zetro::generate(&schema, &options).map_err(Error::Zetro)?;
+
+    // Leave unchanged files alone so that their modification time doesn't
+    // trigger rebuilds
+    if std::fs::read_to_string(out_file).ok().as_deref() != Some(code.as_str()) {
+        std::fs::write(out_file, code).map_err(Error::Io)?;
+    }
+    Ok(out_file.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_into, Error, Language, Options};
+
+    #[test]
+    fn writes_includable_code() {
+        let dir = std::env::temp_dir().join(format!("zetro-build-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let schema = dir.join("schema.json");
+        std::fs::write(
+            &schema,
+            r#"{ "structs": {}, "enums": {}, "routes": {
+                "Ping": { "kind": "query", "description": "Ping", "request": "u8", "response": "u8" }
+            } }"#,
+        )
+        .unwrap();
+
+        let out_file = dir.join("api_generated.rs");
+        let written = generate_into(&schema, &Options::new(Language::Rust), &out_file).unwrap();
+        let code = std::fs::read_to_string(written).unwrap();
+        assert!(!code.contains("#!["));
+
+        let missing = generate_into(
+            &dir.join("missing.json"),
+            &Options::new(Language::Rust),
+            &out_file,
+        );
+        assert!(matches!(missing, Err(Error::Zetro(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,209 @@
+//! Generates Zetro code at compile time, so that it never drifts from the
+//! schema:
+//!
+//! ```ignore
+//! #[allow(dead_code)]
+//! mod api {
+//!     zetro::schema!("schema.json", plugins = [warp(fnv = true)], untagged = true);
+//! }
+//! ```
+//!
+//! `zetro::schema!` forwards here, and needs this crate as a dependency too.
+//! The macro expands to the same code as `zetro generate` for a Rust target.
+//! The schema path is relative to the crate's `Cargo.toml`, and the crate is
+//! rebuilt when the schema changes.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, Lit, LitStr, Token,
+};
+
+/// `schema!("path", option = value, ...)`. Options:
+///
+/// - `untagged = <bool>`: Encode structs as arrays
+/// - `field_casing = "snake" | "camel"`
+/// - `keep_unused_types = <bool>`: Generate types that no route uses
+/// - `plugins = [name(arg = value, ...), ...]`: eg. `[warp(fnv = true)]`
+#[proc_macro]
+pub fn schema(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as SchemaInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct SchemaInput {
+    path: LitStr,
+    options: zetro::Options,
+}
+
+impl Parse for SchemaInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<LitStr>()?;
+        let mut options = zetro::Options::new(zetro::Language::Rust);
+        // Included code can't contain inner attributes
+        options.inner_attributes = false;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "untagged" => options.untagged = input.parse::<syn::LitBool>()?.value,
+                "keep_unused_types" => {
+                    options.keep_unused_types = input.parse::<syn::LitBool>()?.value
+                }
+                "field_casing" => {
+                    let casing = input.parse::<LitStr>()?;
+                    options.field_casing = Some(match casing.value().as_str() {
+                        "snake" => zetro::FieldCasing::Snake,
+                        "camel" => zetro::FieldCasing::Camel,
+                        _ => {
+                            return Err(syn::Error::new(
+                                casing.span(),
+                                "expected \"snake\" or \"camel\"",
+                            ))
+                        }
+                    });
+                }
+                "plugins" => {
+                    let content;
+                    bracketed!(content in input);
+                    let plugins = Punctuated::<PluginInput, Token![,]>::parse_terminated(&content)?;
+                    options.plugins = plugins.into_iter().map(|p| p.0).collect();
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "unknown option `{}`. Expected one of: untagged, field_casing, \
+                            keep_unused_types, plugins",
+                            name
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(Self { path, options })
+    }
+}
+
+/// `name` or `name(arg = value, ...)`. Plugin names may contain dashes, eg.
+/// `class-client`.
+struct PluginInput(zetro::Plugin);
+
+impl Parse for PluginInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = input.parse::<Ident>()?.to_string();
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            name.push('-');
+            name.push_str(&input.parse::<Ident>()?.to_string());
+        }
+        let mut plugin = zetro::Plugin::new(&name);
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            while !content.is_empty() {
+                let key = content.parse::<Ident>()?;
+                content.parse::<Token![=]>()?;
+                let value = match content.parse::<Lit>()? {
+                    Lit::Bool(v) => zetro::PluginArg::Bool(v.value),
+                    Lit::Int(v) => zetro::PluginArg::Int(v.base10_parse()?),
+                    Lit::Float(v) => zetro::PluginArg::Float(v.base10_parse()?),
+                    Lit::Str(v) => zetro::PluginArg::Str(v.value()),
+                    other => {
+                        return Err(syn::Error::new(
+                            other.span(),
+                            "expected a boolean, number or string",
+                        ))
+                    }
+                };
+                plugin = plugin.arg(&key.to_string(), value);
+
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+                }
+            }
+        }
+
+        Ok(Self(plugin))
+    }
+}
+
+fn expand(input: &SchemaInput) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |message: String| syn::Error::new(input.path.span(), message);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(input.path.value());
+
+    let schema = zetro::load_schema(&path).map_err(|e| error(e.to_string()))?;
+    let code = zetro::generate(&schema, &input.options).map_err(|e| error(e.to_string()))?;
+    let code = code
+        .parse::<proc_macro2::TokenStream>()
+        .map_err(|e| error(format!("generated code is invalid: {}", e)))?;
+
+    // Reading the schema through `include_bytes!` makes cargo rebuild the
+    // crate when it changes
+    let path = path.to_string_lossy();
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#path);
+        #code
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaInput;
+    use zetro::{FieldCasing, Plugin};
+
+    #[test]
+    fn parses_options() {
+        let input = syn::parse_str::<SchemaInput>(
+            r#""api/schema.json", untagged = true, keep_unused_types = true,
+            plugins = [warp(fnv = true, workers = 4, name = "api"), class-client],"#,
+        )
+        .unwrap();
+
+        assert_eq!(input.path.value(), "api/schema.json");
+        assert!(input.options.untagged);
+        assert!(input.options.keep_unused_types);
+        assert!(!input.options.inner_attributes);
+        assert_eq!(
+            input.options.plugins,
+            vec![
+                Plugin::new("warp")
+                    .arg("fnv", true)
+                    .arg("workers", 4_i64)
+                    .arg("name", "api"),
+                Plugin::new("class-client"),
+            ]
+        );
+
+        let input =
+            syn::parse_str::<SchemaInput>(r#""schema.json", field_casing = "camel""#).unwrap();
+        assert_eq!(input.options.field_casing, Some(FieldCasing::Camel));
+        assert!(input.options.plugins.is_empty());
+
+        for bad in [
+            r#""schema.json", untagged = "yes""#,
+            r#""schema.json", field_casing = "kebab""#,
+            r#""schema.json", lang = "ts""#,
+            r#""schema.json", plugins = [warp(fnv = [1])]"#,
+            r#"schema, untagged = true"#,
+        ] {
+            assert!(syn::parse_str::<SchemaInput>(bad).is_err(), "{}", bad);
+        }
+    }
+}
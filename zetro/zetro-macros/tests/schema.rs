@@ -0,0 +1,26 @@
+#[allow(dead_code)]
+mod api {
+    zetro_macros::schema!("tests/schema.json", untagged = true);
+}
+
+// Through the path that `zetro` exports
+#[allow(dead_code)]
+mod tagged {
+    zetro::schema!("tests/schema.json", field_casing = "camel");
+}
+
+/// The macro expands to the same code as a generated file
+#[test]
+fn expands_generated_code() {
+    let message = api::Message {
+        id: 7,
+        text: String::from("hi"),
+        status: api::Status::Read,
+    };
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value, serde_json::json!([7, 1, "hi"]));
+
+    let message: tagged::Message =
+        serde_json::from_value(serde_json::json!({ "id": 1, "text": "hi", "status": 0 })).unwrap();
+    assert_eq!(message.status, tagged::Status::Sent);
+}